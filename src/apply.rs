@@ -0,0 +1,372 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cmdline::{ApplyArgs, CliArgs};
+use crate::summary::{EXIT_PARTIAL_FAILURE, EXIT_TOTAL_FAILURE};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{Acquire, MySql, Pool};
+use std::path::Path;
+
+#[cfg(test)]
+pub(crate) mod test_apply;
+
+/// Prefix of the comment marking the LMX file of a block of queries
+const FILE_MARKER: &str = "-- Queries for file ";
+
+/// The statements of a single run, framed by START TRANSACTION and COMMIT in the SQL file
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SqlBlock {
+    /// LMX file named by the `-- Queries for file ...` marker of the block
+    pub source: Option<String>,
+    /// SQL file and line number of the START TRANSACTION of the block
+    pub location: String,
+    pub statements: Vec<String>,
+}
+
+impl SqlBlock {
+    /// Name of the block in messages, the LMX file if known
+    pub fn name(&self) -> &str {
+        self.source.as_deref().unwrap_or(&self.location)
+    }
+}
+
+/// A SQL file split into statements outside of any transaction, e.g. the
+/// schema fingerprint guard of the header, and the blocks of the runs
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SqlScript {
+    pub preamble: Vec<String>,
+    pub blocks: Vec<SqlBlock>,
+}
+
+/// Progress of `apply`, written after each committed block
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct ApplyProgress {
+    pub sql_file: String,
+    /// Number of blocks committed from the start of the SQL file
+    pub committed: usize,
+    /// Name of the last committed block
+    pub last_block: Option<String>,
+}
+
+impl ApplyProgress {
+    /// Reads the progress file, or returns no progress if it does not exist yet.
+    ///
+    /// # Errors
+    /// Returns an error if the progress file exists but cannot be read or parsed
+    ///
+    pub fn load(progress_file: &Path) -> Result<Self> {
+        if !progress_file.exists() {
+            return Ok(ApplyProgress::default());
+        }
+        let content = std::fs::read_to_string(progress_file)?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid progress file {}", progress_file.display()))
+    }
+
+    /// Writes the progress file via a temporary file, so that an interrupted
+    /// write never leaves a truncated progress file behind.
+    ///
+    /// # Errors
+    /// Returns an error if the progress file cannot be written
+    ///
+    pub fn save(&self, progress_file: &Path) -> Result<()> {
+        let temp_file = progress_file.with_extension("tmp");
+        std::fs::write(&temp_file, serde_json::to_string_pretty(self)? + "\n")?;
+        std::fs::rename(&temp_file, progress_file)?;
+        Ok(())
+    }
+}
+
+/// Splits SQL files into statements the way the mariadb client does:
+/// statements end with the current delimiter outside of quotes, and
+/// `DELIMITER` and `SOURCE` commands as well as comments start at the beginning of a line.
+struct ScriptParser {
+    script: SqlScript,
+    block: Option<SqlBlock>,
+    /// Whether START TRANSACTION and COMMIT frame the blocks of the runs.
+    /// Otherwise all statements are added to the preamble.
+    frame_blocks: bool,
+}
+
+impl ScriptParser {
+    /// Parses a single SQL file, files named by `SOURCE` commands are parsed
+    /// recursively. Like the mariadb client, relative paths are resolved
+    /// against the current directory, not the directory of the including file.
+    fn parse_file(&mut self, sql_file: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(sql_file)
+            .with_context(|| format!("Failed to read SQL file {}", sql_file.display()))?;
+        self.parse_text(&content, sql_file)
+    }
+
+    /// Parses the SQL text of `sql_file`
+    fn parse_text(&mut self, content: &str, sql_file: &Path) -> Result<()> {
+        let mut delimiter = ";".to_string();
+        let mut statement = String::new();
+        let mut statement_line = 0;
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let trimmed = line.trim();
+            if quote.is_none() && statement.trim().is_empty() {
+                let upper = trimmed.to_uppercase();
+                if trimmed.is_empty() {
+                    continue;
+                } else if let Some(source) = trimmed.strip_prefix(FILE_MARKER) {
+                    if let Some(block) = self.block.as_mut() {
+                        block.source = Some(source.trim_end_matches(';').to_string());
+                    }
+                    continue;
+                } else if trimmed.starts_with("--") || trimmed.starts_with('#') {
+                    continue;
+                } else if upper.starts_with("DELIMITER ") {
+                    delimiter = trimmed["DELIMITER ".len()..].trim().to_string();
+                    continue;
+                } else if upper.starts_with("SOURCE ") {
+                    let included = trimmed["SOURCE ".len()..].trim().trim_end_matches(';');
+                    self.parse_file(Path::new(included.trim()))?;
+                    continue;
+                }
+                statement.clear();
+                statement_line = line_number;
+            }
+            for c in line.chars().chain(std::iter::once('\n')) {
+                statement.push(c);
+                match (quote, c) {
+                    (Some(_), _) if escaped => escaped = false,
+                    (Some('\'' | '"'), '\\') => escaped = true,
+                    (Some(q), _) if q == c => quote = None,
+                    (Some(_), _) => {}
+                    (None, '\'' | '"' | '`') => quote = Some(c),
+                    (None, _) if statement.ends_with(delimiter.as_str()) => {
+                        statement.truncate(statement.len() - delimiter.len());
+                        let location = format!("{}:{}", sql_file.display(), statement_line);
+                        self.add_statement(statement.trim(), location)?;
+                        statement.clear();
+                        statement_line = line_number;
+                    }
+                    (None, _) => {}
+                }
+            }
+        }
+        if !statement.trim().is_empty() {
+            bail!(
+                "Unterminated statement at {}:{}",
+                sql_file.display(),
+                statement_line
+            );
+        }
+        Ok(())
+    }
+
+    /// Adds a complete statement to the current block or to the preamble
+    fn add_statement(&mut self, statement: &str, location: String) -> Result<()> {
+        if statement.is_empty() {
+            return Ok(());
+        }
+        if !self.frame_blocks {
+            self.script.preamble.push(statement.to_string());
+            return Ok(());
+        }
+        match (statement.to_uppercase().as_str(), self.block.take()) {
+            ("START TRANSACTION", None) => {
+                self.block = Some(SqlBlock {
+                    location,
+                    ..Default::default()
+                });
+            }
+            ("START TRANSACTION", Some(_)) => {
+                bail!("START TRANSACTION inside a transaction at {}", location)
+            }
+            ("COMMIT", Some(block)) => self.script.blocks.push(block),
+            ("COMMIT", None) => bail!("COMMIT without START TRANSACTION at {}", location),
+            (_, Some(mut block)) => {
+                block.statements.push(statement.to_string());
+                self.block = Some(block);
+            }
+            (_, None) => self.script.preamble.push(statement.to_string()),
+        }
+        Ok(())
+    }
+}
+
+/// Reads a SQL file written by lmx2db and splits it into the blocks of the runs.
+///
+/// # Arguments
+/// * `sql_file` - Path to the SQL file, or to the index file of per-run SQL files
+///
+/// # Returns
+/// Returns the statements outside of transactions and the blocks of the runs
+///
+/// # Errors
+/// Returns an error if a file cannot be read or its transactions are not properly framed
+///
+pub fn parse_sql_file(sql_file: &Path) -> Result<SqlScript> {
+    let mut parser = ScriptParser {
+        script: SqlScript::default(),
+        block: None,
+        frame_blocks: true,
+    };
+    parser.parse_file(sql_file)?;
+    if let Some(block) = parser.block {
+        bail!("Transaction at {} is not committed", block.location);
+    }
+    Ok(parser.script)
+}
+
+/// Splits SQL text, e.g. a schema file compiled into the binary, into
+/// statements. `DELIMITER` commands and comments are handled as for
+/// `parse_sql_file`, but transactions are not considered.
+///
+/// # Arguments
+/// * `content` - The SQL text
+/// * `name` - Name of the SQL text in error messages
+///
+/// # Errors
+/// Returns an error if the last statement is not terminated
+///
+pub fn split_sql_statements(content: &str, name: &str) -> Result<Vec<String>> {
+    let mut parser = ScriptParser {
+        script: SqlScript::default(),
+        block: None,
+        frame_blocks: false,
+    };
+    parser.parse_text(content, Path::new(name))?;
+    Ok(parser.script.preamble)
+}
+
+/// Loads a SQL file written by lmx2db into the database. Each block of a run
+/// is executed in a transaction of its own, and the progress is recorded after
+/// each commit. Loading stops at the first failing block, so that `--resume`
+/// continues with this block once the problem has been fixed.
+///
+/// # Arguments
+/// * `apply_args` - Options of the `apply` subcommand
+/// * `pool` - Optional MySQL connection pool, required for loading the file
+/// * `args` - Command line arguments controlling verbosity and dry-run mode
+///
+/// # Returns
+/// Returns the exit status: 0 if all blocks were committed, otherwise the
+/// status for a partial or a total failure
+///
+/// # Errors
+/// Returns an error if there is no database connection, the SQL file or the
+/// progress file cannot be read, or the statements outside of transactions fail
+///
+pub async fn apply_sql_file(
+    apply_args: &ApplyArgs,
+    pool: &Option<Pool<MySql>>,
+    args: &CliArgs,
+) -> Result<u8> {
+    let Some(pool) = pool.as_ref() else {
+        bail!("The apply subcommand needs a database connection (option -u, --db-url)");
+    };
+    let script = parse_sql_file(Path::new(&apply_args.sql_file))?;
+    let progress_file = apply_args.progress_file();
+    let mut progress = if apply_args.resume {
+        ApplyProgress::load(&progress_file)?
+    } else {
+        ApplyProgress::default()
+    };
+    if progress.committed > 0 {
+        // The recorded progress must belong to the same SQL file
+        let last_block = script
+            .blocks
+            .get(progress.committed - 1)
+            .map(|block| block.name().to_string());
+        if progress.sql_file != apply_args.sql_file
+            || progress.committed > script.blocks.len()
+            || progress.last_block != last_block
+        {
+            bail!(
+                "Progress file {} does not match SQL file {}",
+                progress_file.display(),
+                apply_args.sql_file
+            );
+        }
+    }
+    progress.sql_file = apply_args.sql_file.clone();
+    if args.verbose || args.dry_run {
+        println!(
+            "Applying {} of {} runs from {}",
+            script.blocks.len() - progress.committed,
+            script.blocks.len(),
+            apply_args.sql_file
+        );
+    }
+
+    // All statements run on the same connection, so that session variables
+    // set by the preamble remain valid for all blocks.
+    let mut conn = pool.acquire().await?;
+    for statement in &script.preamble {
+        if args.verbose || args.dry_run {
+            println!("Executing query: {}", statement);
+        }
+        if !args.dry_run {
+            sqlx::raw_sql(statement)
+                .execute(&mut *conn)
+                .await
+                .with_context(|| format!("Failed to apply {}", apply_args.sql_file))?;
+        }
+    }
+
+    let first_block = progress.committed;
+    for block in &script.blocks[first_block..] {
+        if args.verbose || args.dry_run {
+            println!("Applying queries for file {}", block.name());
+        }
+        if args.dry_run {
+            continue;
+        }
+        let mut tx = conn.begin().await?;
+        let mut result = Ok(());
+        for statement in &block.statements {
+            if let Err(e) = sqlx::raw_sql(statement).execute(&mut *tx).await {
+                result = Err(e);
+                break;
+            }
+        }
+        if let Err(e) = result {
+            tx.rollback().await?;
+            eprintln!(
+                "Failed to apply queries for file {} ({}): {}",
+                block.name(),
+                block.location,
+                e
+            );
+            println!(
+                "Committed {} of {} runs, continue with `apply --resume` after fixing the problem",
+                progress.committed,
+                script.blocks.len()
+            );
+            return Ok(if progress.committed > first_block {
+                EXIT_PARTIAL_FAILURE
+            } else {
+                EXIT_TOTAL_FAILURE
+            });
+        }
+        tx.commit().await?;
+        progress.committed += 1;
+        progress.last_block = Some(block.name().to_string());
+        progress.save(&progress_file)?;
+    }
+    println!(
+        "Committed {} of {} runs from {}",
+        progress.committed,
+        script.blocks.len(),
+        apply_args.sql_file
+    );
+    Ok(0)
+}
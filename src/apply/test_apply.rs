@@ -0,0 +1,227 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::apply::{apply_sql_file, parse_sql_file, ApplyProgress};
+    use crate::cmdline::{ApplyArgs, CliArgs, DuplicatePolicy};
+    use crate::globbing::find_lmx_summary_files;
+    use crate::jobdata::sql_output::write_sql_file_header;
+    use crate::sqltypes::read_sqltypes;
+    use crate::workers::process_lmx_files;
+    use anyhow::Result;
+    use sqlx::mysql::MySqlPool;
+    use sqlx::MySql;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    /// Writes the SQL of the NAMD runs in file mode, as it is loaded by `apply`
+    async fn write_namd_sql_file(sql_file: &Path, with_header: bool) -> Result<()> {
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            sql_file: sql_file.to_string_lossy().to_string(),
+            project_file: "project.yml".to_string(),
+            settings_file: "settings.yml".to_string(),
+            module_file: "modules.yml".to_string(),
+            on_duplicate: DuplicatePolicy::Skip,
+            do_import: true,
+            ..Default::default()
+        };
+        let sqltypes = read_sqltypes(None, &args).await?;
+        let sources = vec!["tests/data/NAMD".to_string()];
+        if with_header {
+            write_sql_file_header(&sqltypes, &sources, &args)?;
+        }
+        let list_of_files = find_lmx_summary_files(&sources, &args)?.files;
+        let results =
+            process_lmx_files(list_of_files, &None, Arc::new(sqltypes), Arc::new(args)).await?;
+        assert!(results.iter().all(|(_, result)| result.is_ok()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_parse_generated_sql_file() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let sql_file = temp_dir.path().join("import.sql");
+        write_namd_sql_file(&sql_file, true).await?;
+
+        let script = parse_sql_file(&sql_file)?;
+        // The header adds the fingerprint guard outside of any transaction
+        assert_eq!(script.preamble.len(), 2);
+        assert!(script.preamble[0].starts_with("SET SESSION group_concat_max_len"));
+        assert!(script.preamble[1].ends_with("END IF"));
+        assert_eq!(script.blocks.len(), 3);
+        for block in &script.blocks {
+            let source = block.source.as_deref().expect("Missing file marker");
+            assert!(source.starts_with("tests/data/NAMD/run_000"), "{}", source);
+            assert!(source.ends_with(".yml"), "{}", source);
+            // Each INSERT statement is guarded against existing runs on its own
+            assert!(block
+                .statements
+                .iter()
+                .any(|s| s.starts_with("SET @run_exists = EXISTS (")));
+            let inserts: Vec<&String> = block
+                .statements
+                .iter()
+                .filter(|s| s.starts_with("INSERT INTO"))
+                .collect();
+            assert!(inserts[0].starts_with("INSERT INTO runs ("));
+            assert!(inserts
+                .iter()
+                .all(|s| s.ends_with(" FROM DUAL WHERE NOT @run_exists")));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_quotes_and_sources() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        std::fs::create_dir(temp_dir.path().join("runs"))?;
+        std::fs::write(
+            temp_dir.path().join("runs/run_1.sql"),
+            "START TRANSACTION;\n\
+             -- Queries for file run_1/LMX_summary.1.0.yml;\n\
+             INSERT INTO environ (k, v) VALUES ('PS1', 'a;b'), ('Q', 'it''s \\';'), ('L', 'x\n\
+             -- y');\n\
+             SET @x = \"c;d\"; SET @y = 1;\n\
+             COMMIT;\n",
+        )?;
+        std::fs::write(
+            temp_dir.path().join("runs/run_2.sql"),
+            "START TRANSACTION;\n-- Queries for file run_2/LMX_summary.2.0.yml;\nSET @z = 2;\nCOMMIT;\n",
+        )?;
+        let index = temp_dir.path().join("index.sql");
+        // The index names the files by absolute paths like the index files of lmx2db
+        let runs = temp_dir.path().join("runs");
+        std::fs::write(
+            &index,
+            format!(
+                "SOURCE {0}/run_1.sql;\nSOURCE {0}/run_2.sql;\n",
+                runs.display()
+            ),
+        )?;
+
+        let script = parse_sql_file(&index)?;
+        assert!(script.preamble.is_empty());
+        assert_eq!(script.blocks.len(), 2);
+        assert_eq!(script.blocks[0].name(), "run_1/LMX_summary.1.0.yml");
+        assert_eq!(
+            script.blocks[0].statements,
+            vec![
+                "INSERT INTO environ (k, v) VALUES ('PS1', 'a;b'), ('Q', 'it''s \\';'), ('L', 'x\n-- y')",
+                "SET @x = \"c;d\"",
+                "SET @y = 1",
+            ]
+        );
+        assert_eq!(script.blocks[1].name(), "run_2/LMX_summary.2.0.yml");
+        assert_eq!(script.blocks[1].statements, vec!["SET @z = 2"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_broken_transactions() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let sql_file = temp_dir.path().join("broken.sql");
+        for (content, message) in [
+            ("SET @x = 1;\nCOMMIT;\n", "COMMIT without START TRANSACTION"),
+            (
+                "START TRANSACTION;\nSTART TRANSACTION;\n",
+                "inside a transaction",
+            ),
+            ("START TRANSACTION;\nSET @x = 1;\n", "is not committed"),
+            (
+                "START TRANSACTION;\nSET @x = 'open;\nCOMMIT;\n",
+                "Unterminated statement",
+            ),
+        ] {
+            std::fs::write(&sql_file, content)?;
+            let err = parse_sql_file(&sql_file).unwrap_err().to_string();
+            assert!(err.contains(message), "{}", err);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_progress_beyond_the_sql_file() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let sql_file = temp_dir.path().join("import.sql");
+        std::fs::write(
+            &sql_file,
+            "START TRANSACTION;\n-- Queries for file run_1/LMX_summary.1.0.yml;\nSET @x = 1;\nCOMMIT;\n",
+        )?;
+        let apply_args = ApplyArgs {
+            resume: true,
+            progress_file: None,
+            sql_file: sql_file.to_string_lossy().to_string(),
+        };
+        // More blocks committed than the SQL file has, without a last block
+        ApplyProgress {
+            sql_file: apply_args.sql_file.clone(),
+            committed: 5,
+            last_block: None,
+        }
+        .save(&apply_args.progress_file())?;
+
+        // The pool is never connected, the progress is rejected before
+        let pool = Some(MySqlPool::connect_lazy("mysql://localhost/lmxdb")?);
+        let result = apply_sql_file(&apply_args, &pool, &CliArgs::default()).await;
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("does not match SQL file"), "{}", error);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(
+        "../../tests/fixtures/tables.sql",
+        "../../tests/fixtures/functs4test.sql"
+    ))]
+    async fn test_apply_with_resume(pool: sqlx::Pool<MySql>) -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let sql_file = temp_dir.path().join("import.sql");
+        write_namd_sql_file(&sql_file, false).await?;
+        let mut apply_args = ApplyArgs {
+            resume: false,
+            progress_file: None,
+            sql_file: sql_file.to_string_lossy().to_string(),
+        };
+        let args = CliArgs::default();
+        let pool = Some(pool);
+
+        assert_eq!(apply_sql_file(&apply_args, &pool, &args).await?, 0);
+        let progress = ApplyProgress::load(&apply_args.progress_file())?;
+        assert_eq!(progress.committed, 3);
+        let runs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM runs")
+            .fetch_one(pool.as_ref().unwrap())
+            .await?;
+        assert_eq!(runs, 3);
+
+        // Resuming after the last committed block applies nothing again
+        apply_args.resume = true;
+        assert_eq!(apply_sql_file(&apply_args, &pool, &args).await?, 0);
+        let runs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM runs")
+            .fetch_one(pool.as_ref().unwrap())
+            .await?;
+        assert_eq!(runs, 3);
+
+        // A progress file of a different SQL file is rejected
+        let other_file = temp_dir.path().join("other.sql");
+        std::fs::copy(&sql_file, &other_file)?;
+        std::fs::copy(
+            apply_args.progress_file(),
+            format!("{}.progress.json", other_file.display()),
+        )?;
+        apply_args.sql_file = other_file.to_string_lossy().to_string();
+        assert!(apply_sql_file(&apply_args, &pool, &args).await.is_err());
+        Ok(())
+    }
+}
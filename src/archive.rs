@@ -0,0 +1,202 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use flate2::read::GzDecoder;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+#[cfg(test)]
+pub(crate) mod test_archive;
+
+/// File in the root directory of an extracted archive holding the path of the archive
+const ARCHIVE_MARKER: &str = ".lmx2db_archive";
+
+/// Prefix of the temporary directories archives are extracted into
+const TEMP_DIR_PREFIX: &str = "lmx2db_archive_";
+
+/// Checks whether the given path names a supported archive,
+/// i.e. a `.tar`, `.tar.gz`, `.tgz` or `.zip` file.
+pub fn is_archive(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    [".tar", ".tar.gz", ".tgz", ".zip"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+/// Extracts the files needed by lmx2db from an archive into a temporary directory.
+/// Only the run directories, i.e. directories with an LMX_summary member, are extracted,
+/// with their members whose file name starts with "LMX_" or ends with ".yml" or ".yaml".
+/// Of the directories above, only the YAML files are extracted, e.g. project and
+/// modules files. The root directory records the archive for `display_path`.
+///
+/// The members are not read in place, because the import of a run reads its files
+/// by path: profile files are globbed next to the LMX_summary file, and project
+/// and modules files are searched in the parent directories.
+///
+/// # Arguments
+/// * `path` - Path to the archive file
+///
+/// # Returns
+/// A Result containing the temporary directory with the extracted files, which is
+/// removed when dropped, or an error.
+///
+/// Errors if the archive cannot be read or the files cannot be extracted.
+///
+pub fn extract_archive(path: &Path) -> Result<TempDir> {
+    let archive = path.canonicalize()?;
+    let temp_dir = tempfile::Builder::new().prefix(TEMP_DIR_PREFIX).tempdir()?;
+    let root = temp_dir.path();
+    let name = archive.to_string_lossy().to_string();
+
+    if name.ends_with(".zip") {
+        let mut zip_archive = zip::ZipArchive::new(File::open(&archive)?)?;
+        let mut members = Vec::new();
+        for index in 0..zip_archive.len() {
+            let member = zip_archive.by_index(index)?;
+            if member.is_dir() {
+                continue;
+            }
+            // enclosed_name() rejects member names pointing outside of the root
+            let Some(member_path) = member.enclosed_name() else {
+                bail!(
+                    "Invalid member name '{}' in archive {}",
+                    member.name()?,
+                    name
+                );
+            };
+            members.push(member_path);
+        }
+        let needed = needed_members(&members);
+        for index in 0..zip_archive.len() {
+            let mut member = zip_archive.by_index(index)?;
+            if let Some(member_path) = member.enclosed_name().filter(|p| needed.contains(p)) {
+                let target = root.join(&member_path);
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::io::copy(&mut member, &mut File::create(&target)?)?;
+            }
+        }
+    } else {
+        let open_tar = || -> Result<tar::Archive<Box<dyn Read>>> {
+            let reader: Box<dyn Read> = if name.ends_with(".tar") {
+                Box::new(File::open(&archive)?)
+            } else {
+                Box::new(GzDecoder::new(File::open(&archive)?))
+            };
+            Ok(tar::Archive::new(reader))
+        };
+        // The member list is needed before extracting, so the archive is read twice
+        let mut members = Vec::new();
+        for entry in open_tar()?.entries()? {
+            let entry = entry?;
+            if entry.header().entry_type().is_file() {
+                members.push(entry.path()?.to_path_buf());
+            }
+        }
+        let needed = needed_members(&members);
+        for entry in open_tar()?.entries()? {
+            let mut entry = entry?;
+            if entry.header().entry_type().is_file() && needed.contains(&*entry.path()?) {
+                // unpack_in() refuses to write outside of the root
+                entry.unpack_in(root)?;
+            }
+        }
+    }
+
+    std::fs::write(root.join(ARCHIVE_MARKER), &name)?;
+    Ok(temp_dir)
+}
+
+/// Selects the archive members needed by lmx2db: the LMX and YAML files of the
+/// run directories and the YAML files of the directories above them.
+fn needed_members(members: &[PathBuf]) -> HashSet<PathBuf> {
+    let run_dirs: HashSet<&Path> = members
+        .iter()
+        .filter(|member| file_name_of(member).starts_with("LMX_summary"))
+        .filter_map(|member| member.parent())
+        .collect();
+    let parent_dirs: HashSet<&Path> = run_dirs
+        .iter()
+        .flat_map(|dir| dir.ancestors().skip(1))
+        .collect();
+    members
+        .iter()
+        .filter(|member| {
+            let name = file_name_of(member);
+            let is_yaml = name.ends_with(".yml") || name.ends_with(".yaml");
+            let dir = member.parent().unwrap_or_else(|| Path::new(""));
+            (run_dirs.contains(dir) && (name.starts_with("LMX_") || is_yaml))
+                || (parent_dirs.contains(dir) && is_yaml)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns the file name of a path, or an empty string
+fn file_name_of(path: &Path) -> &str {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("")
+}
+
+/// Returns the root directory of the extracted archive containing `path`.
+/// Only the temporary directories created by `extract_archive` are considered,
+/// so paths outside of the temporary directory are mapped without file access.
+fn archive_root(path: &Path) -> Option<PathBuf> {
+    let temp_dir = std::env::temp_dir();
+    let name = path.strip_prefix(&temp_dir).ok()?.components().next()?;
+    if !name
+        .as_os_str()
+        .to_string_lossy()
+        .starts_with(TEMP_DIR_PREFIX)
+    {
+        return None;
+    }
+    let root = temp_dir.join(name);
+    root.join(ARCHIVE_MARKER).is_file().then_some(root)
+}
+
+/// Checks whether the given directory is the root of an extracted archive.
+/// The search for project and modules files stops at this directory.
+pub fn is_archive_root(dir: &Path) -> bool {
+    archive_root(dir).is_some_and(|root| root == dir)
+}
+
+/// Maps a path inside an extracted archive to the path of the archive member,
+/// e.g. `/tmp/lmx2db_archive_abc/run_0001` to `/data/campaign.tar.gz/run_0001`.
+/// Paths outside of extracted archives are returned unchanged.
+pub fn display_path(path: &Path) -> PathBuf {
+    if let Some(root) = archive_root(path) {
+        if let Ok(archive) = std::fs::read_to_string(root.join(ARCHIVE_MARKER)) {
+            let member_path = path.strip_prefix(&root).unwrap_or(path);
+            return Path::new(&archive).join(member_path);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Same as `display_path` for file names given as strings.
+pub fn display_name(file_name: &str) -> String {
+    display_path(Path::new(file_name))
+        .to_string_lossy()
+        .to_string()
+}
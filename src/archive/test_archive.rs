@@ -0,0 +1,148 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::archive::{display_name, is_archive, is_archive_root};
+    use crate::cmdline::CliArgs;
+    use crate::globbing::find_lmx_summary_files;
+    use crate::jobdata::process_lmx_file;
+    use crate::jobdata::table_runs::find_file::find_project_file;
+    use crate::sqltypes::read_sqltypes;
+    use anyhow::Result;
+    use flate2::write::GzEncoder;
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+
+    /// Creates a .tar.gz archive of tests/data/NAMD with all files below `NAMD/`.
+    fn create_tar_gz(temp_dir: &Path) -> Result<PathBuf> {
+        let archive_path = temp_dir.join("campaign.tar.gz");
+        let encoder = GzEncoder::new(File::create(&archive_path)?, flate2::Compression::fast());
+        let mut builder = tar::Builder::new(encoder);
+        builder.append_dir_all("NAMD", "tests/data/NAMD")?;
+        builder.into_inner()?.finish()?;
+        Ok(archive_path)
+    }
+
+    #[test]
+    fn test_is_archive() {
+        assert!(is_archive(Path::new("/data/campaign.tar.gz")));
+        assert!(is_archive(Path::new("campaign.tgz")));
+        assert!(is_archive(Path::new("campaign.tar")));
+        assert!(is_archive(Path::new("campaign.zip")));
+        assert!(!is_archive(Path::new("LMX_summary.1234.0.yml")));
+    }
+
+    #[tokio::test]
+    async fn test_import_from_tar_gz() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let archive_path = create_tar_gz(temp_dir.path())?;
+        let archive_name = archive_path.canonicalize()?.to_string_lossy().to_string();
+
+        let found = find_lmx_summary_files(
+            &vec![archive_path.to_string_lossy().to_string()],
+            &CliArgs::default(),
+        )?;
+        let files = &found.files;
+        assert_eq!(files.len(), 3);
+        let mut display_names: Vec<String> = files.iter().map(|f| display_name(f)).collect();
+        display_names.sort();
+        assert_eq!(
+            display_names[0],
+            format!("{}/NAMD/run_0001/LMX_summary.225250.0.yml", archive_name)
+        );
+
+        // The project file is found at NAMD/project.yml inside the archive
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            sql_file: temp_dir
+                .path()
+                .join("import.sql")
+                .to_string_lossy()
+                .to_string(),
+            project_file: "project.yml".to_string(),
+            settings_file: "settings.yml".to_string(),
+            module_file: "modules.yml".to_string(),
+            ..Default::default()
+        };
+        let file_name = files
+            .iter()
+            .find(|f| f.ends_with("LMX_summary.225250.0.yml"))
+            .unwrap();
+        let project_file = find_project_file(file_name, &args)?;
+        assert_eq!(
+            display_name(project_file.to_str().unwrap()),
+            format!("{}/NAMD/project.yml", archive_name)
+        );
+
+        // The archive member path is used as dirname
+        let sqltypes = read_sqltypes(None, &args).await?;
+        process_lmx_file(file_name, &None, None, &sqltypes, &args).await?;
+        let content = std::fs::read_to_string(&args.sql_file)?;
+        assert!(content.contains(&format!(
+            "-- Queries for file {}/NAMD/run_0001/LMX_summary.225250.0.yml;",
+            archive_name
+        )));
+        assert!(content.contains(&format!("'{}/NAMD/run_0001'", archive_name)));
+
+        // Dropping the found files removes the extracted archive
+        let root = found.archives[0].path().to_path_buf();
+        assert!(is_archive_root(&root));
+        drop(found);
+        assert!(!root.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_stops_at_zip_root() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        // A project file next to the archive must not be found
+        std::fs::copy(
+            "tests/data/NAMD/project.yml",
+            temp_dir.path().join("project.yml"),
+        )?;
+        let archive_path = temp_dir.path().join("run.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path)?);
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("run_0001/LMX_summary.225250.0.yml", options)?;
+        writer.write_all(&std::fs::read(
+            "tests/data/NAMD/run_0001/LMX_summary.225250.0.yml",
+        )?)?;
+        writer.start_file("run_0001/output.log", options)?;
+        writer.write_all(b"not needed by lmx2db")?;
+        writer.start_file("notes/LMX_notes.yml", options)?;
+        writer.write_all(b"not a run directory")?;
+        writer.finish()?;
+
+        let found = find_lmx_summary_files(
+            &vec![archive_path.to_string_lossy().to_string()],
+            &CliArgs::default(),
+        )?;
+        let files = &found.files;
+        assert_eq!(files.len(), 1);
+        // Only LMX and YAML files of run directories are extracted
+        let run_dir = Path::new(&files[0]).parent().unwrap();
+        assert!(!run_dir.join("output.log").exists());
+        assert!(!found.archives[0].path().join("notes").exists());
+
+        let args = CliArgs {
+            project_file: "project.yml".to_string(),
+            ..Default::default()
+        };
+        let result = find_project_file(&files[0], &args);
+        assert!(result.is_err(), "Found project file {:?}", result);
+        Ok(())
+    }
+}
@@ -12,11 +12,261 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use clap::Parser;
+use crate::config::parse_args_with_config;
+use crate::datetime::DateTime;
+use crate::jobdata::sql_output::parse_sql_file_template;
+use crate::schema::{parse_schema_source, SchemaSource};
+use anyhow::Result;
+use clap::builder::RangedU64ValueParser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use glob::Pattern;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 #[cfg(test)]
 mod cmdline_tests;
 
-#[derive(Parser, Debug, Default)]
+/// Policy for runs that already exist in the database, identified by
+/// person, start_date and start_date_n
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Drop the existing run and import it again
+    #[default]
+    Replace,
+    /// Leave the existing run untouched and skip the import
+    Skip,
+    /// Treat an existing run as an error
+    Error,
+    /// Import the run a second time next to the existing one
+    KeepBoth,
+}
+
+/// Transaction granularity for direct imports into the database
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TxMode {
+    /// Commit each run in a transaction of its own
+    #[default]
+    Run,
+    /// Commit all runs of the batch together
+    All,
+    /// Commit groups of this many runs together
+    Batch(usize),
+}
+
+/// Parses the `--tx-mode` argument: `run`, `all` or `batch:N` with N > 0.
+///
+/// # Arguments
+/// * `value` - The argument as given on the command line
+///
+/// # Returns
+/// Returns the parsed transaction mode
+///
+/// # Errors
+/// Returns an error message if the argument cannot be parsed
+///
+pub fn parse_tx_mode(value: &str) -> Result<TxMode, String> {
+    match value {
+        "run" => Ok(TxMode::Run),
+        "all" => Ok(TxMode::All),
+        _ => value
+            .strip_prefix("batch:")
+            .and_then(|runs| runs.parse::<usize>().ok())
+            .filter(|runs| *runs > 0)
+            .map(TxMode::Batch)
+            .ok_or_else(|| {
+                format!(
+                    "'{}' is not a transaction mode, expected run, all or batch:N with N > 0",
+                    value
+                )
+            }),
+    }
+}
+
+/// Handling of symbolic links while searching directories for LMX_summary files
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Follow symbolic links to directories and files
+    #[default]
+    Follow,
+    /// Ignore symbolic links
+    Ignore,
+}
+
+/// Parses the argument of `--newer-than`, which is either a number of seconds
+/// since the epoch, a UTC date and time as "YYYY-MM-DD[ HH:MM[:SS]]" (with a space
+/// or 'T' as separator), or the name of an existing file whose modification
+/// time is used.
+///
+/// # Arguments
+/// * `value` - The argument as given on the command line
+///
+/// # Returns
+/// Returns the parsed point in time
+///
+/// # Errors
+/// Returns an error message if the argument cannot be parsed
+///
+pub fn parse_timestamp(value: &str) -> Result<SystemTime, String> {
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Ok(UNIX_EPOCH + Duration::from_secs(seconds));
+    }
+    if let Ok(metadata) = std::fs::metadata(value) {
+        return metadata.modified().map_err(|e| e.to_string());
+    }
+    let invalid = || {
+        format!(
+            "'{}' is neither a timestamp, a date \"YYYY-MM-DD[ HH:MM[:SS]]\" nor an existing file",
+            value
+        )
+    };
+    DateTime::parse(value, true)
+        .and_then(|date_time| date_time.to_system_time())
+        .ok_or_else(invalid)
+}
+
+/// Subcommands of lmx2db. Without a subcommand, the given input
+/// directories and files are imported once.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Watch directories and import runs as they finish
+    Watch(WatchArgs),
+    /// Load a SQL file written by lmx2db into the database, one transaction per run
+    Apply(ApplyArgs),
+    /// Inspect and compare database schemas
+    #[command(subcommand)]
+    Schema(SchemaCommand),
+    /// Create sqltypes files
+    #[command(subcommand)]
+    Sqltypes(SqltypesCommand),
+    /// Create and update the lmx2db database schema
+    #[command(subcommand)]
+    Db(DbCommand),
+}
+
+/// Subcommands of `db`
+#[derive(Subcommand, Debug, Clone)]
+pub enum DbCommand {
+    /// Create the schema, stored functions and reference data in an empty database
+    Init(DbInitArgs),
+    /// Apply the schema migrations shipped with lmx2db
+    Migrate(DbMigrateArgs),
+}
+
+/// Options of the `db init` subcommand
+#[derive(Args, Debug, Clone)]
+pub struct DbInitArgs {
+    /// Also load the sample data of schema/minimal_data.sql
+    #[arg(long, default_value_t = false)]
+    pub sample_data: bool,
+
+    /// Only record the base schema version of a database created by hand
+    #[arg(long, default_value_t = false, conflicts_with = "sample_data")]
+    pub baseline: bool,
+}
+
+/// Options of the `db migrate` subcommand
+#[derive(Args, Debug, Clone)]
+pub struct DbMigrateArgs {
+    /// Last schema version to migrate to (default: latest)
+    #[arg(long)]
+    pub to: Option<u32>,
+}
+
+/// Subcommands of `schema`
+#[derive(Subcommand, Debug, Clone)]
+pub enum SchemaCommand {
+    /// Compare two schemas and classify the changes for the tables lmx2db writes
+    Diff(SchemaDiffArgs),
+    /// Print or extract the sqltypes file and schema files compiled into lmx2db
+    Dump(SchemaDumpArgs),
+}
+
+/// Options of the `schema dump` subcommand
+#[derive(Args, Debug, Clone)]
+pub struct SchemaDumpArgs {
+    /// Write the files into this directory instead of printing them
+    #[arg(long)]
+    pub output_dir: Option<String>,
+
+    /// Files to dump, e.g. sqltypes.yml or tables.sql (default: all)
+    pub files: Vec<String>,
+}
+
+/// Subcommands of `sqltypes`
+#[derive(Subcommand, Debug, Clone)]
+pub enum SqltypesCommand {
+    /// Create the sqltypes file given by `-t` from CREATE TABLE statements,
+    /// without a database connection
+    FromDdl(FromDdlArgs),
+}
+
+/// Options of the `sqltypes from-ddl` subcommand
+#[derive(Args, Debug, Clone)]
+pub struct FromDdlArgs {
+    /// DDL file with CREATE TABLE statements, e.g. schema/tables.sql
+    pub ddl_file: String,
+}
+
+/// Options of the `schema diff` subcommand. A schema is given as `db` for the
+/// database of `--db-url`, `bundled` for the schema compiled into lmx2db, or
+/// the name of a sqltypes YAML file or a DDL file ending in `.sql`.
+#[derive(Args, Debug, Clone)]
+pub struct SchemaDiffArgs {
+    /// Schema lmx2db was checked against, e.g. the sqltypes file
+    #[arg(value_parser = parse_schema_source)]
+    pub from: SchemaSource,
+
+    /// Schema to compare with, e.g. the live database
+    #[arg(value_parser = parse_schema_source)]
+    pub to: SchemaSource,
+}
+
+/// Options of the `watch` subcommand
+#[derive(Args, Debug, Clone)]
+pub struct WatchArgs {
+    /// Seconds between two scans of the watched directories
+    #[arg(long, default_value_t = 60)]
+    pub interval: u64,
+
+    /// Seconds the files of a run must remain unchanged before the run is imported
+    #[arg(long, default_value_t = 120)]
+    pub settle_time: u64,
+
+    /// File recording the runs already processed, so that restarts do not reprocess them
+    #[arg(long, default_value = "lmx2db_watch_state.json")]
+    pub state_file: String,
+
+    /// Directories to watch
+    #[arg(required = true)]
+    pub directories: Vec<String>,
+}
+
+/// Options of the `apply` subcommand
+#[derive(Args, Debug, Clone)]
+pub struct ApplyArgs {
+    /// Continue after the last run committed by a previous call of `apply`
+    #[arg(long, default_value_t = false)]
+    pub resume: bool,
+
+    /// File recording the runs committed so far (default: SQL file name + ".progress.json")
+    #[arg(long)]
+    pub progress_file: Option<String>,
+
+    /// SQL file to load
+    pub sql_file: String,
+}
+
+impl ApplyArgs {
+    /// Returns the progress file, by default next to the SQL file
+    pub fn progress_file(&self) -> PathBuf {
+        self.progress_file
+            .clone()
+            .unwrap_or_else(|| format!("{}.progress.json", self.sql_file))
+            .into()
+    }
+}
+
+#[derive(Parser, Debug, Default, Clone)]
 #[command(
     name = env!("CARGO_PKG_NAME"),
     author = env!("CARGO_PKG_AUTHORS"),
@@ -38,7 +288,7 @@ pub struct CliArgs {
     pub db_url: String,
 
     /// name of sqltypes file
-    #[arg(short = 't', long, default_value = "sqltypes.yml")]
+    #[arg(short = 't', long, default_value = "sqltypes.yml", global = true)]
     pub sqltypes_file: String,
 
     /// create sqltypes file from database
@@ -49,6 +299,12 @@ pub struct CliArgs {
     #[arg(short = 'f', long, default_value = "import.sql")]
     pub sql_file: String,
 
+    /// Write the SQL of each run to its own file named from this template, e.g.
+    /// "{project}/{code}_{test_case}/{start_date}.sql", relative to the directory
+    /// of the SQL file, which becomes an index listing the files in load order
+    #[arg(long, value_parser = parse_sql_file_template)]
+    pub sql_file_template: Option<String>,
+
     /// import unknown foreign keys rather then raising errors
     #[arg(short = 'i', long, default_value_t = false)]
     pub do_import: bool,
@@ -65,12 +321,92 @@ pub struct CliArgs {
     #[arg(short = 'p', long, default_value = "project.yml")]
     pub project_file: String,
 
-    /// Input directories to process
+    /// How to handle runs that already exist in the database
+    #[arg(long, value_enum, default_value_t = DuplicatePolicy::Replace)]
+    pub on_duplicate: DuplicatePolicy,
+
+    /// Write a JSON report with one entry per LMX_summary file
+    #[arg(long)]
+    pub report_file: Option<String>,
+
+    /// Number of LMX_summary files processed concurrently
+    #[arg(short = 'j', long, default_value_t = 1)]
+    pub jobs: usize,
+
+    /// Transaction granularity for direct imports: "run" (one transaction per run),
+    /// "all" (one transaction for all runs) or "batch:N" (one transaction per N runs).
+    /// In the grouped modes, a failing run is rolled back to its savepoint alone.
+    #[arg(long, default_value = "run", value_parser = parse_tx_mode)]
+    pub tx_mode: TxMode,
+
+    /// Read additional input paths from this file, one per line ("-" for stdin)
+    #[arg(long)]
+    pub files_from: Option<String>,
+
+    /// Skip directories matching this glob pattern (name or path), may be repeated
+    #[arg(long, value_parser = Pattern::new)]
+    pub exclude: Vec<Pattern>,
+
+    /// Only use LMX_summary files below directories matching this glob pattern
+    /// (name or path), may be repeated
+    #[arg(long, value_parser = Pattern::new)]
+    pub include: Vec<Pattern>,
+
+    /// Maximum depth of directories searched below each input directory
+    #[arg(long)]
+    pub max_depth: Option<usize>,
+
+    /// Whether to follow symbolic links while searching directories
+    #[arg(long, value_enum, default_value_t = SymlinkPolicy::Follow)]
+    pub symlinks: SymlinkPolicy,
+
+    /// Only use LMX_summary files modified after this time
+    /// (seconds since epoch, UTC date "YYYY-MM-DD[ HH:MM[:SS]]" or reference file)
+    #[arg(long, value_parser = parse_timestamp)]
+    pub newer_than: Option<SystemTime>,
+
+    /// Maximum number of rows per INSERT statement (default: unlimited)
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_insert_rows: Option<usize>,
+
+    /// Maximum size of an INSERT statement in bytes (default: max_allowed_packet
+    /// of the database server, 16 MiB for the SQL file)
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1024..))]
+    pub max_insert_bytes: Option<usize>,
+
+    /// Configuration file (default: ./lmx2db.yml or ~/.config/lmx2db/config.yml)
+    #[arg(long, env = "LMX2DB_CONFIG")]
+    pub config: Option<String>,
+
+    /// Profile of the configuration file to use
+    #[arg(long, env = "LMX2DB_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Source of each argument value, i.e. command line, environment,
+    /// configuration file, profile or default
+    #[arg(skip)]
+    pub sources: BTreeMap<String, String>,
+
+    /// Input directories or LMX_summary files to process
     pub directories: Vec<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Parses the command line arguments, completed by the configuration file.
+/// See `config::parse_args_with_config` for details.
+pub fn parse_args() -> Result<CliArgs> {
+    parse_args_with_config(std::env::args_os().collect())
 }
 
-pub fn parse_args() -> CliArgs {
-    CliArgs::parse()
+/// Prints one argument together with the source of its value,
+/// e.g. "SQL file: import.sql (default)".
+fn echo_arg(args: &CliArgs, id: &str, label: &str, value: String) {
+    match args.sources.get(id) {
+        Some(source) => println!("{}: {} ({})", label, value, source),
+        None => println!("{}: {}", label, value),
+    }
 }
 
 pub fn echo_args(args: &CliArgs) {
@@ -78,16 +414,181 @@ pub fn echo_args(args: &CliArgs) {
         println!("Performing a dry run");
     }
     if args.verbose || args.dry_run {
-        println!("Verbose: {}", args.verbose);
-        println!("Dry run: {}", args.dry_run);
-        println!("SQLTypes file: {}", args.sqltypes_file);
-        println!("Create SQLTypes: {}", args.create_sqltypes);
-        println!("Database URL: {}", args.db_url);
-        println!("SQL file: {}", args.sql_file);
-        println!("Import unknown foreign keys: {}", args.do_import);
-        println!("Module file: {}", args.module_file);
-        println!("Settings file: {}", args.settings_file);
-        println!("Project file: {}", args.project_file);
-        println!("Input directories: {:?}", args.directories);
+        println!(
+            "Configuration file: {}",
+            args.config.as_deref().unwrap_or("none")
+        );
+        echo_arg(
+            args,
+            "profile",
+            "Profile",
+            args.profile.clone().unwrap_or("none".to_string()),
+        );
+        echo_arg(args, "verbose", "Verbose", args.verbose.to_string());
+        echo_arg(args, "dry_run", "Dry run", args.dry_run.to_string());
+        echo_arg(
+            args,
+            "sqltypes_file",
+            "SQLTypes file",
+            args.sqltypes_file.clone(),
+        );
+        echo_arg(
+            args,
+            "create_sqltypes",
+            "Create SQLTypes",
+            args.create_sqltypes.to_string(),
+        );
+        echo_arg(args, "db_url", "Database URL", args.db_url.clone());
+        echo_arg(args, "sql_file", "SQL file", args.sql_file.clone());
+        echo_arg(
+            args,
+            "sql_file_template",
+            "SQL file template",
+            args.sql_file_template.clone().unwrap_or("none".to_string()),
+        );
+        echo_arg(
+            args,
+            "do_import",
+            "Import unknown foreign keys",
+            args.do_import.to_string(),
+        );
+        echo_arg(args, "module_file", "Module file", args.module_file.clone());
+        echo_arg(
+            args,
+            "settings_file",
+            "Settings file",
+            args.settings_file.clone(),
+        );
+        echo_arg(
+            args,
+            "project_file",
+            "Project file",
+            args.project_file.clone(),
+        );
+        echo_arg(
+            args,
+            "on_duplicate",
+            "Duplicate runs",
+            format!("{:?}", args.on_duplicate),
+        );
+        echo_arg(
+            args,
+            "report_file",
+            "Report file",
+            args.report_file.clone().unwrap_or("none".to_string()),
+        );
+        echo_arg(args, "jobs", "Concurrent jobs", args.jobs.to_string());
+        echo_arg(
+            args,
+            "tx_mode",
+            "Transaction mode",
+            format!("{:?}", args.tx_mode),
+        );
+        echo_arg(
+            args,
+            "files_from",
+            "Read input paths from",
+            args.files_from.clone().unwrap_or("none".to_string()),
+        );
+        echo_arg(
+            args,
+            "exclude",
+            "Exclude patterns",
+            format!(
+                "{:?}",
+                args.exclude.iter().map(|p| p.as_str()).collect::<Vec<_>>()
+            ),
+        );
+        echo_arg(
+            args,
+            "include",
+            "Include patterns",
+            format!(
+                "{:?}",
+                args.include.iter().map(|p| p.as_str()).collect::<Vec<_>>()
+            ),
+        );
+        echo_arg(
+            args,
+            "max_depth",
+            "Maximum depth",
+            args.max_depth
+                .map_or("unlimited".to_string(), |d| d.to_string()),
+        );
+        echo_arg(
+            args,
+            "symlinks",
+            "Symbolic links",
+            format!("{:?}", args.symlinks),
+        );
+        echo_arg(
+            args,
+            "newer_than",
+            "Newer than",
+            args.newer_than
+                .map_or("any time".to_string(), |newer_than| {
+                    format!(
+                        "{} seconds since epoch",
+                        newer_than
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs()
+                    )
+                }),
+        );
+        echo_arg(
+            args,
+            "max_insert_rows",
+            "Maximum rows per INSERT",
+            args.max_insert_rows
+                .map_or("unlimited".to_string(), |n| n.to_string()),
+        );
+        echo_arg(
+            args,
+            "max_insert_bytes",
+            "Maximum bytes per INSERT",
+            args.max_insert_bytes
+                .map_or("max_allowed_packet".to_string(), |n| n.to_string()),
+        );
+        println!("Input directories and files: {:?}", args.directories);
+        if let Some(Command::Watch(watch_args)) = &args.command {
+            println!("Watch directories: {:?}", watch_args.directories);
+            println!("Watch interval: {} s", watch_args.interval);
+            println!("Watch settle time: {} s", watch_args.settle_time);
+            println!("Watch state file: {}", watch_args.state_file);
+        }
+        if let Some(Command::Apply(apply_args)) = &args.command {
+            println!("Apply SQL file: {}", apply_args.sql_file);
+            println!("Resume: {}", apply_args.resume);
+            println!(
+                "Progress file: {}",
+                apply_args.progress_file().to_string_lossy()
+            );
+        }
+        if let Some(Command::Schema(SchemaCommand::Diff(diff_args))) = &args.command {
+            println!("Schema diff from: {}", diff_args.from);
+            println!("Schema diff to: {}", diff_args.to);
+        }
+        if let Some(Command::Schema(SchemaCommand::Dump(dump_args))) = &args.command {
+            println!("Schema dump files: {:?}", dump_args.files);
+            if let Some(output_dir) = &dump_args.output_dir {
+                println!("Schema dump output directory: {}", output_dir);
+            }
+        }
+        if let Some(Command::Db(DbCommand::Init(init_args))) = &args.command {
+            println!("Load sample data: {}", init_args.sample_data);
+            println!("Baseline only: {}", init_args.baseline);
+        }
+        if let Some(Command::Db(DbCommand::Migrate(migrate_args))) = &args.command {
+            println!(
+                "Migrate to version: {}",
+                migrate_args
+                    .to
+                    .map_or("latest".to_string(), |version| version.to_string())
+            );
+        }
+        if let Some(Command::Sqltypes(SqltypesCommand::FromDdl(from_ddl_args))) = &args.command {
+            println!("DDL file: {}", from_ddl_args.ddl_file);
+        }
     };
 }
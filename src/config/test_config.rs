@@ -0,0 +1,139 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::{Command, DuplicatePolicy};
+    use crate::config::parse_args_with_config;
+    use anyhow::Result;
+    use std::ffi::OsString;
+    use tempfile::NamedTempFile;
+
+    const CONFIG: &str = r#"
+sqltypes_file: /shared/sqltypes.yml
+module-file: /shared/modules.yml
+jobs: 2
+exclude:
+  - old
+  - scratch
+profiles:
+  production:
+    db_url: mysql://prod/lmx2db
+    jobs: 8
+    verbose: true
+  staging:
+    db_url: mysql://staging/lmx2db
+    on_duplicate: skip
+"#;
+
+    fn config_file(content: &str) -> Result<NamedTempFile> {
+        let config_file = NamedTempFile::new()?;
+        std::fs::write(config_file.path(), content)?;
+        Ok(config_file)
+    }
+
+    fn argv(config_file: &NamedTempFile, args: &[&str]) -> Vec<OsString> {
+        let mut argv: Vec<OsString> = vec![
+            "lmx2db".into(),
+            "--config".into(),
+            config_file.path().into(),
+        ];
+        argv.extend(args.iter().map(|arg| arg.into()));
+        argv
+    }
+
+    #[test]
+    fn test_profile_precedence() -> Result<()> {
+        let config_file = config_file(CONFIG)?;
+        temp_env::with_var_unset("LMX2DB_DATABASE_URL", || -> Result<()> {
+            let args = parse_args_with_config(argv(
+                &config_file,
+                &["--profile", "production", "-t", "my_sqltypes.yml", "dir1"],
+            ))?;
+            // Command line over profile over top-level configuration over default
+            assert_eq!(args.sqltypes_file, "my_sqltypes.yml");
+            assert_eq!(args.sources["sqltypes_file"], "command line");
+            assert_eq!(args.db_url, "mysql://prod/lmx2db");
+            assert_eq!(args.sources["db_url"], "profile production");
+            assert_eq!(args.jobs, 8);
+            assert!(args.verbose);
+            assert_eq!(args.module_file, "/shared/modules.yml");
+            assert_eq!(args.sources["module_file"], "config file");
+            assert_eq!(args.exclude.len(), 2);
+            assert_eq!(args.sql_file, "import.sql");
+            assert_eq!(args.sources["sql_file"], "default");
+            assert_eq!(args.directories, vec!["dir1"]);
+
+            let args = parse_args_with_config(argv(&config_file, &["--profile", "staging"]))?;
+            assert_eq!(args.db_url, "mysql://staging/lmx2db");
+            assert_eq!(args.on_duplicate, DuplicatePolicy::Skip);
+            assert_eq!(args.jobs, 2);
+            assert!(!args.verbose);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_environment_over_profile() -> Result<()> {
+        let config_file = config_file(CONFIG)?;
+        temp_env::with_var(
+            "LMX2DB_DATABASE_URL",
+            Some("mysql://env/lmx2db"),
+            || -> Result<()> {
+                let args =
+                    parse_args_with_config(argv(&config_file, &["--profile", "production"]))?;
+                assert_eq!(args.db_url, "mysql://env/lmx2db");
+                assert_eq!(args.sources["db_url"], "environment");
+                Ok(())
+            },
+        )
+    }
+
+    #[test]
+    fn test_config_with_subcommand() -> Result<()> {
+        let config_file = config_file(CONFIG)?;
+        let args = parse_args_with_config(argv(&config_file, &["watch", "dir1"]))?;
+        assert_eq!(args.sqltypes_file, "/shared/sqltypes.yml");
+        assert!(matches!(args.command, Some(Command::Watch(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_config() -> Result<()> {
+        let config_file = config_file(CONFIG)?;
+        let result = parse_args_with_config(argv(&config_file, &["--profile", "testing"]));
+        let error_message = format!("{}", result.unwrap_err());
+        assert!(
+            error_message.contains("Profile 'testing' not found"),
+            "Unexpected error message: {}",
+            error_message
+        );
+
+        let config_file = self::config_file("sql_fil: out.sql\n")?;
+        let result = parse_args_with_config(argv(&config_file, &[]));
+        let error_message = format!("{}", result.unwrap_err());
+        assert!(
+            error_message.contains("Unknown option 'sql_fil'"),
+            "Unexpected error message: {}",
+            error_message
+        );
+
+        let config_file = self::config_file("jobs: many\n")?;
+        assert!(parse_args_with_config(argv(&config_file, &[])).is_err());
+
+        let argv = vec!["lmx2db".into(), "--config".into(), "not_there.yml".into()];
+        assert!(parse_args_with_config(argv).is_err());
+        Ok(())
+    }
+}
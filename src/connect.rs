@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use anyhow::Result;
 use sqlx::mysql::MySqlPool;
 
 pub async fn connect_to_database(database_url: &str) -> Option<MySqlPool> {
@@ -25,6 +26,15 @@ pub async fn connect_to_database(database_url: &str) -> Option<MySqlPool> {
     }
 }
 
+/// Reads `max_allowed_packet` of the database server,
+/// which limits the size of a single statement.
+pub async fn read_max_allowed_packet(pool: &MySqlPool) -> Result<usize> {
+    let max_allowed_packet: u64 = sqlx::query_scalar("SELECT @@max_allowed_packet;")
+        .fetch_one(pool)
+        .await?;
+    Ok(usize::try_from(max_allowed_packet).unwrap_or(usize::MAX))
+}
+
 pub async fn disconnect_from_database(pool: Option<MySqlPool>) {
     if let Some(pool) = pool {
         pool.close().await;
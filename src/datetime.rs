@@ -0,0 +1,162 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(test)]
+pub(crate) mod test_datetime;
+
+/// A date `YYYY-MM-DD`, optionally followed by a time `hh:mm[:ss]`
+/// with optional fractional seconds, separated by a blank or `T`
+static DATE_TIME_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(\d{4})-(\d{1,2})-(\d{1,2})(?:[ T](\d{1,2}):(\d{2})(?::(\d{2})(?:\.\d{1,6})?)?)?$",
+    )
+    .unwrap()
+});
+
+const SECONDS_PER_DAY: u64 = 86400;
+
+/// A date and time of the proleptic Gregorian calendar without time zone.
+/// The fields are ordered so that the derived ordering is chronological.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl DateTime {
+    pub const fn new(year: u32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Self {
+        DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        }
+    }
+
+    /// Parses a date `YYYY-MM-DD` or, if `with_time` is set, a date optionally
+    /// followed by a time `hh:mm[:ss]` with optional fractional seconds, which
+    /// are ignored. Returns `None` if the value is no valid calendar date or time.
+    pub fn parse(value: &str, with_time: bool) -> Option<Self> {
+        let caps = DATE_TIME_PATTERN.captures(value.trim())?;
+        if !with_time && caps.get(4).is_some() {
+            return None;
+        }
+        let field = |i: usize| -> u32 { caps.get(i).map_or(0, |m| m.as_str().parse().unwrap()) };
+        let date_time = DateTime::new(field(1), field(2), field(3), field(4), field(5), field(6));
+        if date_time.month == 0
+            || date_time.month > 12
+            || date_time.day == 0
+            || date_time.day > days_in_month(date_time.year, date_time.month)
+            || date_time.hour > 23
+            || date_time.minute > 59
+            || date_time.second > 59
+        {
+            return None;
+        }
+        Some(date_time)
+    }
+
+    /// Converts a point in time into the UTC date and time.
+    /// Points in time before the epoch are mapped to the epoch.
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let (days, seconds) = (seconds / SECONDS_PER_DAY, seconds % SECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        DateTime::new(
+            year,
+            month,
+            day,
+            (seconds / 3600) as u32,
+            (seconds / 60 % 60) as u32,
+            (seconds % 60) as u32,
+        )
+    }
+
+    /// Converts the date and time, taken as UTC, into a point in time.
+    /// Returns `None` before the epoch.
+    pub fn to_system_time(self) -> Option<SystemTime> {
+        if self.year < 1970 {
+            return None;
+        }
+        let seconds = days_from_civil(self.year, self.month, self.day) * SECONDS_PER_DAY
+            + u64::from(self.hour) * 3600
+            + u64::from(self.minute) * 60
+            + u64::from(self.second);
+        Some(UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+}
+
+/// Formats the date and time as `YYYY-MM-DD hh:mm:ss`
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// Returns the number of days of `month` (1..=12) in `year`
+fn days_in_month(year: u32, month: u32) -> u32 {
+    let leap_year =
+        (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400);
+    match month {
+        2 if leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the epoch of a date from 1970 on
+fn days_from_civil(year: u32, month: u32, day: u32) -> u64 {
+    let (year, month, day) = (u64::from(year), u64::from(month), u64::from(day));
+    // Years start in March, so that the leap day is the last day of a year
+    let shifted_year = if month <= 2 { year - 1 } else { year };
+    let era = shifted_year / 400;
+    let year_of_era = shifted_year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Date of the given number of days since the epoch
+fn civil_from_days(days: u64) -> (u32, u32, u32) {
+    let shifted_days = days + 719468;
+    let era = shifted_days / 146097;
+    let day_of_era = shifted_days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = (shifted_month + 2) % 12 + 1;
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year as u32, month as u32, day as u32)
+}
@@ -0,0 +1,73 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::datetime::DateTime;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            DateTime::parse("2024-02-29", false),
+            Some(DateTime::new(2024, 2, 29, 0, 0, 0))
+        );
+        assert_eq!(
+            DateTime::parse("2024-1-5 7:30", true),
+            Some(DateTime::new(2024, 1, 5, 7, 30, 0))
+        );
+        assert_eq!(
+            DateTime::parse("2024-12-31T23:59:59.999", true),
+            Some(DateTime::new(2024, 12, 31, 23, 59, 59))
+        );
+        // Impossible dates and times
+        for value in [
+            "2023-02-29",
+            "2024-02-31",
+            "2024-04-31",
+            "2024-13-01",
+            "2024-00-10",
+        ] {
+            assert_eq!(DateTime::parse(value, true), None, "{}", value);
+        }
+        assert_eq!(DateTime::parse("2024-01-01 24:00:00", true), None);
+        assert_eq!(DateTime::parse("2024-01-01 12:00:00", false), None);
+        assert_eq!(DateTime::parse("yesterday", true), None);
+    }
+
+    #[test]
+    fn test_system_time() {
+        let date_time = DateTime::new(2000, 2, 29, 12, 30, 0);
+        let time = UNIX_EPOCH + Duration::from_secs(951827400);
+        assert_eq!(date_time.to_system_time(), Some(time));
+        assert_eq!(DateTime::from_system_time(time), date_time);
+        assert_eq!(
+            DateTime::from_system_time(UNIX_EPOCH).to_string(),
+            "1970-01-01 00:00:00"
+        );
+        assert_eq!(
+            DateTime::new(1969, 12, 31, 23, 59, 59).to_system_time(),
+            None
+        );
+        // Every day of a leap year and the following year round trips
+        let start = DateTime::new(2024, 1, 1, 0, 0, 0).to_system_time().unwrap();
+        for day in 0..731 {
+            let time = start + Duration::from_secs(day * 86400);
+            assert_eq!(
+                DateTime::from_system_time(time).to_system_time(),
+                Some(time)
+            );
+        }
+    }
+}
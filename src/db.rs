@@ -0,0 +1,389 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::apply::split_sql_statements;
+use crate::cmdline::{CliArgs, DbCommand, DbInitArgs, DbMigrateArgs};
+use crate::schema::BUNDLED_SCHEMA_FILES;
+use anyhow::{bail, Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sqlx::mysql::MySqlConnection;
+use sqlx::{MySql, Pool};
+
+#[cfg(test)]
+pub(crate) mod test_db;
+
+/// Table recording the schema versions applied by `db init` and `db migrate`
+pub const SCHEMA_VERSION_TABLE: &str = "lmx2db_schema_version";
+
+/// Version of the schema before any migration, recorded by `db init --baseline`
+/// for databases created by hand from the files of subdirectory `schema`
+pub const BASE_SCHEMA_VERSION: u32 = 1;
+
+/// Schema file with the sample data, only loaded by `db init --sample-data`
+const SAMPLE_DATA_FILE: &str = "minimal_data.sql";
+
+/// A change of the database schema shipped with lmx2db
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    /// The SQL statements, `DELIMITER` commands are supported
+    pub sql: &'static str,
+}
+
+/// Migrations shipped with lmx2db, ordered by version. A schema change is
+/// added as `schema/migrations/<version>_<name>.sql` with an entry here, e.g.
+/// `Migration { version: 2, name: "...", sql: include_str!("../schema/migrations/0002_....sql") }`.
+/// The files of subdirectory `schema` and `sqltypes.yml` are changed as well,
+/// so that `db init` creates the latest version directly.
+pub const MIGRATIONS: &[Migration] = &[];
+
+// `latest_version` and `run_migrations` rely on the order of the migrations
+const _: () = assert!(
+    migrations_ordered(MIGRATIONS),
+    "MIGRATIONS must have strictly increasing versions greater than BASE_SCHEMA_VERSION"
+);
+
+/// Whether the versions of `migrations` strictly increase, starting
+/// above `BASE_SCHEMA_VERSION`
+pub const fn migrations_ordered(migrations: &[Migration]) -> bool {
+    let mut version = BASE_SCHEMA_VERSION;
+    let mut i = 0;
+    while i < migrations.len() {
+        if migrations[i].version <= version {
+            return false;
+        }
+        version = migrations[i].version;
+        i += 1;
+    }
+    true
+}
+
+/// Returns the schema version after all `migrations`
+pub fn latest_version(migrations: &[Migration]) -> u32 {
+    migrations
+        .last()
+        .map_or(BASE_SCHEMA_VERSION, |migration| migration.version)
+}
+
+/// Returns the schema files loaded by `db init` in their order:
+/// tables, stored functions and procedures, view and MPI names,
+/// followed by the sample data if requested
+pub fn init_files(sample_data: bool) -> Vec<(&'static str, &'static str)> {
+    BUNDLED_SCHEMA_FILES
+        .iter()
+        .filter(|(name, _)| sample_data || *name != SAMPLE_DATA_FILE)
+        .copied()
+        .collect()
+}
+
+/// The `DEFINER` clause of a stored routine or view with its trailing blanks
+static DEFINER_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bDEFINER\s*=\s*(`[^`]*`|'[^']*'|[^\s@]+)@(`[^`]*`|'[^']*'|\S+)\s+").unwrap()
+});
+
+/// Removes the `DEFINER` clause of `CREATE FUNCTION`, `PROCEDURE` and `VIEW`
+/// statements, so that the connected user becomes the definer. Creating objects
+/// for other definers needs additional privileges, and the routines of the
+/// schema use `SQL SECURITY INVOKER` anyway.
+pub fn strip_definer(statement: &str) -> String {
+    DEFINER_PATTERN.replace(statement, "").to_string()
+}
+
+/// Name of a stored function, procedure or view created by a schema file
+static ROUTINE_OR_VIEW_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bCREATE\b[^;(]*?\b(?:FUNCTION|PROCEDURE|VIEW)\s+`?(\w+)`?").unwrap()
+});
+
+/// Returns the names of the stored functions, procedures and views
+/// created by the schema files of `db init`
+pub fn bundled_routines_and_views() -> Vec<&'static str> {
+    init_files(false)
+        .into_iter()
+        .flat_map(|(_, sql)| ROUTINE_OR_VIEW_PATTERN.captures_iter(sql))
+        .filter_map(|captures| captures.get(1).map(|name| name.as_str()))
+        .collect()
+}
+
+/// Fails for a database with the lmx2db tables but without some of the
+/// bundled functions, procedures or views, which is what an interrupted
+/// `db init` leaves behind. Recording a version for it would hide the gap.
+async fn check_schema_complete(conn: &mut MySqlConnection) -> Result<()> {
+    let existing: Vec<String> = sqlx::query_scalar(
+        "SELECT ROUTINE_NAME FROM information_schema.ROUTINES WHERE ROUTINE_SCHEMA = DATABASE() \
+         UNION SELECT TABLE_NAME FROM information_schema.VIEWS WHERE TABLE_SCHEMA = DATABASE()",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let missing: Vec<&str> = bundled_routines_and_views()
+        .into_iter()
+        .filter(|name| {
+            !existing
+                .iter()
+                .any(|found| found.eq_ignore_ascii_case(name))
+        })
+        .collect();
+    if !missing.is_empty() {
+        bail!(
+            "Database contains the lmx2db tables, but lacks {}, e.g. after a failed `db init`. \
+             Drop and recreate the database, then run `db init` again",
+            missing.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Statement creating the table of schema versions
+fn schema_version_table_ddl() -> String {
+    format!(
+        "CREATE TABLE IF NOT EXISTS `{}` (\
+         `version` int(11) NOT NULL, \
+         `name` varchar(256) NOT NULL, \
+         `lmx2db_version` varchar(32) NOT NULL, \
+         `applied` timestamp NOT NULL DEFAULT current_timestamp(), \
+         PRIMARY KEY (`version`)\
+         ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb3 COLLATE=utf8mb3_general_ci",
+        SCHEMA_VERSION_TABLE
+    )
+}
+
+/// Whether `table` exists in the current database
+async fn table_exists(conn: &mut MySqlConnection, table: &str) -> Result<bool> {
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM information_schema.TABLES \
+         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?",
+    )
+    .bind(table)
+    .fetch_one(&mut *conn)
+    .await?;
+    Ok(count > 0)
+}
+
+/// Returns the schema version recorded in the database, `None` if the
+/// database has not been initialized by lmx2db
+pub async fn current_version(conn: &mut MySqlConnection) -> Result<Option<u32>> {
+    if !table_exists(conn, SCHEMA_VERSION_TABLE).await? {
+        return Ok(None);
+    }
+    let version: Option<i64> = sqlx::query_scalar(&format!(
+        "SELECT MAX(version) FROM `{}`",
+        SCHEMA_VERSION_TABLE
+    ))
+    .fetch_one(&mut *conn)
+    .await?;
+    Ok(version.map(|version| version as u32))
+}
+
+/// Records that the schema has reached `version`
+async fn record_version(conn: &mut MySqlConnection, version: u32, name: &str) -> Result<()> {
+    sqlx::raw_sql(&schema_version_table_ddl())
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(&format!(
+        "INSERT INTO `{}` (version, name, lmx2db_version) VALUES (?, ?, ?)",
+        SCHEMA_VERSION_TABLE
+    ))
+    .bind(version)
+    .bind(name)
+    .bind(env!("CARGO_PKG_VERSION"))
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Executes the statements of a SQL script one by one. DDL statements
+/// are committed implicitly by the database, so a failing script is not
+/// rolled back.
+async fn execute_script(
+    conn: &mut MySqlConnection,
+    name: &str,
+    sql: &str,
+    args: &CliArgs,
+) -> Result<()> {
+    let statements = split_sql_statements(sql, name)?;
+    if args.verbose || args.dry_run {
+        println!("Loading {} ({} statements)", name, statements.len());
+    }
+    for statement in statements {
+        let statement = strip_definer(&statement);
+        if args.dry_run {
+            println!("Executing query: {}", statement);
+            continue;
+        }
+        sqlx::raw_sql(&statement)
+            .execute(&mut *conn)
+            .await
+            .with_context(|| format!("Failed to load {}", name))?;
+    }
+    Ok(())
+}
+
+/// Creates the lmx2db schema in an empty database from the schema files
+/// compiled into the binary and records the latest schema version.
+/// With `--baseline`, the files are not loaded, but the base version is
+/// recorded for a database created by hand, so that `db migrate` can update it.
+///
+/// The schema files are not loaded in a transaction, as DDL statements are
+/// committed implicitly. If a statement fails, the database keeps the objects
+/// created so far without a recorded version. It has to be dropped and
+/// recreated before running `db init` again.
+///
+/// # Errors
+/// Returns an error if the database has been initialized before, if it
+/// already contains (or, with `--baseline`, lacks) the lmx2db tables,
+/// or if a statement fails
+///
+pub async fn init_database(
+    conn: &mut MySqlConnection,
+    init_args: &DbInitArgs,
+    args: &CliArgs,
+) -> Result<()> {
+    if let Some(version) = current_version(conn).await? {
+        bail!(
+            "Database has been initialized before, its schema version is {}",
+            version
+        );
+    }
+    let has_tables = table_exists(conn, "runs").await?;
+    if init_args.baseline {
+        if !has_tables {
+            bail!("Database does not contain the lmx2db tables, use `db init` without --baseline");
+        }
+        if args.verbose || args.dry_run {
+            println!("Recording schema version {}", BASE_SCHEMA_VERSION);
+        }
+        if !args.dry_run {
+            record_version(conn, BASE_SCHEMA_VERSION, "baseline").await?;
+        }
+        return Ok(());
+    }
+    if has_tables {
+        check_schema_complete(conn).await?;
+        bail!(
+            "Database already contains the lmx2db tables, use `db init --baseline` to record their schema version"
+        );
+    }
+    for (name, sql) in init_files(init_args.sample_data) {
+        execute_script(conn, name, sql, args).await?;
+    }
+    let version = latest_version(MIGRATIONS);
+    if args.verbose || args.dry_run {
+        println!("Recording schema version {}", version);
+    }
+    if !args.dry_run {
+        record_version(conn, version, "initial schema").await?;
+    }
+    Ok(())
+}
+
+/// Applies the migrations newer than the schema version of the database,
+/// up to version `target` if given. The version is recorded after each
+/// migration, so that a failing migration can be fixed and retried.
+///
+/// # Arguments
+/// * `conn` - The database connection
+/// * `migrations` - The migrations, ordered by version
+/// * `target` - The last version to migrate to, all migrations if `None`
+/// * `args` - Command line arguments controlling verbosity and dry-run mode
+///
+/// # Returns
+/// Returns the number of applied migrations
+///
+/// # Errors
+/// Returns an error if the database has not been initialized by lmx2db,
+/// its version is newer than the latest migration, or a migration fails
+///
+pub async fn run_migrations(
+    conn: &mut MySqlConnection,
+    migrations: &[Migration],
+    target: Option<u32>,
+    args: &CliArgs,
+) -> Result<usize> {
+    if !migrations_ordered(migrations) {
+        bail!(
+            "Migrations must have strictly increasing versions greater than {}",
+            BASE_SCHEMA_VERSION
+        );
+    }
+    let Some(version) = current_version(conn).await? else {
+        if table_exists(conn, "runs").await? {
+            check_schema_complete(conn).await?;
+            bail!(
+                "Database has no schema version, use `db init --baseline` to record the version of its tables"
+            );
+        }
+        bail!("Database has not been initialized, use `db init` to create the lmx2db schema");
+    };
+    let latest = latest_version(migrations);
+    if version > latest {
+        bail!(
+            "Database schema version {} is newer than the latest version {} known to this lmx2db",
+            version,
+            latest
+        );
+    }
+    let target = target.unwrap_or(latest);
+    let pending: Vec<&Migration> = migrations
+        .iter()
+        .filter(|migration| migration.version > version && migration.version <= target)
+        .collect();
+    if args.verbose || args.dry_run || pending.is_empty() {
+        println!(
+            "Database schema version {}, {} migrations to apply",
+            version,
+            pending.len()
+        );
+    }
+    for migration in &pending {
+        let name = format!("migration {} ({})", migration.version, migration.name);
+        execute_script(conn, &name, migration.sql, args).await?;
+        if !args.dry_run {
+            record_version(conn, migration.version, migration.name).await?;
+        }
+    }
+    Ok(pending.len())
+}
+
+/// Updates the database to the latest schema version shipped with lmx2db
+pub async fn migrate_database(
+    conn: &mut MySqlConnection,
+    migrate_args: &DbMigrateArgs,
+    args: &CliArgs,
+) -> Result<()> {
+    let applied = run_migrations(conn, MIGRATIONS, migrate_args.to, args).await?;
+    if applied > 0 && !args.dry_run {
+        println!("Applied {} migrations", applied);
+    }
+    Ok(())
+}
+
+/// Runs a subcommand of `db`
+pub async fn db_command(
+    command: &DbCommand,
+    pool: &Option<Pool<MySql>>,
+    args: &CliArgs,
+) -> Result<u8> {
+    let Some(pool) = pool.as_ref() else {
+        bail!("The db subcommand needs a database connection (option -u, --db-url)");
+    };
+    // All scripts run on the same connection, e.g. for the session
+    // variables of the sample data
+    let mut conn = pool.acquire().await?;
+    match command {
+        DbCommand::Init(init_args) => init_database(&mut conn, init_args, args).await?,
+        DbCommand::Migrate(migrate_args) => migrate_database(&mut conn, migrate_args, args).await?,
+    }
+    Ok(0)
+}
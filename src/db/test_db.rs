@@ -0,0 +1,215 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::apply::split_sql_statements;
+    use crate::cmdline::{CliArgs, DbInitArgs};
+    use crate::db::{
+        bundled_routines_and_views, current_version, init_database, init_files, latest_version,
+        migrations_ordered, run_migrations, strip_definer, Migration, BASE_SCHEMA_VERSION,
+        MIGRATIONS,
+    };
+    use crate::schema::bundled_file;
+    use anyhow::Result;
+    use sqlx::MySql;
+
+    const TEST_MIGRATIONS: &[Migration] = &[
+        Migration {
+            version: 2,
+            name: "add comments",
+            sql: "CREATE TABLE comments (id int(11) NOT NULL, text varchar(256));",
+        },
+        Migration {
+            version: 3,
+            name: "add comment function",
+            sql: "DELIMITER //\n\
+                  CREATE DEFINER=`root`@`localhost` FUNCTION count_comments() RETURNS int(11)\n\
+                  READS SQL DATA\n\
+                  BEGIN\n\
+                  RETURN (SELECT COUNT(*) FROM comments);\n\
+                  END //\n\
+                  DELIMITER ;\n",
+        },
+    ];
+
+    #[test]
+    fn test_strip_definer() {
+        assert_eq!(
+            strip_definer("CREATE DEFINER=`root`@`localhost` FUNCTION f() RETURNS int"),
+            "CREATE FUNCTION f() RETURNS int"
+        );
+        assert_eq!(
+            strip_definer(
+                "CREATE ALGORITHM=UNDEFINED DEFINER=lmxdb@'%' SQL SECURITY INVOKER VIEW v"
+            ),
+            "CREATE ALGORITHM=UNDEFINED SQL SECURITY INVOKER VIEW v"
+        );
+        assert_eq!(strip_definer("SELECT 1"), "SELECT 1");
+    }
+
+    #[test]
+    fn test_init_files() {
+        let names: Vec<&str> = init_files(false).iter().map(|(name, _)| *name).collect();
+        assert_eq!(names[0], "tables.sql");
+        assert!(!names.contains(&"minimal_data.sql"));
+        let names: Vec<&str> = init_files(true).iter().map(|(name, _)| *name).collect();
+        assert_eq!(names.last(), Some(&"minimal_data.sql"));
+    }
+
+    #[test]
+    fn test_latest_version() {
+        assert_eq!(latest_version(&[]), BASE_SCHEMA_VERSION);
+        assert_eq!(latest_version(TEST_MIGRATIONS), 3);
+    }
+
+    #[test]
+    fn test_migrations_ordered() {
+        // Shipped migrations are ordered and newer than the base version
+        assert!(migrations_ordered(MIGRATIONS));
+        assert!(migrations_ordered(TEST_MIGRATIONS));
+        assert!(migrations_ordered(&[]));
+        let reversed = [TEST_MIGRATIONS[1], TEST_MIGRATIONS[0]];
+        assert!(!migrations_ordered(&reversed));
+        let duplicate = [TEST_MIGRATIONS[0], TEST_MIGRATIONS[0]];
+        assert!(!migrations_ordered(&duplicate));
+        let base = Migration {
+            version: BASE_SCHEMA_VERSION,
+            ..TEST_MIGRATIONS[0]
+        };
+        assert!(!migrations_ordered(&[base]));
+    }
+
+    #[test]
+    fn test_split_schema_files() -> Result<()> {
+        for (name, sql) in init_files(true) {
+            let statements = split_sql_statements(sql, name)?;
+            assert!(!statements.is_empty(), "{}", name);
+        }
+        // Stored routines are split at the custom delimiter
+        let statements =
+            split_sql_statements(bundled_file("functions.sql").unwrap(), "functions.sql")?;
+        assert!(statements
+            .iter()
+            .any(|statement| statement.contains("FUNCTION") && statement.contains("RETURN")));
+        let statements = split_sql_statements(TEST_MIGRATIONS[1].sql, "test")?;
+        assert_eq!(statements.len(), 1);
+        assert!(statements[0].ends_with("END"), "{}", statements[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundled_routines_and_views() {
+        let names = bundled_routines_and_views();
+        assert!(names.contains(&"cluster_id"));
+        assert!(names.contains(&"environ_value"));
+        assert!(names.contains(&"Alphabetic_list_of_customer_cases"));
+        // Tables are not listed
+        assert!(!names.contains(&"runs"));
+    }
+
+    #[sqlx::test]
+    async fn test_init_and_migrate(pool: sqlx::Pool<MySql>) -> Result<()> {
+        let args = CliArgs::default();
+        let mut conn = pool.acquire().await?;
+        assert_eq!(current_version(&mut conn).await?, None);
+        // Migrations need a version of the schema
+        assert!(run_migrations(&mut conn, TEST_MIGRATIONS, None, &args)
+            .await
+            .is_err());
+
+        let init_args = DbInitArgs {
+            sample_data: true,
+            baseline: false,
+        };
+        init_database(&mut conn, &init_args, &args).await?;
+        assert_eq!(
+            current_version(&mut conn).await?,
+            Some(latest_version(MIGRATIONS))
+        );
+        let runs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM runs")
+            .fetch_one(&mut *conn)
+            .await?;
+        assert!(runs > 0);
+        // A second init is rejected
+        assert!(init_database(&mut conn, &init_args, &args).await.is_err());
+
+        // Migrate step by step
+        assert_eq!(
+            run_migrations(&mut conn, TEST_MIGRATIONS, Some(2), &args).await?,
+            1
+        );
+        assert_eq!(current_version(&mut conn).await?, Some(2));
+        assert_eq!(
+            run_migrations(&mut conn, TEST_MIGRATIONS, None, &args).await?,
+            1
+        );
+        assert_eq!(current_version(&mut conn).await?, Some(3));
+        let comments: i64 = sqlx::query_scalar("SELECT count_comments()")
+            .fetch_one(&mut *conn)
+            .await?;
+        assert_eq!(comments, 0);
+        assert_eq!(
+            run_migrations(&mut conn, TEST_MIGRATIONS, None, &args).await?,
+            0
+        );
+
+        // An older lmx2db does not know the schema of the database
+        assert!(
+            run_migrations(&mut conn, &TEST_MIGRATIONS[..1], None, &args)
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../tests/fixtures/tables.sql"))]
+    async fn test_init_baseline(pool: sqlx::Pool<MySql>) -> Result<()> {
+        let args = CliArgs::default();
+        let mut conn = pool.acquire().await?;
+        let mut init_args = DbInitArgs {
+            sample_data: false,
+            baseline: false,
+        };
+        // The tables exist already, but without the functions and the view
+        // they look like a failed init, which must not be baselined
+        let error = init_database(&mut conn, &init_args, &args)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Drop and recreate the database"),
+            "{}",
+            error
+        );
+        assert!(error.contains("cluster_id"), "{}", error);
+
+        // A dry run records nothing
+        let dry_run = CliArgs {
+            dry_run: true,
+            ..Default::default()
+        };
+        init_args.baseline = true;
+        init_database(&mut conn, &init_args, &dry_run).await?;
+        assert_eq!(current_version(&mut conn).await?, None);
+
+        init_database(&mut conn, &init_args, &args).await?;
+        assert_eq!(current_version(&mut conn).await?, Some(BASE_SCHEMA_VERSION));
+        assert_eq!(
+            run_migrations(&mut conn, TEST_MIGRATIONS, None, &args).await?,
+            2
+        );
+        Ok(())
+    }
+}
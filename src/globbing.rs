@@ -12,17 +12,76 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::{bail, Result};
-use glob::glob;
+use crate::archive::{extract_archive, is_archive};
+use crate::cmdline::{CliArgs, SymlinkPolicy};
+use anyhow::{bail, Context, Result};
+use glob::{glob, Pattern};
 use regex::Regex;
-use std::path::Path;
+use std::collections::{BTreeMap, HashSet};
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
 
+#[cfg(test)]
+pub(crate) mod lmx_summary_files;
 #[cfg(test)]
 pub(crate) mod lmx_type_files;
 
-pub fn find_lmx_summary_files(paths: &Vec<String>) -> Result<Vec<String>> {
-    let mut result = Vec::new();
+/// File name suffixes of LMX YAML files: plain, gzip and zstd compressed
+pub const LMX_YAML_SUFFIXES: [&str; 3] = [".yml", ".yml.gz", ".yml.zst"];
+
+/// Checks whether a file name ends with one of the `LMX_YAML_SUFFIXES`.
+pub fn has_lmx_yaml_suffix(name: &str) -> bool {
+    LMX_YAML_SUFFIXES
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+/// LMX_summary files found by `find_lmx_summary_files`
+#[derive(Debug)]
+pub struct LmxSummaryFiles {
+    /// The file names, in the order they were found
+    pub files: Vec<String>,
+    /// Temporary directories of the archives holding some of the files,
+    /// which are removed when dropped
+    pub archives: Vec<TempDir>,
+}
+
+/// Function returning the list of LMX_summary files for the given paths.
+/// Each path is either a directory, which is searched recursively for
+/// files matching "LMX_summary*.yml" (optionally compressed with gzip
+/// or zstd, i.e. ending in ".yml.gz" or ".yml.zst"), a single LMX_summary file, or
+/// an archive (.tar, .tar.gz, .tgz, .zip). The run directories of archives are
+/// extracted into a temporary directory, which is then searched like a directory.
+/// Archives are not read in place, see `extract_archive`.
+/// Files found more than once are only returned once, in the order
+/// they were first found.
+///
+/// The search of directories is controlled by the options `--exclude`,
+/// `--include`, `--max-depth` and `--symlinks`. The option `--newer-than`
+/// applies to all LMX_summary files, including those given directly.
+///
+/// # Arguments
+/// * `paths` - Directories or LMX_summary files
+/// * `args` - Reference to command line arguments controlling the search
+///
+/// # Returns
+/// A Result containing the LMX_summary file names or an error. Files extracted
+/// from archives only exist as long as the returned `LmxSummaryFiles` is kept.
+///
+/// Errors if a path does not exist or is neither a directory nor
+/// an LMX_summary file, or if a directory cannot be read.
+///
+pub fn find_lmx_summary_files(paths: &Vec<String>, args: &CliArgs) -> Result<LmxSummaryFiles> {
+    let mut scan = DirectoryScan {
+        args,
+        result: Vec::new(),
+        seen: HashSet::new(),
+        visited: HashSet::new(),
+        archive: None,
+    };
 
+    let mut archives = Vec::new();
     for path_str in paths {
         let path = Path::new(&path_str);
 
@@ -30,29 +89,200 @@ pub fn find_lmx_summary_files(paths: &Vec<String>) -> Result<Vec<String>> {
             bail!("Path does not exist: {}", path_str);
         }
 
-        if !path.is_dir() {
+        scan.archive = None;
+        let search_dir = if path.is_file() && is_archive(path) {
+            let temp_dir = extract_archive(path)?;
+            let root = temp_dir.path().to_path_buf();
+            archives.push(temp_dir);
+            scan.archive = Some((root.clone(), path.canonicalize()?));
+            root
+        } else if path.is_file() {
+            if !is_lmx_summary_file(path) {
+                bail!(
+                    "Path is neither a directory, an archive nor an LMX_summary file: {}",
+                    path_str
+                );
+            }
+            scan.add_file(path)?;
+            continue;
+        } else if path.is_dir() {
+            path.to_path_buf()
+        } else {
             bail!("Path is not a directory: {}", path_str);
+        };
+
+        let included = args.include.is_empty() || scan.matches(&args.include, &search_dir);
+        scan.scan_directory(&search_dir, 0, included)?;
+    }
+
+    Ok(LmxSummaryFiles {
+        files: scan.result,
+        archives,
+    })
+}
+
+/// State of the search for LMX_summary files in directories
+struct DirectoryScan<'a> {
+    args: &'a CliArgs,
+    /// LMX_summary files found so far, in the order they were found
+    result: Vec<String>,
+    /// File names in `result`, for removing duplicates
+    seen: HashSet<String>,
+    /// Canonical paths of directories searched so far, for breaking symlink loops
+    visited: HashSet<PathBuf>,
+    /// Root directory and path of the archive being searched, if any
+    archive: Option<(PathBuf, PathBuf)>,
+}
+
+impl DirectoryScan<'_> {
+    /// Checks whether any of the patterns matches the name or the path of a directory.
+    /// Directories inside extracted archives are matched by their archive member path.
+    fn matches(&self, patterns: &[Pattern], dir: &Path) -> bool {
+        let dir_path = self.display_path(dir);
+        let name = dir.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        patterns
+            .iter()
+            .any(|pattern| pattern.matches(name) || pattern.matches_path(&dir_path))
+    }
+
+    /// Maps a directory inside the archive being searched to the path of the
+    /// archive member, like `archive::display_path` without file access.
+    fn display_path(&self, dir: &Path) -> PathBuf {
+        match &self.archive {
+            Some((root, archive)) => dir
+                .strip_prefix(root)
+                .map_or_else(|_| dir.to_path_buf(), |member| archive.join(member)),
+            None => dir.to_path_buf(),
+        }
+    }
+
+    /// Adds an LMX_summary file, unless it was already found
+    /// or is not newer than the time given with `--newer-than`.
+    fn add_file(&mut self, path: &Path) -> Result<()> {
+        if let Some(newer_than) = self.args.newer_than {
+            if std::fs::metadata(path)?.modified()? <= newer_than {
+                return Ok(());
+            }
+        }
+        let path_str = path.to_string_lossy().to_string();
+        if self.seen.insert(path_str.clone()) {
+            self.result.push(path_str);
         }
+        Ok(())
+    }
 
-        let pattern = format!("{}/**/LMX_summary*.yml", path_str.trim_end_matches('/'));
+    /// Searches a directory recursively for LMX_summary files. The files of
+    /// a directory are added before the files of its subdirectories, both
+    /// in alphabetical order.
+    ///
+    /// # Arguments
+    /// * `dir` - The directory to search
+    /// * `depth` - Number of levels of `dir` below the input directory
+    /// * `included` - Whether `dir` or one of its parents matches an `--include` pattern
+    ///
+    fn scan_directory(&mut self, dir: &Path, depth: usize, included: bool) -> Result<()> {
+        if !self.visited.insert(dir.canonicalize()?) {
+            return Ok(());
+        }
+        let mut entries = std::fs::read_dir(dir)
+            .with_context(|| format!("Cannot read directory {}", dir.display()))?
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+
+        let follow_symlinks = self.args.symlinks == SymlinkPolicy::Follow;
+        let mut subdirs = Vec::new();
+        for entry in entries {
+            let path = entry.path();
+            let mut file_type = entry.file_type()?;
+            if file_type.is_symlink() {
+                if !follow_symlinks {
+                    continue;
+                }
+                // Skip dangling symbolic links
+                let Ok(metadata) = std::fs::metadata(&path) else {
+                    continue;
+                };
+                file_type = metadata.file_type();
+            }
+            if file_type.is_dir() {
+                subdirs.push(path);
+            } else if included && file_type.is_file() && is_lmx_summary_file(&path) {
+                self.add_file(&path)?;
+            }
+        }
 
-        for entry in glob(&pattern)? {
-            let path = entry?;
-            if let Some(path_str) = path.to_str() {
-                result.push(path_str.to_string());
+        if self
+            .args
+            .max_depth
+            .is_some_and(|max_depth| depth >= max_depth)
+        {
+            return Ok(());
+        }
+        for subdir in subdirs {
+            if self.matches(&self.args.exclude, &subdir) {
+                if self.args.verbose || self.args.dry_run {
+                    println!(
+                        "Excluding directory {}",
+                        self.display_path(&subdir).display()
+                    );
+                }
+                continue;
             }
+            let included = included || self.matches(&self.args.include, &subdir);
+            self.scan_directory(&subdir, depth + 1, included)?;
         }
+        Ok(())
     }
+}
 
+/// Checks whether the file name of the given path matches "LMX_summary*.yml",
+/// "LMX_summary*.yml.gz" or "LMX_summary*.yml.zst".
+fn is_lmx_summary_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("LMX_summary") && has_lmx_yaml_suffix(name))
+}
+
+/// Function reading a list of input paths, one per line, from a file
+/// or from stdin if `source` is "-". Leading and trailing white space
+/// is removed, empty lines and lines starting with '#' are ignored.
+///
+/// # Arguments
+/// * `source` - Name of the file with the list, or "-" for stdin
+///
+/// # Returns
+/// A Result containing a vector of paths or an error.
+///
+/// Errors if the file cannot be opened or read.
+///
+pub fn read_file_list(source: &str) -> Result<Vec<String>> {
+    let reader: Box<dyn BufRead> = if source == "-" {
+        Box::new(std::io::stdin().lock())
+    } else {
+        let file = std::fs::File::open(source)
+            .map_err(|e| anyhow::anyhow!("Cannot open file list '{}': {}", source, e))?;
+        Box::new(std::io::BufReader::new(file))
+    };
+    let mut result = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let entry = line.trim();
+        if !entry.is_empty() && !entry.starts_with('#') {
+            result.push(entry.to_string());
+        }
+    }
     Ok(result)
 }
 
 /// Function returning the list of files matching
 /// format!("LMX_{}_profile.{}*.yml", type_str, process_id)
-/// in the same directory as the provided file_name.
-/// The typical file_name is an LMX_summary file.
-/// The process_id is extracted from the file_name using a
-/// regex pattern matching "LMX_summary\.(\d+)\.\d+\.yml".
+/// in the same directory as the provided file_name, including files
+/// compressed with gzip (".yml.gz") or zstd (".yml.zst").
+/// The typical file_name is an LMX_summary file, which may be
+/// compressed as well. The process_id is extracted from the file_name
+/// using a regex pattern matching "LMX_summary\.(\d+)\.\d+\.yml".
+/// A profile found both plain and compressed, e.g. after an interrupted
+/// compression, is returned only once, as the plain file.
 ///
 /// # Arguments
 /// * `file_name` - The reference file name to determine the directory.
@@ -61,7 +291,8 @@ pub fn find_lmx_summary_files(paths: &Vec<String>) -> Result<Vec<String>> {
 /// # Returns
 /// A Result containing a vector of matching file names or an error.
 ///
-/// Errors if the process ID cannot be extracted or if globbing fails.
+/// Errors if the process ID cannot be extracted, if globbing fails, or if a
+/// profile exists in several compressed copies, but not as plain file.
 ///
 pub fn find_lmx_type_files(file_name: &str, type_str: &str) -> Result<Vec<String>> {
     let path = Path::new(file_name);
@@ -79,16 +310,40 @@ pub fn find_lmx_type_files(file_name: &str, type_str: &str) -> Result<Vec<String
         bail!("Cannot extract process ID from file name: {}", file_name);
     }
     let pattern = format!(
-        "{}/LMX_{}_profile.{}*.yml",
+        "{}/LMX_{}_profile.{}*.yml*",
         parent_dir.display(),
         type_str,
         process_id
     );
-    let mut result = Vec::new();
+    // The copies of each profile, keyed by the name of the plain file
+    let mut profiles: BTreeMap<String, Vec<String>> = BTreeMap::new();
     for entry in glob(&pattern)? {
         let path = entry?;
         if let Some(path_str) = path.to_str() {
-            result.push(path_str.to_string());
+            if has_lmx_yaml_suffix(path_str) {
+                let plain_name = path_str
+                    .strip_suffix(".gz")
+                    .or_else(|| path_str.strip_suffix(".zst"))
+                    .unwrap_or(path_str);
+                profiles
+                    .entry(plain_name.to_string())
+                    .or_default()
+                    .push(path_str.to_string());
+            }
+        }
+    }
+    let mut result = Vec::new();
+    for (plain_name, copies) in profiles {
+        if copies.contains(&plain_name) {
+            result.push(plain_name);
+        } else if let [copy] = copies.as_slice() {
+            result.push(copy.clone());
+        } else {
+            bail!(
+                "Profile {} exists in several compressed copies: {}",
+                plain_name,
+                copies.join(", ")
+            );
         }
     }
     Ok(result)
@@ -0,0 +1,217 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::{parse_timestamp, CliArgs, SymlinkPolicy};
+    use crate::globbing::{find_lmx_summary_files, read_file_list};
+    use anyhow::Result;
+    use glob::Pattern;
+    use std::path::Path;
+
+    #[test]
+    fn test_single_lmx_summary_file() -> Result<()> {
+        let file_name = "tests/data/NAMD/run_0001/LMX_summary.225250.0.yml".to_string();
+        let files = find_lmx_summary_files(&vec![file_name.clone()], &CliArgs::default())?.files;
+        assert_eq!(files, vec![file_name]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_other_file_is_rejected() -> Result<()> {
+        let result = find_lmx_summary_files(
+            &vec!["tests/data/NAMD/project.yml".to_string()],
+            &CliArgs::default(),
+        );
+        assert!(result.is_err());
+        let error_message = format!("{}", result.unwrap_err());
+        assert!(
+            error_message
+                .contains("Path is neither a directory, an archive nor an LMX_summary file"),
+            "Unexpected error message: {}",
+            error_message
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_files_and_directories_without_duplicates() -> Result<()> {
+        // The file is also found in the directory and must only be listed once
+        let files = find_lmx_summary_files(
+            &vec![
+                "tests/data/NAMD/run_0002/LMX_summary.223561.0.yml".to_string(),
+                "tests/data/NAMD/run_0002".to_string(),
+                "tests/data/GROMACS".to_string(),
+            ],
+            &CliArgs::default(),
+        )?
+        .files;
+        assert_eq!(
+            files,
+            vec![
+                "tests/data/NAMD/run_0002/LMX_summary.223561.0.yml",
+                "tests/data/GROMACS/run_64/LMX_summary.376231.0.yml",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_file_list() -> Result<()> {
+        let temp_file = tempfile::NamedTempFile::new()?;
+        std::fs::write(
+            temp_file.path(),
+            "# runs to import\n\
+             tests/data/NAMD/run_0001/LMX_summary.225250.0.yml\n\
+             \n  tests/data/GROMACS  \n",
+        )?;
+        let paths = read_file_list(temp_file.path().to_str().unwrap())?;
+        assert_eq!(
+            paths,
+            vec![
+                "tests/data/NAMD/run_0001/LMX_summary.225250.0.yml",
+                "tests/data/GROMACS",
+            ]
+        );
+        let files = find_lmx_summary_files(&paths, &CliArgs::default())?.files;
+        assert_eq!(files.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_file_list() {
+        let result = read_file_list("not_there.txt");
+        assert!(result.is_err());
+    }
+
+    /// Creates the following tree of LMX_summary files below `root`:
+    /// - `LMX_summary.1.0.yml`
+    /// - `project/run_1/LMX_summary.2.0.yml`
+    /// - `project/old/run_1/LMX_summary.3.0.yml`
+    /// - `scratch/LMX_summary.4.0.yml`
+    /// - `link` - symbolic link to `other`, which contains `LMX_summary.5.0.yml`
+    fn create_tree(root: &Path, other: &Path) -> Result<()> {
+        for file_name in [
+            "LMX_summary.1.0.yml",
+            "project/run_1/LMX_summary.2.0.yml",
+            "project/old/run_1/LMX_summary.3.0.yml",
+            "scratch/LMX_summary.4.0.yml",
+        ] {
+            let path = root.join(file_name);
+            std::fs::create_dir_all(path.parent().unwrap())?;
+            std::fs::write(&path, "mock content")?;
+        }
+        std::fs::write(other.join("LMX_summary.5.0.yml"), "mock content")?;
+        std::os::unix::fs::symlink(other, root.join("link"))?;
+        Ok(())
+    }
+
+    /// Returns the found files relative to `root`
+    fn find_relative(root: &Path, args: &CliArgs) -> Result<Vec<String>> {
+        let files = find_lmx_summary_files(&vec![root.to_str().unwrap().to_string()], args)?.files;
+        Ok(files
+            .iter()
+            .map(|f| {
+                Path::new(f)
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect())
+    }
+
+    #[test]
+    fn test_scan_filters() -> Result<()> {
+        let root = tempfile::tempdir()?;
+        let other = tempfile::tempdir()?;
+        create_tree(root.path(), other.path())?;
+
+        // Without filters, all files are found, symbolic links are followed
+        let files = find_relative(root.path(), &CliArgs::default())?;
+        assert_eq!(
+            files,
+            vec![
+                "LMX_summary.1.0.yml",
+                "link/LMX_summary.5.0.yml",
+                "project/old/run_1/LMX_summary.3.0.yml",
+                "project/run_1/LMX_summary.2.0.yml",
+                "scratch/LMX_summary.4.0.yml",
+            ]
+        );
+
+        let args = CliArgs {
+            exclude: vec![Pattern::new("old")?, Pattern::new("*/scratch")?],
+            symlinks: SymlinkPolicy::Ignore,
+            ..Default::default()
+        };
+        assert_eq!(
+            find_relative(root.path(), &args)?,
+            vec!["LMX_summary.1.0.yml", "project/run_1/LMX_summary.2.0.yml"]
+        );
+
+        let args = CliArgs {
+            include: vec![Pattern::new("run_*")?],
+            ..Default::default()
+        };
+        assert_eq!(
+            find_relative(root.path(), &args)?,
+            vec![
+                "project/old/run_1/LMX_summary.3.0.yml",
+                "project/run_1/LMX_summary.2.0.yml",
+            ]
+        );
+
+        let args = CliArgs {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            find_relative(root.path(), &args)?,
+            vec![
+                "LMX_summary.1.0.yml",
+                "link/LMX_summary.5.0.yml",
+                "scratch/LMX_summary.4.0.yml",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_newer_than() -> Result<()> {
+        let root = tempfile::tempdir()?;
+        let old_file = root.path().join("LMX_summary.1.0.yml");
+        let new_file = root.path().join("LMX_summary.2.0.yml");
+        std::fs::write(&old_file, "mock content")?;
+        std::fs::write(&new_file, "mock content")?;
+        std::fs::File::options()
+            .write(true)
+            .open(&old_file)?
+            .set_modified(parse_timestamp("2020-06-30 12:00").unwrap())?;
+
+        let args = CliArgs {
+            newer_than: Some(parse_timestamp("2024-01-01").unwrap()),
+            ..Default::default()
+        };
+        let root_str = root.path().to_str().unwrap().to_string();
+        let files = find_lmx_summary_files(&vec![root_str], &args)?.files;
+        assert_eq!(files, vec![new_file.to_str().unwrap()]);
+
+        // The filter also applies to files given directly
+        let files =
+            find_lmx_summary_files(&vec![old_file.to_str().unwrap().to_string()], &args)?.files;
+        assert!(files.is_empty());
+        Ok(())
+    }
+}
@@ -14,8 +14,12 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::globbing::find_lmx_type_files;
+    use crate::cmdline::CliArgs;
+    use crate::globbing::{find_lmx_summary_files, find_lmx_type_files};
+    use crate::jobdata::read_lmx_summary;
     use anyhow::Result;
+    use std::io::Write;
+    use std::path::Path;
 
     #[test]
     fn test_invalid_file_name() -> Result<()> {
@@ -155,4 +159,112 @@ mod tests {
 
         Ok(())
     }
+
+    /// Copies an LMX file into `target_dir`, compressed according to `suffix`.
+    fn copy_compressed(source: &Path, target_dir: &Path, suffix: &str) -> Result<()> {
+        let file_name = source.file_name().unwrap().to_str().unwrap();
+        let target = std::fs::File::create(target_dir.join(format!("{}{}", file_name, suffix)))?;
+        let content = std::fs::read(source)?;
+        match suffix {
+            ".gz" => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(target, flate2::Compression::fast());
+                encoder.write_all(&content)?;
+                encoder.finish()?;
+            }
+            ".zst" => zstd::stream::copy_encode(content.as_slice(), target, 0)?,
+            _ => std::fs::write(target_dir.join(file_name), content)?,
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_compressed_type_files() -> Result<()> {
+        // Mix plain, gzip and zstd compressed files in one run directory
+        let temp_dir = tempfile::tempdir()?;
+        let source_dir = Path::new("tests/data/NAMD/run_0002");
+        let suffixes = ["", ".gz", ".zst"];
+        let mut sources: Vec<_> = std::fs::read_dir(source_dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<_>>()?;
+        sources.sort();
+        for (index, source) in sources.iter().enumerate() {
+            let file_name = source.file_name().unwrap().to_str().unwrap();
+            if file_name.starts_with("LMX_summary") {
+                copy_compressed(source, temp_dir.path(), ".gz")?;
+            } else if file_name.starts_with("LMX_") {
+                copy_compressed(source, temp_dir.path(), suffixes[index % 3])?;
+            }
+        }
+
+        // The compressed summary file is found and paired by process id
+        let files = find_lmx_summary_files(
+            &vec![temp_dir.path().to_str().unwrap().to_string()],
+            &CliArgs::default(),
+        )?
+        .files;
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("LMX_summary.223561.0.yml.gz"));
+        for type_str in ["MPI", "itimer"] {
+            let expected = find_lmx_type_files(
+                "tests/data/NAMD/run_0002/LMX_summary.223561.0.yml",
+                type_str,
+            )?;
+            let found = find_lmx_type_files(&files[0], type_str)?;
+            assert_eq!(found.len(), expected.len());
+        }
+
+        // Compressed files are decompressed while reading
+        assert_eq!(
+            read_lmx_summary(&files[0])?,
+            read_lmx_summary("tests/data/NAMD/run_0002/LMX_summary.223561.0.yml")?
+        );
+        for found in find_lmx_type_files(&files[0], "MPI")? {
+            let file_name = Path::new(&found).file_name().unwrap().to_str().unwrap();
+            let plain_name = file_name.trim_end_matches(".gz").trim_end_matches(".zst");
+            assert_eq!(
+                read_lmx_summary(&found)?,
+                read_lmx_summary(source_dir.join(plain_name).to_str().unwrap())?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_plain_and_compressed_copy_side_by_side() -> Result<()> {
+        // An interrupted compression leaves the plain file next to the compressed one
+        let temp_dir = tempfile::tempdir()?;
+        let source_dir = Path::new("tests/data/NAMD/run_0002");
+        let summary = source_dir.join("LMX_summary.223561.0.yml");
+        copy_compressed(&summary, temp_dir.path(), "")?;
+        let expected = find_lmx_type_files(summary.to_str().unwrap(), "MPI")?;
+        assert!(!expected.is_empty());
+        for profile in &expected {
+            copy_compressed(Path::new(profile), temp_dir.path(), "")?;
+            copy_compressed(Path::new(profile), temp_dir.path(), ".gz")?;
+        }
+
+        let file_name = temp_dir.path().join("LMX_summary.223561.0.yml");
+        let found = find_lmx_type_files(file_name.to_str().unwrap(), "MPI")?;
+        assert_eq!(found.len(), expected.len());
+        assert!(found.iter().all(|f| f.ends_with(".yml")), "{:?}", found);
+
+        // Without the plain file, two compressed copies are ambiguous
+        for profile in &found {
+            std::fs::remove_file(profile)?;
+            copy_compressed(
+                &source_dir.join(Path::new(profile).file_name().unwrap()),
+                temp_dir.path(),
+                ".zst",
+            )?;
+        }
+        let result = find_lmx_type_files(file_name.to_str().unwrap(), "MPI");
+        let error = result.unwrap_err().to_string();
+        assert!(
+            error.contains("exists in several compressed copies"),
+            "{}",
+            error
+        );
+        Ok(())
+    }
 }
@@ -12,18 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cmdline::CliArgs;
-use anyhow::Result;
+use crate::archive::display_name;
+use crate::cmdline::{CliArgs, DuplicatePolicy};
+use crate::jobdata::statement::Statement;
+use crate::jobdata::table_runs::foreign_keys::{self, RunsForeignKeys};
+use crate::jobdata::transaction::TransactionGroup;
+use crate::sqltypes::{unfilled_columns, SqlTypeHashMap};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
 use sqlx::MySql;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs::OpenOptions;
-use std::io::Write;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 pub type LmxSummary = HashMap<String, HashMap<String, serde_yaml::Value>>;
 
 pub(crate) mod base_data;
 pub(crate) mod checktypes;
 pub(crate) mod create_sql;
+pub(crate) mod sql_output;
+pub(crate) mod statement;
 pub(crate) mod table_environ;
 pub(crate) mod table_iprof;
 pub(crate) mod table_mmm;
@@ -37,6 +47,85 @@ pub(crate) mod test_import;
 pub(crate) mod test_job_failures;
 #[cfg(test)]
 pub(crate) mod test_sql_file;
+pub(crate) mod transaction;
+
+/// Outcome of successfully processing a single LMX summary file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportStatus {
+    /// The queries for the run were executed or written to the SQL file
+    Imported,
+    /// The run already exists in the database and was skipped
+    Skipped,
+}
+
+/// Tables with row counts in the `ImportOutcome`
+pub const IMPORT_TABLES: [&str; 8] = [
+    "runs",
+    "tasks",
+    "mmm",
+    "environ",
+    "settings",
+    "mpi",
+    "mpi_details",
+    "iprof",
+];
+
+/// Result of successfully processing a single LMX summary file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportOutcome {
+    pub status: ImportStatus,
+    /// Run id assigned by the database, only known for direct imports
+    pub rid: Option<i64>,
+    /// Number of inserted rows per table
+    pub row_counts: BTreeMap<String, usize>,
+    /// Project data of the run as read from its project file
+    pub foreign_keys: Option<RunsForeignKeys>,
+    /// User id from the environ section, used if no person is given
+    pub user: Option<String>,
+    /// Problems which did not stop the import, e.g. missing toolchain data
+    pub warnings: Vec<String>,
+}
+
+impl ImportOutcome {
+    /// Creates the outcome for a skipped run, with no rows inserted
+    pub fn skipped() -> Self {
+        ImportOutcome {
+            status: ImportStatus::Skipped,
+            rid: None,
+            row_counts: BTreeMap::new(),
+            foreign_keys: None,
+            user: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Final status of a processed LMX summary file, e.g. in the state file of the
+/// watch mode. It is serialized in lowercase, e.g. `"imported"`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    Imported,
+    Skipped,
+    Failed,
+}
+
+impl RunStatus {
+    /// Returns the status of a file processed with the given result
+    pub fn of(result: &Result<ImportOutcome>) -> Self {
+        match result {
+            Ok(outcome) => match outcome.status {
+                ImportStatus::Imported => RunStatus::Imported,
+                ImportStatus::Skipped => RunStatus::Skipped,
+            },
+            Err(_) => RunStatus::Failed,
+        }
+    }
+}
+
+/// Serializes the output of concurrent workers, so that the blocks
+/// of queries belonging to different runs never interleave.
+static SQL_OUTPUT_LOCK: Mutex<()> = Mutex::new(());
 
 /// Processes a single LMX summary file by collecting SQL queries and executing them against a database.
 ///
@@ -48,22 +137,27 @@ pub(crate) mod test_sql_file;
 ///
 /// * `file_name` - Path to the LMX summary file to process
 /// * `pool` - Optional MySQL connection pool for database operations. If `None`, queries are written to a file
+/// * `group` - Optional transaction shared with other runs, see `TransactionGroup`
 /// * `sqltypes` - HashMap containing the database schema mapping for generating SQL queries
 /// * `args` - Command line arguments controlling processing behavior including verbosity, dry-run mode, and transaction settings
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on success, or a `sqlx::Error` if database operations fail
+/// Returns the `ImportOutcome` on success, or a `sqlx::Error` if database operations fail
 ///
 /// # Behavior
 ///
 /// - Reads and parses the LMX summary file as YAML
+/// - Reads the project file of the run
+/// - With a database connection and `args.on_duplicate` set to `skip` or `error`,
+///   checks for an existing run before generating any SQL
 /// - Generates SQL queries based on file content and sqltypes schema mappings
 /// - Adds a comment marker identifying the source file being processed
+/// - Collects the inserted rows per table and the warnings into the `ImportOutcome`
 /// - Delegates query execution to `process_sql_queries()` which handles:
 ///   - Database execution (if pool is provided)
 ///   - File output (if pool is None)
-///   - Transaction management based on `args.transaction_per_job`
+///   - Transaction management based on `args.tx_mode`
 ///
 /// # Panics
 ///
@@ -71,20 +165,64 @@ pub(crate) mod test_sql_file;
 pub async fn process_lmx_file(
     file_name: &str,
     pool: &Option<sqlx::Pool<MySql>>,
-    sqltypes: &HashMap<String, HashMap<String, String>>,
+    group: Option<&TransactionGroup>,
+    sqltypes: &SqlTypeHashMap,
     args: &CliArgs,
-) -> Result<()> {
-    // Collect the SQL queries into a Vec<String> and process them later.
-    let mut query_list: Vec<String> = Vec::new();
+) -> Result<ImportOutcome> {
+    // Collect the SQL statements into a Vec<Statement> and process them later.
+    let mut query_list: Vec<Statement> = Vec::new();
     // Adding a comment line as a marker for the file being processed
-    query_list.push(format!("-- Queries for file {};", file_name));
+    query_list.push(format!("-- Queries for file {};", display_name(file_name)).into());
 
     // Read the LMX summary file into a hashmap
     let lmx_summary = read_lmx_summary(file_name)?;
 
+    // Find a project file and read its RunsForeignKeys
+    let run_foreign_keys = foreign_keys::read_project_file(file_name, args)?;
+    let user = foreign_keys::extract_user_id(&lmx_summary);
+
+    // Look for an existing run before generating any SQL
+    if let Some(db_pool) = pool.as_ref() {
+        if matches!(
+            args.on_duplicate,
+            DuplicatePolicy::Skip | DuplicatePolicy::Error
+        ) && foreign_keys::run_exists(&run_foreign_keys, db_pool, &lmx_summary, args).await?
+        {
+            if args.on_duplicate == DuplicatePolicy::Error {
+                bail!("Run already exists in the database");
+            }
+            return Ok(ImportOutcome {
+                foreign_keys: Some(run_foreign_keys),
+                user: Some(user),
+                ..ImportOutcome::skipped()
+            });
+        }
+    }
+    let mut warnings: Vec<String> = Vec::new();
+
+    // Determine the SQL file of the run, if each run is written to its own file
+    let run_file: Option<PathBuf> = match (pool, &args.sql_file_template) {
+        (None, Some(template)) => Some(sql_output::run_sql_file(
+            template,
+            file_name,
+            &lmx_summary,
+            args,
+        )?),
+        _ => None,
+    };
+
     // Generate SQL queries for the 'runs' table
     query_list.extend(
-        table_runs::import_into_runs_table(file_name, pool, &lmx_summary, sqltypes, args).await?,
+        table_runs::import_into_runs_table(
+            file_name,
+            pool,
+            &lmx_summary,
+            &run_foreign_keys,
+            sqltypes,
+            &mut warnings,
+            args,
+        )
+        .await?,
     );
 
     // Generate SQL queries for the 'settings' table
@@ -121,10 +259,49 @@ pub async fn process_lmx_file(
         file_name, sqltypes, args,
     )?);
 
+    // Count the rows per table before the queries are consumed
+    let mut row_counts: BTreeMap<String, usize> = IMPORT_TABLES
+        .iter()
+        .map(|table| (table.to_string(), 0))
+        .collect();
+    // Columns the database requires, but no INSERT of the run fills
+    let mut unfilled: BTreeSet<(String, String)> = BTreeSet::new();
+    for inserted in query_list.iter().filter_map(|q| q.inserted.as_ref()) {
+        *row_counts.entry(inserted.table.clone()).or_default() += inserted.rows;
+        for column in unfilled_columns(&inserted.table, &inserted.columns, sqltypes) {
+            unfilled.insert((inserted.table.clone(), column));
+        }
+    }
+    for (table, column) in unfilled {
+        let warning = format!(
+            "Column {} of table {} is NOT NULL and has no default, but lmx2db does not fill it",
+            column, table
+        );
+        if args.verbose || args.dry_run {
+            println!("Warning: {}", warning);
+        }
+        warnings.push(warning);
+    }
+
     // Process the collected SQL queries
-    process_sql_queries(query_list, pool, args).await?;
+    let rid = process_sql_queries(
+        file_name,
+        query_list,
+        pool,
+        group,
+        run_file.as_deref(),
+        args,
+    )
+    .await?;
 
-    Ok(())
+    Ok(ImportOutcome {
+        status: ImportStatus::Imported,
+        rid,
+        row_counts,
+        foreign_keys: Some(run_foreign_keys),
+        user: Some(user),
+        warnings,
+    })
 }
 
 /// Processes a collection of SQL queries by either executing them against a database or writing to a file.
@@ -134,38 +311,52 @@ pub async fn process_lmx_file(
 ///
 /// # Arguments
 ///
-/// * `query_list` - Vector of SQL query strings to process
+/// * `file_name` - The LMX_summary file the queries were generated for
+/// * `query_list` - Vector of SQL statements to process
 /// * `pool` - Optional MySQL connection pool for creating new transactions
-/// * `transaction` - Optional ongoing transaction to use for query execution
+/// * `group` - Optional transaction shared with other runs, used instead of a new transaction
+/// * `run_file` - Optional SQL file of its own for this run, used instead of `args.sql_file`
 /// * `args` - Command line arguments controlling transaction and output behavior
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on success, or a `sqlx::Error` if database operations fail
+/// Returns the value of `@rid` if the queries set it and were executed against the
+/// database, `None` otherwise, or a `sqlx::Error` if database operations fail
 ///
 /// # Behavior
 ///
 /// ## Database Mode (when pool is Some)
-/// - If `args.transaction_per_job` is true, creates a new transaction for this batch
-/// - Otherwise uses the provided transaction
-/// - Concatenates and re-splits queries on semicolons to handle fragmented queries
-/// - Executes each query and commits the transaction if using per-job mode
-/// - **Note:** Each query is expected to end with a semicolon. The function splits on ';'
-///   and adds it back, ensuring proper SQL statement termination for sqlx execution.
+/// - Without a transaction group, creates a new transaction for this run
+/// - Otherwise executes the queries inside a savepoint of the group's transaction
+/// - Executes each query with its bound values and commits the transaction of the run
 ///
 /// ## File Mode (when pool is None)
-/// - Appends all queries to the file specified in `args.sql_file`
+/// - Appends all queries to the file specified in `args.sql_file`, with bound values
+///   rendered as escaped literals
+/// - Writes of concurrent workers are serialized, so blocks of different runs never interleave
 /// - Creates the file if it doesn't exist
 /// - Ensures output ends with a newline
+/// - With `run_file`, writes the queries to that file instead and appends a `SOURCE`
+///   line for it to the index file `args.sql_file`
 /// - In dry-run mode, prints queries to stdout instead of writing to file
 ///
 /// # Panics
 ///
 pub async fn process_sql_queries(
-    query_list: Vec<String>,
+    file_name: &str,
+    query_list: Vec<Statement>,
     pool: &Option<sqlx::Pool<MySql>>,
+    group: Option<&TransactionGroup>,
+    run_file: Option<&Path>,
     args: &CliArgs,
-) -> Result<()> {
+) -> Result<Option<i64>> {
+    // In the grouped transaction modes, the run joins the transaction of its group
+    if let (Some(group), Some(_), false) = (group, pool.as_ref(), args.dry_run) {
+        return group.execute_run(file_name, &query_list, args).await;
+    }
+    // Only a run's block of queries sets @rid
+    let sets_rid = query_list.iter().any(|q| q.sql.starts_with("SET @rid ="));
+    let mut rid = None;
     // Create a new transaction for this job only if we have a database connection
     // and we are not in dry-run mode.
     let mut tx_per_job = if let Some(p) = pool.as_ref() {
@@ -188,25 +379,36 @@ pub async fn process_sql_queries(
         // needing to be split further on semicolons.
         for query in query_list {
             if args.verbose || args.dry_run {
-                println!("Executing query: {}", query);
+                println!("Executing query: {}", query.render());
             }
             if !args.dry_run {
                 if let Some(tx) = tx_per_job.as_mut() {
-                    sqlx::query(&query).execute(&mut **tx).await?;
+                    query.to_query().execute(&mut **tx).await?;
                 } // else case should not happen as pool is Some
             }
         }
+        // Read back the run id assigned within this transaction
+        if let (true, Some(tx)) = (sets_rid, tx_per_job.as_mut()) {
+            rid = sqlx::query_scalar("SELECT CAST(@rid AS SIGNED);")
+                .fetch_one(&mut **tx)
+                .await?;
+        }
     } else {
         // No database connection available, write (append) them to a file.
         let mut query_list_with_transaction = vec!["START TRANSACTION;".to_string()];
-        query_list_with_transaction.extend(query_list);
+        query_list_with_transaction.extend(query_list.iter().map(Statement::render));
         query_list_with_transaction.push("COMMIT;".to_string());
         let extended_query_list = query_list_with_transaction;
+        // Hold the lock until the whole block has been written
+        let _guard = SQL_OUTPUT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let output_file = run_file.map_or(args.sql_file.clone(), |path| {
+            path.to_string_lossy().to_string()
+        });
         if args.verbose || args.dry_run {
             println!(
                 "No database connection available, writing {} lines with queries to file: {}",
                 extended_query_list.len(),
-                args.sql_file
+                output_file
             );
         }
         if args.dry_run {
@@ -214,6 +416,10 @@ pub async fn process_sql_queries(
             for query in &extended_query_list {
                 println!("Query to write: {}", query);
             }
+        } else if let Some(path) = run_file {
+            let mut concatenated = extended_query_list.join("\n");
+            concatenated.push('\n');
+            sql_output::write_run_sql_file(path, &concatenated, args)?;
         } else {
             // Open the file in append mode
             let mut file = OpenOptions::new()
@@ -236,11 +442,34 @@ pub async fn process_sql_queries(
     if let Some(tx) = tx_per_job {
         tx.commit().await?;
     }
-    Ok(())
+    Ok(rid)
 }
 
+/// Reads an LMX YAML file, i.e. an LMX_summary or LMX profile file.
+/// Files ending in ".gz" or ".zst" are decompressed while reading.
+///
+/// # Arguments
+/// * `file_name` - Path to the LMX file
+///
+/// # Returns
+/// Returns the parsed `LmxSummary`
+///
+/// # Errors
+/// Returns an error if the file cannot be read, decompressed or parsed
+///
 pub(crate) fn read_lmx_summary(file_name: &str) -> Result<LmxSummary> {
-    let file_content = std::fs::read_to_string(file_name)?;
+    let file = std::fs::File::open(file_name)?;
+    let mut reader: Box<dyn Read> = if file_name.ends_with(".gz") {
+        Box::new(flate2::read::GzDecoder::new(file))
+    } else if file_name.ends_with(".zst") {
+        Box::new(zstd::stream::read::Decoder::new(file)?)
+    } else {
+        Box::new(file)
+    };
+    let mut file_content = String::new();
+    reader
+        .read_to_string(&mut file_content)
+        .with_context(|| format!("Failed to read {}", file_name))?;
     let lmx_summary: LmxSummary = serde_yaml::from_str(&file_content)?;
     Ok(lmx_summary)
 }
@@ -12,54 +12,59 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::sqltypes::SqlTypeHashMap;
+use crate::datetime::DateTime;
+use crate::jobdata::create_sql::SqlValue;
+use crate::sqltypes::{SqlColumn, SqlTypeHashMap};
 use anyhow::{bail, Result};
+use once_cell::sync::Lazy;
 use regex::Regex;
 
 #[cfg(test)]
 pub(crate) mod elementary;
 #[cfg(test)]
+mod extended_types;
+#[cfg(test)]
 mod type_normalization_tests;
 #[cfg(test)]
 pub(crate) mod wrong_values;
 
-/// Retrieves the expected SQL types for the specified keys in a given table.
+/// Retrieves the column descriptions for the specified keys in a given table.
 ///
 /// # Arguments
 /// * `table_name` - The name of the table to look up
-/// * `keys` - A slice of column names for which to retrieve types
+/// * `keys` - A slice of column names for which to retrieve the descriptions
 /// * `sqltypes` - A reference to the SQL type mapping
 ///
-/// Returns a vector of expected SQL types corresponding to the provided keys.
+/// Returns a vector of column descriptions corresponding to the provided keys.
 ///
 /// # Errors
 /// Returns an error if the table or any of the keys are not found in the type mapping
 ///
-pub fn get_types(
+pub fn get_columns<'a>(
     table_name: &str,
     keys: &[String],
-    sqltypes: &SqlTypeHashMap,
-) -> Result<Vec<String>> {
+    sqltypes: &'a SqlTypeHashMap,
+) -> Result<Vec<&'a SqlColumn>> {
     let table_map = sqltypes.get(table_name);
     if table_map.is_none() {
         anyhow::bail!("Table {} not found in type check map", table_name);
     }
     let table_map = table_map.unwrap();
 
-    // Pre-compute expected types for all keys
-    let mut expected_types: Vec<String> = Vec::new();
+    // Pre-compute the column descriptions for all keys
+    let mut columns: Vec<&SqlColumn> = Vec::new();
     for key in keys.iter() {
-        let expected_type = table_map.get(key);
-        if expected_type.is_none() {
+        let column = table_map.get(key);
+        if column.is_none() {
             anyhow::bail!(
                 "Column {} not found in type check map for table {}",
                 key,
                 table_name
             );
         }
-        expected_types.push(expected_type.unwrap().clone());
+        columns.push(column.unwrap());
     }
-    Ok(expected_types)
+    Ok(columns)
 }
 
 /// Checks whether the provided values conform to the expected SQL types
@@ -68,45 +73,76 @@ pub fn get_types(
 /// # Arguments
 /// * `table_name` - The name of the table to check against
 /// * `keys` - A slice of column names corresponding to the values
-/// * `types` - A slice of expected SQL types for each provided value row
+/// * `columns` - The descriptions of the columns of each provided value row
 /// * `values` - A slice of value rows to validate
 ///
 /// `table_name` and `keys` are only needed for error reporting.
 /// They do not influence the type checking logic.
+/// Session variables and stored function calls are references resolved by
+/// the database and are not checked. NULL and null literals are accepted for
+/// nullable columns only.
+/// Enum values must be one of the members listed in the type, `date`,
+/// `datetime` and `timestamp` values are parsed and checked against the
+/// range of the type, and `float` and `double` values must be finite.
 ///
 /// # Errors
-/// Returns an error if any value does not conform to its expected SQL type
+/// Returns an error if any value does not conform to its expected SQL type,
+/// or if a column that does not accept NULL gets a null value
 ///
 pub fn check_types(
     table_name: &str,
     keys: &[String],
-    types: &[String],
-    values: &[Vec<serde_yaml::Value>],
+    columns: &[&SqlColumn],
+    values: &[Vec<SqlValue>],
 ) -> Result<()> {
-    // The following regexes will be used multiple times
-    let id_pattern = Regex::new(r"^@\w+id$|^[A-Za-z_]\w*_id\([^;]*\)$").unwrap();
-    let varbinary_pattern = Regex::new(r"varbinary\((\d+)\)").unwrap();
-    let varchar_pattern = Regex::new(r"varchar\((\d+)\)").unwrap();
-
     for value_row in values {
-        if value_row.len() != types.len() {
+        if value_row.len() != columns.len() {
             bail!(
                 "Row length mismatch in table {}: expected {} columns, got {}",
                 table_name,
-                types.len(),
+                columns.len(),
                 value_row.len()
             );
         }
-        for (i, expected_type) in types.iter().enumerate() {
-            let value = &value_row[i];
+        for (i, column) in columns.iter().enumerate() {
+            let expected_type = column.sql_type.as_str();
+            let value = match &value_row[i] {
+                SqlValue::Null | SqlValue::Literal(serde_yaml::Value::Null) if column.nullable => {
+                    continue
+                }
+                SqlValue::Null | SqlValue::Literal(serde_yaml::Value::Null) => bail!(
+                    "Column {} in table {} is NOT NULL, but the value is null",
+                    keys[i],
+                    table_name
+                ),
+                SqlValue::Literal(value) => value,
+                SqlValue::Variable(_) | SqlValue::Function(_, _) => continue,
+            };
 
             // Normalize type string to lowercase for case-insensitive matching
             let expected_type_lower = expected_type.to_lowercase();
             let is_unsigned = expected_type_lower.contains("unsigned");
 
+            // Enum members may contain any of the type names below, so check enums first
+            if let Some(members) = column.enum_members() {
+                let value_str = try_cast_into_string(value).unwrap_or_default();
+                // Enum values are compared case-insensitively, like with the
+                // default collation of MariaDB
+                if !members
+                    .iter()
+                    .any(|member| member.eq_ignore_ascii_case(&value_str))
+                {
+                    bail!(
+                        "Column {} in table {} expects {}, but value '{}' is not one of the enum members",
+                        keys[i],
+                        table_name,
+                        expected_type,
+                        value_str
+                    );
+                }
             // Check for integer types: bigint, tinyint, smallint, int (order matters!)
             // Check most specific types first (tinyint, smallint) before generic int
-            if expected_type_lower.contains("bigint") {
+            } else if expected_type_lower.contains("bigint") {
                 // BIGINT types: bigint(20) or bigint(20) unsigned
                 if is_unsigned {
                     // unsigned bigint: 0 to u64::MAX
@@ -121,14 +157,13 @@ pub fn check_types(
                     // No range check needed - u64 is the max range
                 } else {
                     // signed bigint: i64::MIN to i64::MAX
-                    let value_str = try_cast_into_string(value).unwrap_or_default();
-                    if !id_pattern.is_match(&value_str) && value.as_i64().is_none() {
+                    if value.as_i64().is_none() {
                         bail!(
-                                "Column {} in table {} expects bigint, but value '{}' is neither a reference (@\\w+id) nor a valid integer",
-                                keys[i],
-                                table_name,
-                                value_str
-                            );
+                            "Column {} in table {} expects bigint, but value '{}' is not a valid integer",
+                            keys[i],
+                            table_name,
+                            try_cast_into_string(value).unwrap_or_default()
+                        );
                     }
                     // No range check needed - i64 is the max range for signed bigint
                 }
@@ -155,25 +190,22 @@ pub fn check_types(
                     }
                 } else {
                     // signed tinyint: i8::MIN to i8::MAX
-                    let value_str = try_cast_into_string(value).unwrap_or_default();
-                    if !id_pattern.is_match(&value_str) {
-                        if value.as_i64().is_none() {
-                            bail!(
-                                "Column {} in table {} expects tinyint, but value '{}' is neither a reference (@\\w+id) nor a valid integer",
-                                keys[i],
-                                table_name,
-                                value_str
-                            );
-                        }
-                        let intval = value.as_i64().unwrap();
-                        if intval < i8::MIN as i64 || intval > i8::MAX as i64 {
-                            bail!(
-                                "Value {} is out of signed tinyint range ({}..={})",
-                                intval,
-                                i8::MIN,
-                                i8::MAX
-                            );
-                        }
+                    if value.as_i64().is_none() {
+                        bail!(
+                            "Column {} in table {} expects tinyint, but value '{}' is not a valid integer",
+                            keys[i],
+                            table_name,
+                            try_cast_into_string(value).unwrap_or_default()
+                        );
+                    }
+                    let intval = value.as_i64().unwrap();
+                    if intval < i8::MIN as i64 || intval > i8::MAX as i64 {
+                        bail!(
+                            "Value {} is out of signed tinyint range ({}..={})",
+                            intval,
+                            i8::MIN,
+                            i8::MAX
+                        );
                     }
                 }
             } else if expected_type_lower.contains("smallint") {
@@ -199,25 +231,22 @@ pub fn check_types(
                     }
                 } else {
                     // signed smallint: i16::MIN to i16::MAX
-                    let value_str = try_cast_into_string(value).unwrap_or_default();
-                    if !id_pattern.is_match(&value_str) {
-                        if value.as_i64().is_none() {
-                            bail!(
-                                "Column {} in table {} expects smallint, but value '{}' is neither a reference (@\\w+id) nor a valid integer",
-                                keys[i],
-                                table_name,
-                                value_str
-                            );
-                        }
-                        let intval = value.as_i64().unwrap();
-                        if intval < i16::MIN as i64 || intval > i16::MAX as i64 {
-                            bail!(
-                                "Value {} is out of signed smallint range ({}..={})",
-                                intval,
-                                i16::MIN,
-                                i16::MAX
-                            );
-                        }
+                    if value.as_i64().is_none() {
+                        bail!(
+                            "Column {} in table {} expects smallint, but value '{}' is not a valid integer",
+                            keys[i],
+                            table_name,
+                            try_cast_into_string(value).unwrap_or_default()
+                        );
+                    }
+                    let intval = value.as_i64().unwrap();
+                    if intval < i16::MIN as i64 || intval > i16::MAX as i64 {
+                        bail!(
+                            "Value {} is out of signed smallint range ({}..={})",
+                            intval,
+                            i16::MIN,
+                            i16::MAX
+                        );
                     }
                 }
             } else if expected_type_lower.contains("int(") {
@@ -246,44 +275,144 @@ pub fn check_types(
                     }
                 } else {
                     // signed int: i32::MIN to i32::MAX
-                    let value_str = try_cast_into_string(value).unwrap_or_default();
-                    if !id_pattern.is_match(&value_str) {
-                        if value.as_i64().is_none() {
-                            bail!(
-                                "Column {} in table {} expects int(11), but value '{}' is neither a reference (@\\w+id) nor a valid integer",
-                                keys[i],
-                                table_name,
-                                value_str
-                            );
-                        }
-                        let intval = value.as_i64().unwrap();
-                        if intval < i32::MIN as i64 || intval > i32::MAX as i64 {
-                            bail!(
-                                "Column {} in table {} expects {}, but value {} is out of i32 range ({}..={})",
-                                keys[i],
-                                table_name,
-                                expected_type,
-                                intval,
-                                i32::MIN,
-                                i32::MAX
-                            );
-                        }
+                    if value.as_i64().is_none() {
+                        bail!(
+                            "Column {} in table {} expects int(11), but value '{}' is not a valid integer",
+                            keys[i],
+                            table_name,
+                            try_cast_into_string(value).unwrap_or_default()
+                        );
+                    }
+                    let intval = value.as_i64().unwrap();
+                    if intval < i32::MIN as i64 || intval > i32::MAX as i64 {
+                        bail!(
+                            "Column {} in table {} expects {}, but value {} is out of i32 range ({}..={})",
+                            keys[i],
+                            table_name,
+                            expected_type,
+                            intval,
+                            i32::MIN,
+                            i32::MAX
+                        );
                     }
                 }
-            } else if expected_type.contains("float") {
-                if value.as_f64().is_none() {
+            } else if expected_type_lower.starts_with("float")
+                || expected_type_lower.starts_with("double")
+            {
+                let single = expected_type_lower.starts_with("float");
+                let Some(floatval) = value.as_f64() else {
                     bail!(
                         "Column {} in table {} expects {}, but value cannot be cast to float",
                         keys[i],
                         table_name,
                         expected_type
                     );
+                };
+                if !floatval.is_finite() {
+                    bail!(
+                        "Column {} in table {} expects {}, but value {} is not a finite number",
+                        keys[i],
+                        table_name,
+                        expected_type,
+                        floatval
+                    );
                 }
-            } else if let Some(caps) = varbinary_pattern.captures(expected_type) {
-                let max_length: usize = caps.get(1).unwrap().as_str().parse().unwrap();
+                if single && floatval.abs() > f32::MAX as f64 {
+                    bail!(
+                        "Column {} in table {} expects {}, but value {} is out of single precision range ({}..={})",
+                        keys[i],
+                        table_name,
+                        expected_type,
+                        floatval,
+                        f32::MIN,
+                        f32::MAX
+                    );
+                }
+                if is_unsigned && floatval < 0.0 {
+                    bail!(
+                        "Column {} in table {} expects unsigned type {}, but value {} is negative",
+                        keys[i],
+                        table_name,
+                        expected_type,
+                        floatval
+                    );
+                }
+            } else if let Some(caps) = DECIMAL_PATTERN.captures(&expected_type_lower) {
+                let precision: usize = caps.get(1).unwrap().as_str().parse().unwrap();
+                let scale: usize = caps
+                    .get(2)
+                    .map_or(0, |scale| scale.as_str().parse().unwrap());
+                let value_str = try_cast_into_string(value).unwrap_or_default();
+                let Some((negative, integer_digits)) = decimal_integer_digits(&value_str, scale)
+                else {
+                    bail!(
+                        "Column {} in table {} expects {}, but value '{}' is not a valid decimal number",
+                        keys[i],
+                        table_name,
+                        expected_type,
+                        value_str
+                    );
+                };
+                if integer_digits > precision.saturating_sub(scale) {
+                    bail!(
+                        "Column {} in table {} expects {}, but value '{}' has {} integer digits > {}",
+                        keys[i],
+                        table_name,
+                        expected_type,
+                        value_str,
+                        integer_digits,
+                        precision.saturating_sub(scale)
+                    );
+                }
+                if is_unsigned && negative {
+                    bail!(
+                        "Column {} in table {} expects unsigned type {}, but value '{}' is negative",
+                        keys[i],
+                        table_name,
+                        expected_type,
+                        value_str
+                    );
+                }
+            } else if let Some(caps) = TEMPORAL_PATTERN.captures(&expected_type_lower) {
+                let temporal_type = caps.get(1).unwrap().as_str();
+                let value_str = try_cast_into_string(value).unwrap_or_default();
+                let Some(date_time) = DateTime::parse(&value_str, temporal_type != "date") else {
+                    bail!(
+                        "Column {} in table {} expects {}, but value '{}' is not a valid {}",
+                        keys[i],
+                        table_name,
+                        expected_type,
+                        value_str,
+                        if temporal_type == "date" {
+                            "date (YYYY-MM-DD)"
+                        } else {
+                            "date and time (YYYY-MM-DD hh:mm:ss)"
+                        }
+                    );
+                };
+                let (min, max) = if temporal_type == "timestamp" {
+                    (TIMESTAMP_MIN, TIMESTAMP_MAX)
+                } else {
+                    (DATETIME_MIN, DATETIME_MAX)
+                };
+                if date_time < min || date_time > max {
+                    bail!(
+                        "Column {} in table {} expects {}, but value '{}' is out of range ({}..={})",
+                        keys[i],
+                        table_name,
+                        expected_type,
+                        value_str,
+                        min,
+                        max
+                    );
+                }
+            } else if let Some(caps) = BINARY_PATTERN.captures(expected_type) {
+                // Binary values are given as hex digits, two per byte
+                let variable = caps.get(1).is_some();
+                let byte_length: usize = caps.get(2).unwrap().as_str().parse().unwrap();
 
                 if let Ok(value_str) = try_cast_into_string(value) {
-                    if !value_str.chars().all(|c| "0123456789abcdef".contains(c)) {
+                    if !value_str.chars().all(|c| c.is_ascii_hexdigit()) {
                         bail!(
                             "Column {} in table {} expects {}, but string value '{}' contains invalid hex characters",
                             keys[i],
@@ -292,16 +421,27 @@ pub fn check_types(
                             value_str
                         );
                     }
-                    if value_str.len() * 4 >= max_length {
+                    let bytes = value_str.len().div_ceil(2);
+                    if variable && bytes > byte_length {
                         bail!(
-                            "Column {} in table {} expects {}, but string value '{}' has length {} * 4 = {} >= {}",
+                            "Column {} in table {} expects {}, but string value '{}' has {} bytes > {}",
                             keys[i],
                             table_name,
                             expected_type,
                             value_str,
-                            value_str.len(),
-                            value_str.len() * 4,
-                            max_length
+                            bytes,
+                            byte_length
+                        );
+                    }
+                    if !variable && bytes != byte_length {
+                        bail!(
+                            "Column {} in table {} expects {}, but string value '{}' has {} bytes instead of {}",
+                            keys[i],
+                            table_name,
+                            expected_type,
+                            value_str,
+                            bytes,
+                            byte_length
                         );
                     }
                 } else {
@@ -312,7 +452,7 @@ pub fn check_types(
                         expected_type
                     );
                 }
-            } else if let Some(caps) = varchar_pattern.captures(expected_type) {
+            } else if let Some(caps) = VARCHAR_PATTERN.captures(expected_type) {
                 let max_length: usize = caps.get(1).unwrap().as_str().parse().unwrap();
 
                 if let Ok(value_str) = try_cast_into_string(value) {
@@ -341,6 +481,56 @@ pub fn check_types(
     Ok(())
 }
 
+/// Range of `date` and `datetime` columns
+const DATETIME_MIN: DateTime = DateTime::new(1000, 1, 1, 0, 0, 0);
+const DATETIME_MAX: DateTime = DateTime::new(9999, 12, 31, 23, 59, 59);
+
+/// Range of `timestamp` columns (UTC)
+const TIMESTAMP_MIN: DateTime = DateTime::new(1970, 1, 1, 0, 0, 1);
+const TIMESTAMP_MAX: DateTime = DateTime::new(2038, 1, 19, 3, 14, 7);
+
+/// Types `binary(n)` and `varbinary(n)`
+static BINARY_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(var)?binary\((\d+)\)").unwrap());
+
+/// Type `varchar(n)`
+static VARCHAR_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"varchar\((\d+)\)").unwrap());
+
+/// Types `decimal(p)` and `decimal(p,s)`
+static DECIMAL_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^decimal\((\d+)(?:,\s*(\d+))?\)").unwrap());
+
+/// Types `date`, `datetime` and `timestamp`
+static TEMPORAL_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(datetime|timestamp|date)\b").unwrap());
+
+/// A decimal number without exponent
+static PLAIN_DECIMAL_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[+-]?(\d+(\.\d*)?|\.\d+)$").unwrap());
+
+/// Parses a decimal number and returns whether it is negative and the
+/// number of its integer digits. Numbers in exponential notation are
+/// expanded to `scale` fractional digits first.
+/// Returns `None` if the value is not a finite decimal number.
+fn decimal_integer_digits(value: &str, scale: usize) -> Option<(bool, usize)> {
+    let value = value.trim();
+    let number: f64 = value.parse().ok()?;
+    if !number.is_finite() {
+        return None;
+    }
+    // Plain numbers are counted from the text, f64 would lose digits
+    let digits = if PLAIN_DECIMAL_PATTERN.is_match(value) {
+        value.to_string()
+    } else {
+        format!("{:.*}", scale, number)
+    };
+    let integer_part = digits
+        .trim_start_matches(['+', '-'])
+        .split('.')
+        .next()
+        .unwrap_or_default();
+    Some((number < 0.0, integer_part.trim_start_matches('0').len()))
+}
+
 /// Attempts to convert a YAML value into a string representation.
 ///
 /// # Arguments
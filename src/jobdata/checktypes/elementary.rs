@@ -15,7 +15,8 @@
 #[cfg(test)]
 mod tests {
     use crate::cmdline::CliArgs;
-    use crate::jobdata::checktypes::{check_types, get_types};
+    use crate::jobdata::checktypes::{check_types, get_columns};
+    use crate::jobdata::create_sql::SqlValue;
     use crate::sqltypes::{read_sqltypes, SqlTypeHashMap};
     use anyhow::Result;
     use sqlx::{MySql, Pool};
@@ -37,7 +38,7 @@ mod tests {
         let keys = vec!["non_existing_key".to_string()];
 
         // Test for non-existing table
-        let result = get_types("non_existing_table", &keys, &sqltypes);
+        let result = get_columns("non_existing_table", &keys, &sqltypes);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
@@ -45,7 +46,7 @@ mod tests {
         );
 
         // Test for non-existing key in existing table
-        let result = get_types("runs", &keys, &sqltypes);
+        let result = get_columns("runs", &keys, &sqltypes);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
@@ -69,15 +70,17 @@ mod tests {
         let sqltypes: SqlTypeHashMap = read_sqltypes(Some(pool), &args).await?;
 
         let keys = vec!["clid".to_string()];
-        let values = vec![vec![serde_yaml::Value::String("not_an_id".to_string())]];
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::String(
+            "not_an_id".to_string(),
+        ))]];
 
-        // Test for foreign key that is not an @\w+id reference and not an integer
-        let types = get_types("runs", &keys, &sqltypes)?;
-        let result = check_types("runs", &keys, &types, &values);
+        // Test for foreign key given as a string instead of a session variable or an integer
+        let columns = get_columns("runs", &keys, &sqltypes)?;
+        let result = check_types("runs", &keys, &columns, &values);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
-            "Column clid in table runs expects int(11), but value 'not_an_id' is neither a reference (@\\w+id) nor a valid integer"
+            "Column clid in table runs expects int(11), but value 'not_an_id' is not a valid integer"
         );
         Ok(())
     }
@@ -96,20 +99,20 @@ mod tests {
         };
         let sqltypes: SqlTypeHashMap = read_sqltypes(Some(pool), &args).await?;
 
-        // Test for foreign key that is a valid @\w+id reference
+        // Test for foreign key that is a session variable
         let keys_ref = vec!["clid".to_string()];
-        let values_ref = vec![vec![serde_yaml::Value::String("@clid".to_string())]];
-        let types_ref = get_types("runs", &keys_ref, &sqltypes)?;
-        let result_ref = check_types("runs", &keys_ref, &types_ref, &values_ref);
+        let values_ref = vec![vec![SqlValue::variable("@clid")]];
+        let columns_ref = get_columns("runs", &keys_ref, &sqltypes)?;
+        let result_ref = check_types("runs", &keys_ref, &columns_ref, &values_ref);
         assert!(result_ref.is_ok());
 
         // Test for foreign key that is a valid integer
         let keys_int = vec!["clid".to_string()];
-        let values_int = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            12345,
+        let values_int = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(12345),
         ))]];
-        let types_int = get_types("runs", &keys_int, &sqltypes)?;
-        let result_int = check_types("runs", &keys_int, &types_int, &values_int);
+        let columns_int = get_columns("runs", &keys_int, &sqltypes)?;
+        let result_int = check_types("runs", &keys_int, &columns_int, &values_int);
         assert!(result_int.is_ok());
         Ok(())
     }
@@ -132,20 +135,20 @@ mod tests {
         let keys = vec!["clid".to_string(), "compiler".to_string()];
         let values = vec![
             vec![
-                serde_yaml::Value::String("@clid".to_string()),
-                serde_yaml::Value::String("gcc".to_string()),
+                SqlValue::variable("@clid"),
+                SqlValue::from(serde_yaml::Value::String("gcc".to_string())),
             ],
             vec![
-                serde_yaml::Value::Number(serde_yaml::Number::from(12345)),
-                serde_yaml::Value::String("icc".to_string()),
+                SqlValue::from(serde_yaml::Value::Number(serde_yaml::Number::from(12345))),
+                SqlValue::from(serde_yaml::Value::String("icc".to_string())),
             ],
             vec![
-                serde_yaml::Value::String("@cl2id".to_string()),
-                serde_yaml::Value::String("clang".to_string()),
+                SqlValue::variable("@cl2id"),
+                SqlValue::from(serde_yaml::Value::String("clang".to_string())),
             ],
         ];
-        let types = get_types("runs", &keys, &sqltypes)?;
-        let result = check_types("runs", &keys, &types, &values);
+        let columns = get_columns("runs", &keys, &sqltypes)?;
+        let result = check_types("runs", &keys, &columns, &values);
         assert!(result.is_ok());
         Ok(())
     }
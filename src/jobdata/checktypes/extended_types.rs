@@ -0,0 +1,111 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::jobdata::checktypes::check_types;
+    use crate::jobdata::create_sql::SqlValue;
+    use crate::sqltypes::SqlColumn;
+    use anyhow::Result;
+
+    /// Checks a single value against `column_type`
+    fn check(column_type: &str, value: serde_yaml::Value) -> Result<()> {
+        let keys = vec!["col".to_string()];
+        let column = SqlColumn::from_column_type(column_type);
+        let values = vec![vec![SqlValue::from(value)]];
+        check_types("test_table", &keys, &[&column], &values)
+    }
+
+    fn string(value: &str) -> serde_yaml::Value {
+        serde_yaml::Value::String(value.to_string())
+    }
+
+    fn float(value: f64) -> serde_yaml::Value {
+        serde_yaml::Value::Number(value.into())
+    }
+
+    #[test]
+    fn test_enum_members() -> Result<()> {
+        let enum_type = "enum('p2p','collective','MPI-IO','it''s')";
+        check(enum_type, string("collective"))?;
+        check(enum_type, string("mpi-io"))?;
+        check(enum_type, string("it's"))?;
+        let err = check(enum_type, string("int(11)")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Column col in table test_table expects enum('p2p','collective','MPI-IO','it''s'), but value 'int(11)' is not one of the enum members"
+        );
+        assert!(check("enum('AC','DC') null", serde_yaml::Value::Null).is_ok());
+        assert!(check("enum('AC','DC')", string("")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_float_and_double() -> Result<()> {
+        check("float", float(f32::MAX as f64))?;
+        check("double", float(1.0e300))?;
+        let err = check("float", float(1.0e39)).unwrap_err();
+        assert!(err.to_string().contains("is out of single precision range"));
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let err = check("double", float(value)).unwrap_err();
+            assert!(err.to_string().ends_with("is not a finite number"));
+            assert!(check("float null", float(value)).is_err());
+        }
+        assert!(check("double", string("1.5")).is_err());
+        assert!(check("float unsigned", float(-1.0)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal() -> Result<()> {
+        check("decimal(5,2)", string("999.99"))?;
+        check("decimal(5,2)", float(-12.5))?;
+        check("decimal(5,2)", string("0001.5"))?;
+        check("decimal(10)", serde_yaml::Value::Number(1234567890.into()))?;
+        let err = check("decimal(5,2)", string("1000")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Column col in table test_table expects decimal(5,2), but value '1000' has 4 integer digits > 3"
+        );
+        assert!(check("decimal(5,2)", string("1e3")).is_err());
+        assert!(check("decimal(5,2)", string("abc")).is_err());
+        assert!(check("decimal(5,2)", string("nan")).is_err());
+        assert!(check("decimal(5,2) unsigned", string("-1")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_date_and_time() -> Result<()> {
+        check("date", string("2024-02-29"))?;
+        check("datetime", string("2024-02-29 23:59:59"))?;
+        check("datetime", string("9999-12-31"))?;
+        check("datetime(6)", string("2024-01-01T12:00:00.123456"))?;
+        check("timestamp", string("2038-01-19 03:14:07"))?;
+        let err = check("date", string("2023-02-29")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Column col in table test_table expects date, but value '2023-02-29' is not a valid date (YYYY-MM-DD)"
+        );
+        assert!(check("date", string("2024-01-01 00:00:00")).is_err());
+        assert!(check("datetime", string("2024-01-01 24:00:00")).is_err());
+        assert!(check("datetime", string("0000-00-00 00:00:00")).is_err());
+        let err = check("timestamp", string("1969-12-31 23:59:59")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Column col in table test_table expects timestamp, but value '1969-12-31 23:59:59' is out of range (1970-01-01 00:00:01..=2038-01-19 03:14:07)"
+        );
+        assert!(check("date", string("0999-12-31")).is_err());
+        Ok(())
+    }
+}
@@ -15,17 +15,19 @@
 #[cfg(test)]
 mod tests {
     use crate::jobdata::checktypes::check_types;
+    use crate::jobdata::create_sql::SqlValue;
+    use crate::sqltypes::SqlColumn;
     use anyhow::Result;
 
     #[test]
     fn test_bigint_unsigned_lowercase() -> Result<()> {
         // Test that lowercase "bigint(20) unsigned" is properly handled
         let keys = vec!["count".to_string()];
-        let types = vec!["bigint(20) unsigned".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            u64::MAX,
+        let column = SqlColumn::from_column_type("bigint(20) unsigned");
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(u64::MAX),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_ok(),
             "Should accept max u64 value for unsigned bigint"
@@ -37,11 +39,11 @@ mod tests {
     fn test_bigint_signed_lowercase() -> Result<()> {
         // Test that lowercase "bigint(20)" is properly handled
         let keys = vec!["count".to_string()];
-        let types = vec!["bigint(20)".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            i64::MAX,
+        let column = SqlColumn::from_column_type("bigint(20)");
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(i64::MAX),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_ok(),
             "Should accept max i64 value for signed bigint"
@@ -53,11 +55,11 @@ mod tests {
     fn test_bigint_signed_negative() -> Result<()> {
         // Test that lowercase "bigint(20)" handles negative values
         let keys = vec!["count".to_string()];
-        let types = vec!["bigint(20)".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            i64::MIN,
+        let column = SqlColumn::from_column_type("bigint(20)");
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(i64::MIN),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_ok(),
             "Should accept min i64 value for signed bigint"
@@ -69,11 +71,11 @@ mod tests {
     fn test_int_unsigned_lowercase() -> Result<()> {
         // Test that lowercase "int(11) unsigned" is properly handled
         let keys = vec!["tid".to_string()];
-        let types = vec!["int(11) unsigned".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            u32::MAX,
+        let column = SqlColumn::from_column_type("int(11) unsigned");
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(u32::MAX),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_ok(),
             "Should accept max u32 value for unsigned int"
@@ -85,12 +87,12 @@ mod tests {
     fn test_int_unsigned_overflow() -> Result<()> {
         // Test that unsigned int rejects values > u32::MAX
         let keys = vec!["tid".to_string()];
-        let types = vec!["int(11) unsigned".to_string()];
+        let column = SqlColumn::from_column_type("int(11) unsigned");
         let too_large: u64 = u32::MAX as u64 + 1;
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            too_large,
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(too_large),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_err(),
             "Should reject value > u32::MAX for unsigned int"
@@ -106,11 +108,11 @@ mod tests {
     fn test_int_signed_lowercase() -> Result<()> {
         // Test that lowercase "int(11)" is properly handled
         let keys = vec!["calls".to_string()];
-        let types = vec!["int(11)".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            i32::MAX,
+        let column = SqlColumn::from_column_type("int(11)");
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(i32::MAX),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(result.is_ok(), "Should accept max i32 value for signed int");
         Ok(())
     }
@@ -119,12 +121,12 @@ mod tests {
     fn test_int_signed_overflow() -> Result<()> {
         // Test that signed int rejects values > i32::MAX
         let keys = vec!["calls".to_string()];
-        let types = vec!["int(11)".to_string()];
+        let column = SqlColumn::from_column_type("int(11)");
         let too_large: i64 = i32::MAX as i64 + 1;
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            too_large,
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(too_large),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_err(),
             "Should reject value > i32::MAX for signed int"
@@ -140,11 +142,11 @@ mod tests {
     fn test_tinyint_unsigned_lowercase() -> Result<()> {
         // Test that lowercase "tinyint(4) unsigned" is properly handled
         let keys = vec!["ht".to_string()];
-        let types = vec!["tinyint(4) unsigned".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            u8::MAX,
+        let column = SqlColumn::from_column_type("tinyint(4) unsigned");
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(u8::MAX),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_ok(),
             "Should accept max u8 value for unsigned tinyint"
@@ -156,12 +158,12 @@ mod tests {
     fn test_tinyint_unsigned_overflow() -> Result<()> {
         // Test that unsigned tinyint rejects values > u8::MAX
         let keys = vec!["ht".to_string()];
-        let types = vec!["tinyint(4) unsigned".to_string()];
+        let column = SqlColumn::from_column_type("tinyint(4) unsigned");
         let too_large: u16 = u8::MAX as u16 + 1;
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            too_large,
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(too_large),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_err(),
             "Should reject value > u8::MAX for unsigned tinyint"
@@ -177,11 +179,11 @@ mod tests {
     fn test_smallint_unsigned_lowercase() -> Result<()> {
         // Test that lowercase "smallint(6) unsigned" is properly handled
         let keys = vec!["regid".to_string()];
-        let types = vec!["smallint(6) unsigned".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            u16::MAX,
+        let column = SqlColumn::from_column_type("smallint(6) unsigned");
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(u16::MAX),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_ok(),
             "Should accept max u16 value for unsigned smallint"
@@ -193,12 +195,12 @@ mod tests {
     fn test_smallint_unsigned_overflow() -> Result<()> {
         // Test that unsigned smallint rejects values > u16::MAX
         let keys = vec!["regid".to_string()];
-        let types = vec!["smallint(6) unsigned".to_string()];
+        let column = SqlColumn::from_column_type("smallint(6) unsigned");
         let too_large: u32 = u16::MAX as u32 + 1;
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            too_large,
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(too_large),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_err(),
             "Should reject value > u16::MAX for unsigned smallint"
@@ -214,11 +216,11 @@ mod tests {
     fn test_case_insensitive_uppercase_bigint() -> Result<()> {
         // Test that uppercase "BIGINT(20) UNSIGNED" is also handled (case-insensitive)
         let keys = vec!["count".to_string()];
-        let types = vec!["BIGINT(20) UNSIGNED".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            u64::MAX,
+        let column = SqlColumn::from_column_type("BIGINT(20) UNSIGNED");
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(u64::MAX),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_ok(),
             "Should accept max u64 value for BIGINT UNSIGNED (uppercase)"
@@ -230,11 +232,11 @@ mod tests {
     fn test_mixed_case_int() -> Result<()> {
         // Test that mixed case "Int(11) Unsigned" is also handled
         let keys = vec!["tid".to_string()];
-        let types = vec!["Int(11) Unsigned".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            u32::MAX,
+        let column = SqlColumn::from_column_type("Int(11) Unsigned");
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(u32::MAX),
         ))]];
-        let result = check_types("test_table", &keys, &types, &values);
+        let result = check_types("test_table", &keys, &[&column], &values);
         assert!(
             result.is_ok(),
             "Should accept max u32 value for Int Unsigned (mixed case)"
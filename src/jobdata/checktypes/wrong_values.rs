@@ -15,7 +15,8 @@
 #[cfg(test)]
 mod tests {
     use crate::cmdline::CliArgs;
-    use crate::jobdata::checktypes::{check_types, get_types};
+    use crate::jobdata::checktypes::{check_types, get_columns};
+    use crate::jobdata::create_sql::SqlValue;
     use crate::sqltypes::{read_sqltypes, SqlTypeHashMap};
     use anyhow::Result;
     use sqlx::{MySql, Pool};
@@ -35,9 +36,9 @@ mod tests {
         let sqltypes: SqlTypeHashMap = read_sqltypes(Some(pool), &args).await?;
         let long_string = "a".repeat(40); // Assuming max length is less than 32
         let keys = vec!["compiler".to_string()];
-        let values = vec![vec![serde_yaml::Value::String(long_string)]];
-        let types = get_types("runs", &keys, &sqltypes)?;
-        let result = check_types("runs", &keys, &types, &values);
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::String(long_string))]];
+        let columns = get_columns("runs", &keys, &sqltypes)?;
+        let result = check_types("runs", &keys, &columns, &values);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
@@ -64,11 +65,11 @@ mod tests {
         };
         let sqltypes: SqlTypeHashMap = read_sqltypes(Some(pool), &args).await?;
         let keys = vec!["nodes".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            10000000,
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(10000000),
         ))]];
-        let types = get_types("runs", &keys, &sqltypes)?;
-        let result = check_types("runs", &keys, &types, &values);
+        let columns = get_columns("runs", &keys, &sqltypes)?;
+        let result = check_types("runs", &keys, &columns, &values);
         assert!(result.is_ok());
         Ok(())
     }
@@ -86,19 +87,19 @@ mod tests {
             ..Default::default()
         };
         let sqltypes: SqlTypeHashMap = read_sqltypes(Some(pool), &args).await?;
-        let long_binary = "a".repeat(1030); // Assuming max length is less than 4096
+        // 8194 hex digits are 4097 bytes, one more than the column holds
+        let long_binary = "a".repeat(8194);
         let keys = vec!["affinity".to_string()];
-        let values = vec![vec![serde_yaml::Value::String(long_binary)]];
-        let types = get_types("tasks", &keys, &sqltypes)?;
-        let result = check_types("tasks", &keys, &types, &values);
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::String(long_binary))]];
+        let columns = get_columns("tasks", &keys, &sqltypes)?;
+        let result = check_types("tasks", &keys, &columns, &values);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
             format!(
-                "Column affinity in table tasks expects varbinary(4096), but string value '{}' has length {} * 4 = {} >= 4096",
-                "a".repeat(1030),
-                1030,
-                1030*4
+                "Column affinity in table tasks expects varbinary(4096), but string value '{}' has {} bytes > 4096",
+                "a".repeat(8194),
+                4097
             )
         );
         Ok(())
@@ -118,11 +119,11 @@ mod tests {
         };
         let sqltypes: SqlTypeHashMap = read_sqltypes(Some(pool), &args).await?;
         let keys = vec!["tid".to_string()];
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            -1,
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(-1),
         ))]];
-        let types = get_types("tasks", &keys, &sqltypes)?;
-        let result = check_types("tasks", &keys, &types, &values);
+        let columns = get_columns("tasks", &keys, &sqltypes)?;
+        let result = check_types("tasks", &keys, &columns, &values);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
@@ -146,11 +147,11 @@ mod tests {
         let sqltypes: SqlTypeHashMap = read_sqltypes(Some(pool), &args).await?;
         let keys = vec!["tid".to_string()];
         let too_large: u64 = u32::MAX as u64 + 1;
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            too_large,
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(too_large),
         ))]];
-        let types = get_types("tasks", &keys, &sqltypes)?;
-        let result = check_types("tasks", &keys, &types, &values);
+        let columns = get_columns("tasks", &keys, &sqltypes)?;
+        let result = check_types("tasks", &keys, &columns, &values);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
@@ -179,11 +180,11 @@ mod tests {
         let sqltypes: SqlTypeHashMap = read_sqltypes(Some(pool), &args).await?;
         let keys = vec!["calls".to_string()];
         let too_large: i64 = i32::MAX as i64 + 1;
-        let values = vec![vec![serde_yaml::Value::Number(serde_yaml::Number::from(
-            too_large,
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Number(
+            serde_yaml::Number::from(too_large),
         ))]];
-        let types = get_types("mpi", &keys, &sqltypes)?;
-        let result = check_types("mpi", &keys, &types, &values);
+        let columns = get_columns("mpi", &keys, &sqltypes)?;
+        let result = check_types("mpi", &keys, &columns, &values);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
@@ -211,12 +212,11 @@ mod tests {
         };
         let sqltypes: SqlTypeHashMap = read_sqltypes(Some(pool), &args).await?;
         let keys = vec!["elapsed".to_string()];
-        let values = vec![vec![serde_yaml::Value::Null]];
-        let types = get_types("runs", &keys, &sqltypes)?;
-        let result = check_types("runs", &keys, &types, &values);
+        let values = vec![vec![SqlValue::from(serde_yaml::Value::Null)]];
+        let columns = get_columns("runs", &keys, &sqltypes)?;
+        let result = check_types("runs", &keys, &columns, &values);
         assert!(result.is_err());
-        let expected_msg =
-            "Column elapsed in table runs expects float, but value cannot be cast to float";
+        let expected_msg = "Column elapsed in table runs is NOT NULL, but the value is null";
         assert_eq!(result.unwrap_err().to_string(), expected_msg);
         Ok(())
     }
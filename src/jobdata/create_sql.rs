@@ -12,91 +12,332 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::jobdata::checktypes::{check_types, get_types, try_cast_into_string};
-use crate::sqltypes::SqlTypeHashMap;
-use anyhow::Result;
+use crate::cmdline::{CliArgs, DuplicatePolicy};
+use crate::jobdata::checktypes::{check_types, get_columns, try_cast_into_string};
+use crate::jobdata::statement::{hex_digits, BindValue, InsertedRows, Statement};
+use crate::jobdata::table_runs::foreign_keys::NEW_RUN;
+use crate::sqltypes::{SqlColumn, SqlTypeHashMap};
+use anyhow::{bail, Result};
 
+#[cfg(test)]
+pub(crate) mod test_binary;
 #[cfg(test)]
 pub(crate) mod test_import;
 #[cfg(test)]
+pub(crate) mod test_null;
+#[cfg(test)]
+pub(crate) mod test_row_count;
+#[cfg(test)]
+pub(crate) mod test_split_insert;
+#[cfg(test)]
+pub(crate) mod test_sql_value;
+#[cfg(test)]
 pub(crate) mod test_update;
 
+/// Size limit of INSERT statements if neither `--max-insert-bytes` is given
+/// nor a database connection is available, the default `max_allowed_packet`
+/// of MariaDB
+pub const DEFAULT_MAX_INSERT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Maximum number of `?` placeholders of a prepared statement in MariaDB
+pub const MAX_PLACEHOLDERS: usize = u16::MAX as usize;
+
+/// Limits for the size of a single INSERT statement. Rows exceeding
+/// these limits are inserted by several statements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertLimits {
+    pub max_rows: Option<usize>,
+    pub max_bytes: usize,
+    /// Condition each row is only inserted under, e.g. `NEW_RUN`
+    pub condition: Option<&'static str>,
+}
+
+impl Default for InsertLimits {
+    fn default() -> Self {
+        InsertLimits {
+            max_rows: None,
+            max_bytes: DEFAULT_MAX_INSERT_BYTES,
+            condition: None,
+        }
+    }
+}
+
+impl InsertLimits {
+    /// Takes the limits from `--max-insert-rows` and `--max-insert-bytes`.
+    /// With a database connection, the byte limit has been capped at
+    /// `max_allowed_packet` of the server before.
+    /// With `--on-duplicate skip`, the rows are not inserted for an existing run.
+    pub fn from_args(args: &CliArgs) -> Self {
+        InsertLimits {
+            max_rows: args.max_insert_rows,
+            max_bytes: args.max_insert_bytes.unwrap_or(DEFAULT_MAX_INSERT_BYTES),
+            condition: (args.on_duplicate == DuplicatePolicy::Skip).then_some(NEW_RUN),
+        }
+    }
+}
+
+/// A value of a column in the statements created by `create_import_statement`
+/// and `create_update_statement`. Only literals are checked against the column
+/// type, references are resolved by the database.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    /// A value from the input data, quoted if it is a string
+    /// or the column has a string type, a hex literal for binary columns
+    Literal(serde_yaml::Value),
+    /// A session variable set by a previous statement, e.g. `@rid`
+    Variable(String),
+    /// A call of a stored function with its arguments, e.g. `mpi_call_id('MPI_Send')`
+    Function(String, Vec<SqlValue>),
+    /// SQL NULL
+    Null,
+}
+
+impl SqlValue {
+    /// Creates a reference to the session variable `name`, e.g. `@rid`
+    pub fn variable(name: &str) -> Self {
+        SqlValue::Variable(name.to_string())
+    }
+
+    /// Creates a call of the stored function `name` with the given arguments
+    pub fn function(name: &str, arguments: Vec<SqlValue>) -> Self {
+        SqlValue::Function(name.to_string(), arguments)
+    }
+
+    /// Renders the value as SQL for `column`, with literals bound to `?`
+    /// placeholders. Arguments of stored functions are rendered without a column.
+    /// NULL and null literals are rendered as `NULL`, values of binary
+    /// columns are bound as bytes and rendered as hex literals `X'...'`.
+    ///
+    /// # Errors
+    /// Returns an error if a literal cannot be cast into a string,
+    /// or if the value of a binary column is not hexadecimal
+    ///
+    pub fn to_sql(&self, column: Option<&SqlColumn>) -> Result<Statement> {
+        match self {
+            SqlValue::Null | SqlValue::Literal(serde_yaml::Value::Null) => Ok("NULL".into()),
+            SqlValue::Literal(value) => {
+                let value_string = try_cast_into_string(value)?;
+                let sql_type = column.map_or("", |column| column.sql_type.as_str());
+                let bind_value = if sql_type.contains("binary") {
+                    match hex_digits(&value_string) {
+                        Some(digits) => BindValue::Hex(digits),
+                        None => bail!(
+                            "Value '{}' for column type {} is not hexadecimal",
+                            value_string,
+                            sql_type
+                        ),
+                    }
+                } else if value.is_string() || sql_type.contains("varchar") {
+                    BindValue::Text(value_string)
+                } else {
+                    BindValue::Number(value_string)
+                };
+                Ok(Statement::new("?").bind(bind_value))
+            }
+            SqlValue::Variable(name) => Ok(name.as_str().into()),
+            SqlValue::Function(name, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| argument.to_sql(None))
+                    .collect::<Result<Vec<Statement>>>()?;
+                Ok(join_statements(
+                    &format!("{}(", name),
+                    &arguments,
+                    ", ",
+                    ")",
+                ))
+            }
+        }
+    }
+}
+
+/// Joins the parts of a statement with `separator`, between `prefix` and `suffix`.
+/// The values bound to the parts are kept in order.
+fn join_statements(prefix: &str, parts: &[Statement], separator: &str, suffix: &str) -> Statement {
+    let sql: Vec<&str> = parts.iter().map(|part| part.sql.as_str()).collect();
+    Statement::with_binds(
+        format!("{}{}{}", prefix, sql.join(separator), suffix),
+        parts.iter().flat_map(|part| part.binds.clone()).collect(),
+    )
+}
+
+impl From<serde_yaml::Value> for SqlValue {
+    fn from(value: serde_yaml::Value) -> Self {
+        match value {
+            serde_yaml::Value::Null => SqlValue::Null,
+            value => SqlValue::Literal(value),
+        }
+    }
+}
+
+impl From<&str> for SqlValue {
+    fn from(value: &str) -> Self {
+        SqlValue::Literal(serde_yaml::Value::String(value.to_string()))
+    }
+}
+
+impl From<String> for SqlValue {
+    fn from(value: String) -> Self {
+        SqlValue::Literal(serde_yaml::Value::String(value))
+    }
+}
+
+/// Creates INSERT statements for one or more rows of a table.
+/// The values are checked against the column types in `sqltypes` first.
+/// Literal values are bound to `?` placeholders, see `SqlValue::to_sql`.
+/// The rows are inserted by a single statement, unless this exceeds
+/// the row or byte limit or the number of placeholders MariaDB accepts.
+/// Then they are split into several statements. The byte limit applies
+/// to the statement as rendered for the SQL file.
+/// With a condition in `limits`, each row is selected `FROM DUAL WHERE` the
+/// condition instead, and the rows are combined by `UNION ALL`.
+///
+/// # Arguments
+/// * `table_name` - The name of the table
+/// * `keys` - The column names
+/// * `values` - The value rows, each with one value per column
+/// * `sqltypes` - The SQL type mapping of the database schema
+/// * `limits` - The limits for a single INSERT statement
+///
+/// # Returns
+/// Returns the INSERT statements, none if there are no rows.
+/// Each statement records the rows it inserts.
+///
+/// # Errors
+/// Returns an error if a value does not conform to its column type,
+/// or if a single row exceeds the byte limit
+///
 pub fn create_import_statement(
     table_name: &str,
     keys: &[String],
-    values: &[Vec<serde_yaml::Value>],
+    values: &[Vec<SqlValue>],
     sqltypes: &SqlTypeHashMap,
-) -> Result<String> {
+    limits: &InsertLimits,
+) -> Result<Vec<Statement>> {
     // First, check types
-    let types: Vec<String> = get_types(table_name, keys, sqltypes)?;
-    check_types(table_name, keys, &types, values)?;
+    let columns = get_columns(table_name, keys, sqltypes)?;
+    check_types(table_name, keys, &columns, values)?;
 
-    let value_rows: Vec<String> = values
-        .iter()
-        .map(|value_row| {
-            let row_values: Vec<String> = value_row
-                .iter()
-                .enumerate()
-                .map(|(i, v)| {
-                    let v_string = try_cast_into_string(v)?;
-                    let v_final = if types[i].contains("varbinary")
-                        || types[i].contains("varchar")
-                        || types[i].contains("binary")
-                    {
-                        format!("'{}'", v_string.replace("'", "''"))
-                    } else {
-                        v_string
-                    };
-                    Ok(v_final)
-                })
-                .collect::<Result<Vec<String>>>()?;
-            Ok(format!("({})", row_values.join(", ")))
-        })
-        .collect::<Result<Vec<String>>>()?;
-
-    let sql = format!(
-        "INSERT INTO {} ({}) VALUES\n{};",
-        table_name,
-        keys.join(", "),
-        value_rows.join(",\n")
-    );
-    Ok(sql)
+    let (header, row_prefix, row_suffix, separator) = match limits.condition {
+        Some(condition) => (
+            format!("INSERT INTO {} ({})\n", table_name, keys.join(", ")),
+            "SELECT ".to_string(),
+            format!(" FROM DUAL WHERE {}", condition),
+            "\nUNION ALL\n",
+        ),
+        None => (
+            format!("INSERT INTO {} ({}) VALUES\n", table_name, keys.join(", ")),
+            "(".to_string(),
+            ")".to_string(),
+            ",\n",
+        ),
+    };
+    let mut statements: Vec<Statement> = Vec::new();
+    let mut rows: Vec<Statement> = Vec::new();
+    // Size of the current statement without the final ';'
+    let mut size = header.len();
+    let mut placeholders = 0;
+    for value_row in values {
+        let row_values: Vec<Statement> = value_row
+            .iter()
+            .enumerate()
+            .map(|(i, v)| v.to_sql(Some(columns[i])))
+            .collect::<Result<Vec<Statement>>>()?;
+        let row = join_statements(&row_prefix, &row_values, ", ", &row_suffix);
+        let row_size = row.render().len();
+        if header.len() + row_size + 1 > limits.max_bytes {
+            bail!(
+                "Row of table {} has {} bytes and exceeds the limit of {} bytes per INSERT statement",
+                table_name,
+                row_size,
+                limits.max_bytes
+            );
+        }
+        if !rows.is_empty()
+            && (size + separator.len() + row_size + 1 > limits.max_bytes
+                || placeholders + row.binds.len() > MAX_PLACEHOLDERS
+                || limits
+                    .max_rows
+                    .is_some_and(|max_rows| rows.len() >= max_rows))
+        {
+            statements.push(insert_statement(
+                table_name, keys, &header, &rows, separator,
+            ));
+            rows.clear();
+            size = header.len();
+            placeholders = 0;
+        }
+        size += row_size + if rows.is_empty() { 0 } else { separator.len() };
+        placeholders += row.binds.len();
+        rows.push(row);
+    }
+    if !rows.is_empty() {
+        statements.push(insert_statement(
+            table_name, keys, &header, &rows, separator,
+        ));
+    }
+    Ok(statements)
+}
+
+/// Creates the statement inserting `rows` of a table
+fn insert_statement(
+    table_name: &str,
+    keys: &[String],
+    header: &str,
+    rows: &[Statement],
+    separator: &str,
+) -> Statement {
+    join_statements(header, rows, separator, ";").inserting(InsertedRows {
+        table: table_name.to_string(),
+        columns: keys.to_vec(),
+        rows: rows.len(),
+    })
 }
 
+/// Creates an UPDATE statement setting the given columns of a table.
+/// The values are checked against the column types in `sqltypes` first
+/// and bound to `?` placeholders like in `create_import_statement`.
+///
+/// # Arguments
+/// * `table_name` - The name of the table
+/// * `column` - Pairs of column name and value
+/// * `where_clause` - The condition selecting the rows to update
+/// * `sqltypes` - The SQL type mapping of the database schema
+///
+/// # Returns
+/// Returns the UPDATE statement
+///
+/// # Errors
+/// Returns an error if a value does not conform to its column type
+///
 pub fn create_update_statement(
     table_name: &str,
-    column: &[(String, serde_yaml::Value)],
+    column: &[(String, SqlValue)],
     where_clause: &str,
     sqltypes: &SqlTypeHashMap,
-) -> Result<String> {
+) -> Result<Statement> {
     // First, check types - convert to new API format
     let keys: Vec<String> = column.iter().map(|(k, _)| k.clone()).collect();
-    let values: Vec<Vec<serde_yaml::Value>> = vec![column.iter().map(|(_, v)| v.clone()).collect()];
-    let types: Vec<String> = get_types(table_name, &keys, sqltypes)?;
-    check_types(table_name, &keys, &types, &values)?;
+    let values: Vec<Vec<SqlValue>> = vec![column.iter().map(|(_, v)| v.clone()).collect()];
+    let columns = get_columns(table_name, &keys, sqltypes)?;
+    check_types(table_name, &keys, &columns, &values)?;
 
-    let set_clauses: Vec<String> = column
+    let set_clauses: Vec<Statement> = column
         .iter()
         .enumerate()
         .map(|(i, (k, v))| {
-            let v_string = try_cast_into_string(v)?;
-            let v_final = if types[i].contains("varbinary")
-                || types[i].contains("varchar")
-                || types[i].contains("binary")
-            {
-                format!("'{}'", v_string.replace("'", "''"))
-            } else {
-                v_string
-            };
-            Ok(format!("{} = {}", k, v_final))
+            let value = v.to_sql(Some(columns[i]))?;
+            Ok(Statement::with_binds(
+                format!("{} = {}", k, value.sql),
+                value.binds,
+            ))
         })
-        .collect::<Result<Vec<String>>>()?;
-
-    let sql = format!(
-        "UPDATE {} SET {} WHERE {};",
-        table_name,
-        set_clauses.join(",\n"),
-        where_clause
-    );
-    Ok(sql)
+        .collect::<Result<Vec<Statement>>>()?;
+
+    Ok(join_statements(
+        &format!("UPDATE {} SET ", table_name),
+        &set_clauses,
+        ",\n",
+        &format!(" WHERE {};", where_clause),
+    ))
 }
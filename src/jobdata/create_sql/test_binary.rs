@@ -0,0 +1,138 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::CliArgs;
+    use crate::jobdata::create_sql::{create_import_statement, InsertLimits, SqlValue};
+    use crate::jobdata::statement::{hex_digits, BindValue, Statement};
+    use crate::sqltypes::{read_sqltypes, SqlColumn, SqlTypeHashMap};
+    use anyhow::Result;
+
+    async fn shipped_sqltypes() -> Result<SqlTypeHashMap> {
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            ..Default::default()
+        };
+        read_sqltypes(None, &args).await
+    }
+
+    fn import_runs_md5sum(md5sum: &str, sqltypes: &SqlTypeHashMap) -> Result<Vec<String>> {
+        let keys = vec!["rid".to_string(), "md5sum_exe".to_string()];
+        let values = vec![vec![SqlValue::variable("@rid"), SqlValue::from(md5sum)]];
+        let statements =
+            create_import_statement("runs", &keys, &values, sqltypes, &InsertLimits::default())?;
+        Ok(statements.iter().map(Statement::render).collect())
+    }
+
+    #[test]
+    fn test_hex_digits() {
+        assert_eq!(hex_digits("00ff"), Some("00ff".to_string()));
+        assert_eq!(hex_digits("ABC"), Some("0ABC".to_string()));
+        assert_eq!(hex_digits(""), Some(String::new()));
+        assert_eq!(hex_digits("0x01"), None);
+        assert_eq!(hex_digits("00 1"), None);
+        assert_eq!(BindValue::Hex("0ABC".to_string()).to_literal(), "X'0ABC'");
+    }
+
+    #[test]
+    fn test_binary_to_sql() {
+        let value = SqlValue::from("0001");
+        assert_eq!(
+            value
+                .to_sql(Some(&SqlColumn::from_column_type("varbinary(4096)")))
+                .unwrap()
+                .render(),
+            "X'0001'"
+        );
+        assert_eq!(
+            value
+                .to_sql(Some(&SqlColumn::from_column_type("binary(2) null")))
+                .unwrap()
+                .render(),
+            "X'0001'"
+        );
+        assert_eq!(
+            value
+                .to_sql(Some(&SqlColumn::from_column_type("varchar(32)")))
+                .unwrap()
+                .render(),
+            "'0001'"
+        );
+        // Numbers read from YAML are hex digits as well
+        let value = SqlValue::Literal(serde_yaml::Value::Number(101.into()));
+        assert_eq!(
+            value
+                .to_sql(Some(&SqlColumn::from_column_type("varbinary(4096)")))
+                .unwrap()
+                .render(),
+            "X'0101'"
+        );
+        let err = SqlValue::from("node0")
+            .to_sql(Some(&SqlColumn::from_column_type("varbinary(4096)")))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Value 'node0' for column type varbinary(4096) is not hexadecimal"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_binary_md5sum() -> Result<()> {
+        let sqltypes = shipped_sqltypes().await?;
+        let md5sum = "d41d8cd98f00b204e9800998ecf8427e";
+        let statements = import_runs_md5sum(md5sum, &sqltypes)?;
+        assert_eq!(
+            statements,
+            vec![format!(
+                "INSERT INTO runs (rid, md5sum_exe) VALUES\n(@rid, X'{}');",
+                md5sum
+            )]
+        );
+
+        // binary(16) takes exactly 16 bytes
+        for length in [30, 34] {
+            let err = import_runs_md5sum(&"a".repeat(length), &sqltypes).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "Column md5sum_exe in table runs expects binary(16), but string value '{}' has {} bytes instead of 16",
+                    "a".repeat(length),
+                    length / 2
+                )
+            );
+        }
+        let err = import_runs_md5sum("g41d8cd98f00b204e9800998ecf8427e", &sqltypes).unwrap_err();
+        assert!(err.to_string().contains("contains invalid hex characters"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_varbinary_affinity() -> Result<()> {
+        let sqltypes = shipped_sqltypes().await?;
+        let keys = vec!["affinity".to_string()];
+        let limits = InsertLimits::default();
+
+        // 4096 bytes fit, an odd number of digits counts as whole bytes
+        for affinity in ["ff".repeat(4096), "f".repeat(8191)] {
+            let values = vec![vec![SqlValue::from(affinity)]];
+            let statements = create_import_statement("tasks", &keys, &values, &sqltypes, &limits)?;
+            assert_eq!(statements.len(), 1);
+        }
+        let values = vec![vec![SqlValue::from("f".repeat(8193))]];
+        let err = create_import_statement("tasks", &keys, &values, &sqltypes, &limits).unwrap_err();
+        assert!(err.to_string().ends_with("has 4097 bytes > 4096"));
+        Ok(())
+    }
+}
@@ -15,7 +15,7 @@
 #[cfg(test)]
 mod tests {
     use crate::cmdline::CliArgs;
-    use crate::jobdata::create_sql::create_import_statement;
+    use crate::jobdata::create_sql::{create_import_statement, InsertLimits, SqlValue};
     use crate::sqltypes::{read_sqltypes, SqlTypeHashMap};
     use anyhow::Result;
     use sqlx::{MySql, Pool};
@@ -42,14 +42,15 @@ mod tests {
             "nodes".to_string(),
         ];
         let values = vec![vec![
-            serde_yaml::Value::String("@rid".to_string()),
-            serde_yaml::Value::String("gcc".to_string()),
-            serde_yaml::Value::Number(serde_yaml::Number::from(16)),
+            SqlValue::variable("@rid"),
+            SqlValue::from(serde_yaml::Value::String("gcc".to_string())),
+            SqlValue::from(serde_yaml::Value::Number(serde_yaml::Number::from(16))),
         ]];
 
-        let sql = create_import_statement("runs", &keys, &values, &sqltypes)?;
+        let sql =
+            create_import_statement("runs", &keys, &values, &sqltypes, &InsertLimits::default())?;
         assert_eq!(
-            sql,
+            sql[0].render(),
             "INSERT INTO runs (rid, compiler, nodes) VALUES\n(@rid, 'gcc', 16);"
         );
         Ok(())
@@ -78,25 +79,26 @@ mod tests {
         ];
         let values = vec![
             vec![
-                serde_yaml::Value::String("@testaid".to_string()),
-                serde_yaml::Value::String("gcc".to_string()),
-                serde_yaml::Value::Number(serde_yaml::Number::from(16)),
+                SqlValue::variable("@testaid"),
+                SqlValue::from(serde_yaml::Value::String("gcc".to_string())),
+                SqlValue::from(serde_yaml::Value::Number(serde_yaml::Number::from(16))),
             ],
             vec![
-                serde_yaml::Value::String("@testbid".to_string()),
-                serde_yaml::Value::String("icc".to_string()),
-                serde_yaml::Value::Number(serde_yaml::Number::from(32)),
+                SqlValue::variable("@testbid"),
+                SqlValue::from(serde_yaml::Value::String("icc".to_string())),
+                SqlValue::from(serde_yaml::Value::Number(serde_yaml::Number::from(32))),
             ],
             vec![
-                serde_yaml::Value::String("@testcid".to_string()),
-                serde_yaml::Value::String("clang".to_string()),
-                serde_yaml::Value::Number(serde_yaml::Number::from(64)),
+                SqlValue::variable("@testcid"),
+                SqlValue::from(serde_yaml::Value::String("clang".to_string())),
+                SqlValue::from(serde_yaml::Value::Number(serde_yaml::Number::from(64))),
             ],
         ];
 
-        let sql = create_import_statement("runs", &keys, &values, &sqltypes)?;
+        let sql =
+            create_import_statement("runs", &keys, &values, &sqltypes, &InsertLimits::default())?;
         assert_eq!(
-            sql,
+            sql[0].render(),
             "INSERT INTO runs (rid, compiler, nodes) VALUES\n(@testaid, 'gcc', 16),\n(@testbid, 'icc', 32),\n(@testcid, 'clang', 64);"
         );
         Ok(())
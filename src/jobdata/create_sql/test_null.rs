@@ -0,0 +1,111 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::CliArgs;
+    use crate::jobdata::create_sql::{
+        create_import_statement, create_update_statement, InsertLimits, SqlValue,
+    };
+    use crate::jobdata::statement::Statement;
+    use crate::jobdata::table_mmm::import_into_mmm_table;
+    use crate::jobdata::LmxSummary;
+    use crate::sqltypes::{read_sqltypes, split_nullable, SqlColumn, SqlTypeHashMap};
+    use anyhow::Result;
+    use std::collections::HashMap;
+
+    async fn shipped_sqltypes() -> Result<SqlTypeHashMap> {
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            ..Default::default()
+        };
+        read_sqltypes(None, &args).await
+    }
+
+    #[test]
+    fn test_split_nullable() {
+        assert_eq!(split_nullable("varchar(32) null"), ("varchar(32)", true));
+        assert_eq!(
+            split_nullable("int(11) unsigned"),
+            ("int(11) unsigned", false)
+        );
+        assert_eq!(
+            SqlValue::Literal(serde_yaml::Value::Null)
+                .to_sql(Some(&SqlColumn::from_column_type("int(11) null")))
+                .unwrap()
+                .render(),
+            "NULL"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_null_for_nullable_columns() -> Result<()> {
+        let sqltypes = shipped_sqltypes().await?;
+        let null = || SqlValue::from(serde_yaml::Value::Null);
+        let columns = vec![
+            ("perf_unit".to_string(), null()),
+            ("git_commit".to_string(), null()),
+            ("jobid".to_string(), null()),
+        ];
+        let sql = create_update_statement("runs", &columns, "rid = @rid", &sqltypes)?;
+        assert_eq!(
+            sql.render(),
+            "UPDATE runs SET perf_unit = NULL,\ngit_commit = NULL,\njobid = NULL WHERE rid = @rid;"
+        );
+
+        // A column that does not accept NULL is named in the error
+        let columns = vec![("elapsed".to_string(), null())];
+        let err = create_update_statement("runs", &columns, "rid = @rid", &sqltypes).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Column elapsed in table runs is NOT NULL, but the value is null"
+        );
+        let keys = vec!["rid".to_string(), "k".to_string(), "value".to_string()];
+        let values = vec![vec![
+            SqlValue::variable("@rid"),
+            null(),
+            SqlValue::from("v"),
+        ]];
+        let err = create_import_statement(
+            "settings",
+            &keys,
+            &values,
+            &sqltypes,
+            &InsertLimits::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Column k in table settings is NOT NULL, but the value is null"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_null_in_mmm_data() -> Result<()> {
+        let sqltypes = shipped_sqltypes().await?;
+        let min_max_times: HashMap<String, serde_yaml::Value> =
+            serde_yaml::from_str("min_comm: [3, 1.5]\nmed_comm: [~, ~]\nmax_comm: ~\n")?;
+        let lmx_summary: LmxSummary = HashMap::from([("min_max_times".to_string(), min_max_times)]);
+        let sql: Vec<String> = import_into_mmm_table(&lmx_summary, &sqltypes, &CliArgs::default())?
+            .iter()
+            .map(Statement::render)
+            .collect();
+        assert_eq!(sql.len(), 2);
+        // The null entry is omitted, the null values are inserted as NULL
+        assert!(sql[1].contains("(rid, mintask, mincomm, medtask, medcomm)"));
+        assert!(sql[1].ends_with("(@rid, 3, 1.5, NULL, NULL);"));
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::CliArgs;
+    use crate::jobdata::create_sql::{create_import_statement, InsertLimits, SqlValue};
+    use crate::jobdata::statement::InsertedRows;
+    use crate::sqltypes::{read_sqltypes, SqlTypeHashMap};
+    use anyhow::Result;
+
+    #[tokio::test]
+    async fn test_inserted_rows() -> Result<()> {
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            ..Default::default()
+        };
+        let sqltypes: SqlTypeHashMap = read_sqltypes(None, &args).await?;
+
+        let keys = vec!["rid".to_string(), "k".to_string(), "value".to_string()];
+        let values = vec![
+            vec![
+                SqlValue::variable("@rid"),
+                SqlValue::from(serde_yaml::Value::String("PS1".to_string())),
+                SqlValue::from(serde_yaml::Value::String("(it's) a (prompt".to_string())),
+            ],
+            vec![
+                SqlValue::variable("@rid"),
+                SqlValue::from(serde_yaml::Value::String("PATH".to_string())),
+                SqlValue::from(serde_yaml::Value::String("/usr/bin,\n(/bin)".to_string())),
+            ],
+        ];
+        let sql = create_import_statement(
+            "environ",
+            &keys,
+            &values,
+            &sqltypes,
+            &InsertLimits::default(),
+        )?;
+        assert_eq!(
+            sql[0].inserted,
+            Some(InsertedRows {
+                table: "environ".to_string(),
+                columns: keys.clone(),
+                rows: 2,
+            })
+        );
+
+        // Each statement of a split INSERT records its own rows
+        let limits = InsertLimits {
+            max_rows: Some(1),
+            ..Default::default()
+        };
+        let sql = create_import_statement("environ", &keys, &values, &sqltypes, &limits)?;
+        assert_eq!(sql.len(), 2);
+        assert!(sql
+            .iter()
+            .all(|statement| statement.inserted.as_ref().map(|i| i.rows) == Some(1)));
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::CliArgs;
+    use crate::jobdata::create_sql::{
+        create_import_statement, InsertLimits, SqlValue, MAX_PLACEHOLDERS,
+    };
+    use crate::jobdata::table_runs::foreign_keys::NEW_RUN;
+    use crate::sqltypes::{read_sqltypes, SqlTypeHashMap};
+    use anyhow::Result;
+
+    /// Creates `count` rows for the environ table
+    fn environ_rows(count: usize) -> Vec<Vec<SqlValue>> {
+        (0..count)
+            .map(|i| {
+                vec![
+                    SqlValue::variable("@rid"),
+                    SqlValue::from(format!("VAR_{:04}", i)),
+                    SqlValue::from("x".repeat(100)),
+                ]
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_split_by_rows_and_bytes() -> Result<()> {
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            ..Default::default()
+        };
+        let sqltypes: SqlTypeHashMap = read_sqltypes(None, &args).await?;
+        let keys = vec!["rid".to_string(), "k".to_string(), "value".to_string()];
+        let values = environ_rows(250);
+
+        // Without limits, all rows are inserted by a single statement
+        let statements = create_import_statement(
+            "environ",
+            &keys,
+            &values,
+            &sqltypes,
+            &InsertLimits::default(),
+        )?;
+        assert_eq!(statements.len(), 1);
+
+        // Row limit
+        let limits = InsertLimits {
+            max_rows: Some(100),
+            ..Default::default()
+        };
+        let statements = create_import_statement("environ", &keys, &values, &sqltypes, &limits)?;
+        let rows: Vec<usize> = statements
+            .iter()
+            .map(|statement| statement.inserted.as_ref().unwrap().rows)
+            .collect();
+        assert_eq!(rows, vec![100, 100, 50]);
+
+        // Byte limit
+        let limits = InsertLimits {
+            max_bytes: 4096,
+            ..Default::default()
+        };
+        let statements = create_import_statement("environ", &keys, &values, &sqltypes, &limits)?;
+        assert!(statements.len() > 1);
+        assert!(statements
+            .iter()
+            .all(|statement| statement.sql.len() <= 4096));
+        let total_rows: usize = statements
+            .iter()
+            .map(|statement| statement.inserted.as_ref().unwrap().rows)
+            .sum();
+        assert_eq!(total_rows, 250);
+        assert!(statements[0]
+            .sql
+            .starts_with("INSERT INTO environ (rid, k, value) VALUES\n"));
+        assert!(statements[1]
+            .sql
+            .starts_with("INSERT INTO environ (rid, k, value) VALUES\n"));
+        assert!(statements[1].sql.ends_with(");"));
+
+        // A single row larger than the byte limit cannot be inserted
+        let limits = InsertLimits {
+            max_bytes: 100,
+            ..Default::default()
+        };
+        let result = create_import_statement("environ", &keys, &values, &sqltypes, &limits);
+        assert!(result.is_err());
+
+        // No rows, no statements
+        let statements = create_import_statement("environ", &keys, &[], &sqltypes, &limits)?;
+        assert!(statements.is_empty());
+
+        // Placeholder limit, each row binds two values
+        let values = environ_rows(40000);
+        let statements = create_import_statement(
+            "environ",
+            &keys,
+            &values,
+            &sqltypes,
+            &InsertLimits::default(),
+        )?;
+        let binds: Vec<usize> = statements
+            .iter()
+            .map(|statement| statement.binds.len())
+            .collect();
+        assert_eq!(
+            binds,
+            vec![MAX_PLACEHOLDERS - 1, 80000 - MAX_PLACEHOLDERS + 1]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_split_with_condition() -> Result<()> {
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            ..Default::default()
+        };
+        let sqltypes: SqlTypeHashMap = read_sqltypes(None, &args).await?;
+        let keys = vec!["rid".to_string(), "k".to_string(), "value".to_string()];
+        let values = environ_rows(250);
+
+        // Each row is selected under the condition, also after splitting
+        let limits = InsertLimits {
+            max_bytes: 4096,
+            condition: Some(NEW_RUN),
+            ..Default::default()
+        };
+        let statements = create_import_statement("environ", &keys, &values, &sqltypes, &limits)?;
+        assert!(statements.len() > 1);
+        let total_rows: usize = statements
+            .iter()
+            .map(|statement| statement.inserted.as_ref().unwrap().rows)
+            .sum();
+        assert_eq!(total_rows, 250);
+        for statement in &statements {
+            let sql = statement.render();
+            assert!(sql.len() <= 4096);
+            assert!(sql.starts_with("INSERT INTO environ (rid, k, value)\nSELECT @rid, 'VAR_"));
+            assert!(sql.ends_with(" FROM DUAL WHERE NOT @run_exists;"));
+            assert_eq!(
+                sql.matches(" FROM DUAL WHERE NOT @run_exists").count(),
+                statement.inserted.as_ref().unwrap().rows
+            );
+            assert_eq!(
+                sql.matches("\nUNION ALL\nSELECT ").count() + 1,
+                statement.inserted.as_ref().unwrap().rows
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::CliArgs;
+    use crate::jobdata::create_sql::{create_import_statement, InsertLimits, SqlValue};
+    use crate::jobdata::statement::BindValue;
+    use crate::sqltypes::{read_sqltypes, SqlColumn, SqlTypeHashMap};
+    use anyhow::Result;
+
+    #[test]
+    fn test_sql_value_rendering() -> Result<()> {
+        assert_eq!(
+            SqlValue::variable("@rid")
+                .to_sql(Some(&SqlColumn::from_column_type("int(11)")))?
+                .render(),
+            "@rid"
+        );
+        assert_eq!(
+            SqlValue::Null
+                .to_sql(Some(&SqlColumn::from_column_type("varchar(32)")))?
+                .render(),
+            "NULL"
+        );
+        assert_eq!(
+            SqlValue::from("it's")
+                .to_sql(Some(&SqlColumn::from_column_type("varchar(32)")))?
+                .render(),
+            "'it''s'"
+        );
+        let number = SqlValue::Literal(serde_yaml::Value::Number(42.into()));
+        assert_eq!(
+            number
+                .to_sql(Some(&SqlColumn::from_column_type("int(11)")))?
+                .render(),
+            "42"
+        );
+        assert_eq!(
+            number
+                .to_sql(Some(&SqlColumn::from_column_type("varchar(32)")))?
+                .render(),
+            "'42'"
+        );
+        let function = SqlValue::function(
+            "location_id",
+            vec![
+                SqlValue::from("node's"),
+                SqlValue::variable("@cl_name"),
+                SqlValue::from("nodes"),
+            ],
+        );
+        assert_eq!(
+            function
+                .to_sql(Some(&SqlColumn::from_column_type("int(11)")))?
+                .render(),
+            "location_id('node''s', @cl_name, 'nodes')"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_strings_are_never_references() -> Result<()> {
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            ..Default::default()
+        };
+        let sqltypes: SqlTypeHashMap = read_sqltypes(None, &args).await?;
+        let keys = vec!["rid".to_string(), "k".to_string(), "value".to_string()];
+
+        // Strings looking like SQL are bound and quoted as any other string
+        let values = vec![vec![
+            SqlValue::variable("@rid"),
+            SqlValue::from("@rid"),
+            SqlValue::from("routine_id('a','b')"),
+        ]];
+        let sql = create_import_statement(
+            "environ",
+            &keys,
+            &values,
+            &sqltypes,
+            &InsertLimits::default(),
+        )?;
+        assert_eq!(
+            sql[0].sql,
+            "INSERT INTO environ (rid, k, value) VALUES\n(@rid, ?, ?);"
+        );
+        assert_eq!(
+            sql[0].binds,
+            vec![
+                BindValue::Text("@rid".to_string()),
+                BindValue::Text("routine_id('a','b')".to_string())
+            ]
+        );
+        assert_eq!(
+            sql[0].render(),
+            "INSERT INTO environ (rid, k, value) VALUES\n(@rid, '@rid', 'routine_id(''a'',''b'')');"
+        );
+
+        // A string is no reference for an integer column
+        let values = vec![vec![
+            SqlValue::from("@rid"),
+            SqlValue::from("k"),
+            SqlValue::from("v"),
+        ]];
+        let result = create_import_statement(
+            "environ",
+            &keys,
+            &values,
+            &sqltypes,
+            &InsertLimits::default(),
+        );
+        assert!(result.is_err());
+        Ok(())
+    }
+}
@@ -15,7 +15,8 @@
 #[cfg(test)]
 mod tests {
     use crate::cmdline::CliArgs;
-    use crate::jobdata::create_sql::create_update_statement;
+    use crate::jobdata::create_sql::{create_update_statement, SqlValue};
+    use crate::jobdata::statement::BindValue;
     use crate::sqltypes::{read_sqltypes, SqlTypeHashMap};
     use anyhow::Result;
     use sqlx::{MySql, Pool};
@@ -37,23 +38,31 @@ mod tests {
             .expect("Failed to read sqltypes");
 
         let tuple = [
-            (
-                "rid".to_string(),
-                serde_yaml::Value::String("@rid".to_string()),
-            ),
+            ("rid".to_string(), SqlValue::variable("@rid")),
             (
                 "compiler".to_string(),
-                serde_yaml::Value::String("gcc-10".to_string()),
+                SqlValue::from(serde_yaml::Value::String("gcc-10".to_string())),
             ),
             (
                 "nodes".to_string(),
-                serde_yaml::Value::Number(serde_yaml::Number::from(32)),
+                SqlValue::from(serde_yaml::Value::Number(serde_yaml::Number::from(32))),
             ),
         ];
 
         let sql = create_update_statement("runs", &tuple, "rid = @rid", &sqltypes)?;
         assert_eq!(
-            sql,
+            sql.sql,
+            "UPDATE runs SET rid = @rid,\ncompiler = ?,\nnodes = ? WHERE rid = @rid;"
+        );
+        assert_eq!(
+            sql.binds,
+            vec![
+                BindValue::Text("gcc-10".to_string()),
+                BindValue::Number("32".to_string())
+            ]
+        );
+        assert_eq!(
+            sql.render(),
             "UPDATE runs SET rid = @rid,\ncompiler = 'gcc-10',\nnodes = 32 WHERE rid = @rid;"
         );
         Ok(())
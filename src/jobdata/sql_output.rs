@@ -0,0 +1,312 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cmdline::CliArgs;
+use crate::datetime::DateTime;
+use crate::jobdata::table_runs::foreign_keys::{
+    extract_start_date, extract_user_id, read_project_file, DEFAULT_CLUSTER,
+};
+use crate::jobdata::LmxSummary;
+use crate::sqltypes::{fingerprint_guard, schema_fingerprint, SqlTypeHashMap};
+use anyhow::{anyhow, Context, Result};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[cfg(test)]
+pub(crate) mod test_sql_output;
+
+/// Placeholders available in the template given with `--sql-file-template`
+pub const SQL_FILE_PLACEHOLDERS: [&str; 9] = [
+    "project",
+    "code",
+    "code_version",
+    "test_case",
+    "cluster",
+    "user",
+    "start_date",
+    "start_date_n",
+    "directory",
+];
+
+/// Expands the placeholders `{name}` of a SQL file template.
+///
+/// # Arguments
+/// * `template` - The template, e.g. `{project}/{code}_{test_case}/{start_date}.sql`
+/// * `value_of` - Returns the value of a placeholder, or `None` for unknown names
+///
+/// # Returns
+/// Returns the expanded template
+///
+/// # Errors
+/// Returns an error message for unknown placeholders or unbalanced braces
+///
+fn expand_template(
+    template: &str,
+    value_of: impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(format!(
+                "Unmatched '}}' in SQL file template '{}'",
+                template
+            ));
+        }
+        expanded.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| format!("Unmatched '{{' in SQL file template '{}'", template))?;
+        let name = &rest[start + 1..end];
+        let value = value_of(name).ok_or_else(|| {
+            format!(
+                "Unknown placeholder '{{{}}}' in SQL file template '{}', expected one of: {}",
+                name,
+                template,
+                SQL_FILE_PLACEHOLDERS.join(", ")
+            )
+        })?;
+        expanded.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Value parser for `--sql-file-template`, rejects templates with unknown placeholders.
+///
+/// # Arguments
+/// * `value` - The template as given on the command line
+///
+/// # Returns
+/// Returns the template unchanged
+///
+/// # Errors
+/// Returns an error message if the template cannot be expanded
+///
+pub fn parse_sql_file_template(value: &str) -> Result<String, String> {
+    expand_template(value, |name| {
+        SQL_FILE_PLACEHOLDERS
+            .contains(&name)
+            .then(|| name.to_string())
+    })?;
+    if value.trim().is_empty() {
+        return Err("SQL file template must not be empty".to_string());
+    }
+    Ok(value.to_string())
+}
+
+/// Makes a placeholder value safe to be used as a single path component.
+/// Characters other than ASCII letters, digits and `-_.+` are replaced by `_`.
+fn sanitize_path_component(value: &str) -> String {
+    let sanitized: String = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "-_.+".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match sanitized.as_str() {
+        "" | "." | ".." => "_".to_string(),
+        _ => sanitized,
+    }
+}
+
+/// Determines the SQL file of a single run from `args.sql_file_template`.
+/// Relative paths are resolved against the directory of the index file `args.sql_file`.
+///
+/// # Arguments
+/// * `template` - The SQL file template
+/// * `file_name` - Path to the LMX summary file of the run
+/// * `lmx_summary` - The parsed LMX summary of the run
+/// * `args` - Command line arguments, used for finding the project file
+///
+/// # Returns
+/// Returns the path of the SQL file for the run
+///
+/// # Errors
+/// Returns an error if the project file or the start date of the run cannot be read
+///
+pub fn run_sql_file(
+    template: &str,
+    file_name: &str,
+    lmx_summary: &LmxSummary,
+    args: &CliArgs,
+) -> Result<PathBuf> {
+    let foreign_keys = read_project_file(file_name, args)?;
+    let (start_date, start_date_n) = extract_start_date(lmx_summary)?;
+    let directory = Path::new(file_name)
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let values: HashMap<&str, String> = HashMap::from([
+        ("project", foreign_keys.project),
+        ("code", foreign_keys.code),
+        ("code_version", foreign_keys.code_version),
+        ("test_case", foreign_keys.test_case),
+        (
+            "cluster",
+            foreign_keys
+                .cluster
+                .unwrap_or_else(|| DEFAULT_CLUSTER.to_string()),
+        ),
+        ("user", extract_user_id(lmx_summary)),
+        ("start_date", start_date.to_string()),
+        ("start_date_n", start_date_n.to_string()),
+        ("directory", directory),
+    ]);
+    let expanded = expand_template(template, |name| {
+        values.get(name).map(|value| sanitize_path_component(value))
+    })
+    .map_err(anyhow::Error::msg)?;
+    let index_dir = Path::new(&args.sql_file)
+        .parent()
+        .unwrap_or_else(|| Path::new(""));
+    Ok(index_dir.join(expanded))
+}
+
+/// Writes the SQL of a single run to its own file and appends the file to the index
+/// file `args.sql_file` as `SOURCE` command. The caller must serialize concurrent calls,
+/// so that the index lists the files in load order. The index names the file by its
+/// absolute path, which the mariadb client resolves independent of its working directory.
+///
+/// # Arguments
+/// * `path` - The SQL file of the run as returned by `run_sql_file`
+/// * `content` - The SQL text of the run
+/// * `args` - Command line arguments with the index file
+///
+/// # Errors
+/// Returns an error if the file already exists, written for another run or by
+/// an earlier invocation, or if writing the files fails
+///
+pub fn write_run_sql_file(path: &Path, content: &str, args: &CliArgs) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+    }
+    // Never overwrite a run file, its SOURCE line is already part of an index
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::AlreadyExists {
+                anyhow!(
+                    "SQL file {} already exists, written for another run or by an earlier call",
+                    path.display()
+                )
+            } else {
+                anyhow::Error::new(e)
+                    .context(format!("Failed to create SQL file {}", path.display()))
+            }
+        })?;
+    file.write_all(content.as_bytes())
+        .with_context(|| format!("Failed to write SQL file {}", path.display()))?;
+
+    let listed = std::path::absolute(path)
+        .with_context(|| format!("Failed to resolve SQL file {}", path.display()))?;
+    let mut index = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&args.sql_file)
+        .with_context(|| format!("Failed to open index file {}", args.sql_file))?;
+    writeln!(index, "SOURCE {};", listed.display())?;
+    Ok(())
+}
+
+/// Formats a point in time as UTC date and time "YYYY-MM-DD HH:MM:SS UTC"
+fn format_utc(time: SystemTime) -> String {
+    format!("{} UTC", DateTime::from_system_time(time))
+}
+
+/// Creates the header of a SQL file: comments recording the lmx2db version,
+/// the creation time, the source directories and the schema fingerprint,
+/// followed by the guard stopping the load into a database with different column types.
+///
+/// # Arguments
+/// * `sqltypes` - The column types the SQL statements are checked against
+/// * `sources` - The input directories and files
+/// * `created` - The creation time of the SQL file
+///
+/// # Returns
+/// Returns the lines of the header
+///
+pub fn sql_file_header(
+    sqltypes: &SqlTypeHashMap,
+    sources: &[String],
+    created: SystemTime,
+) -> Vec<String> {
+    let column_count: usize = sqltypes.values().map(|columns| columns.len()).sum();
+    let mut header = vec![
+        format!("-- Created by lmx2db {}", env!("CARGO_PKG_VERSION")),
+        format!("-- Created at: {}", format_utc(created)),
+        format!("-- Source directories: {}", sources.join(", ")),
+        format!(
+            "-- Schema fingerprint: {} ({} tables, {} columns)",
+            schema_fingerprint(sqltypes),
+            sqltypes.len(),
+            column_count
+        ),
+    ];
+    header.extend(fingerprint_guard(sqltypes));
+    header
+}
+
+/// Writes the header to the SQL file `args.sql_file` if the file is new or empty.
+/// A file that already has content is appended to without a second header.
+///
+/// # Arguments
+/// * `sqltypes` - The column types the SQL statements are checked against
+/// * `sources` - The input directories and files
+/// * `args` - Command line arguments with the SQL file
+///
+/// # Errors
+/// Returns an error if the SQL file cannot be written
+///
+pub fn write_sql_file_header(
+    sqltypes: &SqlTypeHashMap,
+    sources: &[String],
+    args: &CliArgs,
+) -> Result<()> {
+    let is_empty = std::fs::metadata(&args.sql_file).map_or(true, |m| m.len() == 0);
+    if !is_empty {
+        if args.verbose || args.dry_run {
+            println!("Appending to {} without a new header", args.sql_file);
+        }
+        return Ok(());
+    }
+    let header = sql_file_header(sqltypes, sources, SystemTime::now());
+    if args.dry_run {
+        println!("Dry run mode - not writing to file. Header would be:");
+        for line in &header {
+            println!("Header line: {}", line);
+        }
+        return Ok(());
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&args.sql_file)
+        .with_context(|| format!("Failed to open SQL file {}", args.sql_file))?;
+    file.write_all((header.join("\n") + "\n").as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,198 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::CliArgs;
+    use crate::globbing::find_lmx_summary_files;
+    use crate::jobdata::read_lmx_summary;
+    use crate::jobdata::sql_output::{
+        format_utc, parse_sql_file_template, run_sql_file, sanitize_path_component,
+        sql_file_header, write_sql_file_header,
+    };
+    use crate::sqltypes::{read_sqltypes, schema_fingerprint};
+    use crate::workers::process_lmx_files;
+    use anyhow::Result;
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::time::{Duration, UNIX_EPOCH};
+    use tempfile::{NamedTempFile, TempDir};
+
+    #[test]
+    fn test_parse_sql_file_template() {
+        assert!(parse_sql_file_template("{project}/{code}_{test_case}/{start_date}.sql").is_ok());
+        assert!(parse_sql_file_template("{cluster}/{user}/{directory}_{start_date_n}.sql").is_ok());
+        assert!(parse_sql_file_template("runs/{code_version}.sql").is_ok());
+        let err = parse_sql_file_template("{host}.sql").unwrap_err();
+        assert!(err.contains("Unknown placeholder '{host}'"), "{}", err);
+        assert!(parse_sql_file_template("{project.sql").is_err());
+        assert!(parse_sql_file_template("project}.sql").is_err());
+        assert!(parse_sql_file_template("").is_err());
+    }
+
+    #[test]
+    fn test_sanitize_path_component() {
+        assert_eq!(sanitize_path_component("3.0.2"), "3.0.2");
+        assert_eq!(sanitize_path_component("C. Pospiech"), "C._Pospiech");
+        assert_eq!(sanitize_path_component("../etc"), ".._etc");
+        assert_eq!(sanitize_path_component(".."), "_");
+        assert_eq!(sanitize_path_component(""), "_");
+    }
+
+    #[test]
+    fn test_run_sql_file() -> Result<()> {
+        let args = CliArgs {
+            sql_file: "out/index.sql".to_string(),
+            project_file: "project.yml".to_string(),
+            ..Default::default()
+        };
+        let file_name = "tests/data/NAMD/run_0001/LMX_summary.225250.0.yml";
+        let lmx_summary = read_lmx_summary(file_name)?;
+        let path = run_sql_file(
+            "{project}/{code}_{test_case}/{directory}.sql",
+            file_name,
+            &lmx_summary,
+            &args,
+        )?;
+        assert_eq!(path, Path::new("out/4paper_2025/NAMD_STMV/run_0001.sql"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_one_sql_file_per_run() -> Result<()> {
+        // Keep temp_dir in scope to prevent automatic deletion until test completes
+        let temp_dir = TempDir::new()?;
+        let index_file = temp_dir.path().join("index.sql");
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            sql_file: index_file.to_string_lossy().to_string(),
+            sql_file_template: Some("{project}/{code}_{test_case}/{start_date}.sql".to_string()),
+            project_file: "project.yml".to_string(),
+            settings_file: "settings.yml".to_string(),
+            module_file: "modules.yml".to_string(),
+            jobs: 2,
+            ..Default::default()
+        };
+        let list_of_files =
+            find_lmx_summary_files(&vec!["tests/data/NAMD".to_string()], &args)?.files;
+        assert_eq!(list_of_files.len(), 3);
+        let sqltypes = read_sqltypes(None, &args).await?;
+
+        let results = process_lmx_files(
+            list_of_files.clone(),
+            &None,
+            Arc::new(sqltypes.clone()),
+            Arc::new(args.clone()),
+        )
+        .await?;
+        for (file_name, result) in &results {
+            assert!(result.is_ok(), "Processing {} failed", file_name);
+        }
+
+        // The index lists one file per run, each holding a complete transaction
+        let index = std::fs::read_to_string(&index_file)?;
+        let listed: Vec<&str> = index.lines().collect();
+        assert_eq!(listed.len(), 3);
+        let run_dir = std::path::absolute(temp_dir.path().join("4paper_2025/NAMD_STMV"))?;
+        for line in listed {
+            let start_date = line
+                .strip_prefix(&format!("SOURCE {}/", run_dir.display()))
+                .and_then(|l| l.strip_suffix(".sql;"))
+                .unwrap_or_else(|| panic!("Unexpected index line: {}", line));
+            assert!(start_date.parse::<i64>().is_ok(), "{}", line);
+            let content = std::fs::read_to_string(&line["SOURCE ".len()..line.len() - 1])?;
+            assert!(content.starts_with("START TRANSACTION;\n"));
+            assert!(content.ends_with("COMMIT;\n"));
+            assert_eq!(content.matches("-- Queries for file ").count(), 1);
+        }
+
+        // A second call refuses to overwrite the files of the runs
+        let results =
+            process_lmx_files(list_of_files, &None, Arc::new(sqltypes), Arc::new(args)).await?;
+        for (file_name, result) in &results {
+            let error = result.as_ref().expect_err(file_name).to_string();
+            assert!(
+                error.ends_with("written for another run or by an earlier call"),
+                "{}",
+                error
+            );
+        }
+        assert_eq!(std::fs::read_to_string(&index_file)?, index);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(UNIX_EPOCH), "1970-01-01 00:00:00 UTC");
+        assert_eq!(
+            format_utc(UNIX_EPOCH + Duration::from_secs(951827400)),
+            "2000-02-29 12:30:00 UTC"
+        );
+        assert_eq!(
+            format_utc(UNIX_EPOCH + Duration::from_secs(1735689599)),
+            "2024-12-31 23:59:59 UTC"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sql_file_header() -> Result<()> {
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            ..Default::default()
+        };
+        let sqltypes = read_sqltypes(None, &args).await?;
+        let header = sql_file_header(
+            &sqltypes,
+            &["tests/data/NAMD".to_string()],
+            UNIX_EPOCH + Duration::from_secs(1735689599),
+        );
+        assert_eq!(
+            header[0],
+            format!("-- Created by lmx2db {}", env!("CARGO_PKG_VERSION"))
+        );
+        assert_eq!(header[1], "-- Created at: 2024-12-31 23:59:59 UTC");
+        assert_eq!(header[2], "-- Source directories: tests/data/NAMD");
+        assert!(header[3].starts_with(&format!(
+            "-- Schema fingerprint: {} ({} tables, ",
+            schema_fingerprint(&sqltypes),
+            sqltypes.len()
+        )));
+        assert!(header[4..]
+            .iter()
+            .any(|line| line.contains("SIGNAL SQLSTATE")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_sql_file_header_once() -> Result<()> {
+        // Keep temp_file in scope to prevent automatic deletion until test completes
+        let temp_file = NamedTempFile::new()?;
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            sql_file: temp_file.path().to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let sqltypes = read_sqltypes(None, &args).await?;
+        let sources = vec!["tests/data/NAMD".to_string()];
+        write_sql_file_header(&sqltypes, &sources, &args)?;
+        let content = std::fs::read_to_string(temp_file.path())?;
+        assert!(content.starts_with("-- Created by lmx2db "));
+        assert!(content.ends_with("DELIMITER ;\n"));
+
+        // A file with content does not get a second header
+        write_sql_file_header(&sqltypes, &sources, &args)?;
+        assert_eq!(std::fs::read_to_string(temp_file.path())?, content);
+        Ok(())
+    }
+}
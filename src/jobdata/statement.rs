@@ -0,0 +1,210 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sqlx::mysql::MySqlArguments;
+use sqlx::MySql;
+
+#[cfg(test)]
+pub(crate) mod test_statement;
+
+/// Quotes a string as SQL string literal. Quotes, backslashes
+/// and NUL characters inside the string are escaped.
+pub fn quote_string(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('\'');
+    for c in value.chars() {
+        match c {
+            '\'' => literal.push_str("''"),
+            '\\' => literal.push_str("\\\\"),
+            '\0' => literal.push_str("\\0"),
+            _ => literal.push(c),
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
+/// Returns the hex digits of `value` for a `BindValue::Hex`. A value with
+/// an odd number of digits is padded with a leading zero, since the hex
+/// literal must describe whole bytes. Returns `None` if the value is not hexadecimal.
+pub fn hex_digits(value: &str) -> Option<String> {
+    if !value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let padding = if value.len() % 2 == 1 { "0" } else { "" };
+    Some(format!("{}{}", padding, value))
+}
+
+/// Decodes hex digits as returned by `hex_digits` into bytes
+fn decode_hex(digits: &str) -> Vec<u8> {
+    digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap_or("00"), 16).unwrap_or(0))
+        .collect()
+}
+
+/// A value bound to a `?` placeholder of a `Statement`
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindValue {
+    Int(i64),
+    Text(String),
+    /// A number as given in the input data, rendered as is. Integers are
+    /// bound as such, other numbers as text, so that the database converts
+    /// them to the column type without loss of precision.
+    Number(String),
+    /// Hex digits of a binary value, an even number of them, rendered as
+    /// hex literal `X'...'` and bound as bytes
+    Hex(String),
+}
+
+impl From<i64> for BindValue {
+    fn from(value: i64) -> Self {
+        BindValue::Int(value)
+    }
+}
+
+impl From<&str> for BindValue {
+    fn from(value: &str) -> Self {
+        BindValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for BindValue {
+    fn from(value: String) -> Self {
+        BindValue::Text(value)
+    }
+}
+
+impl BindValue {
+    /// Renders the value as a SQL literal, strings are quoted with `quote_string`.
+    pub fn to_literal(&self) -> String {
+        match self {
+            BindValue::Int(value) => value.to_string(),
+            BindValue::Text(value) => quote_string(value),
+            BindValue::Number(value) => value.clone(),
+            BindValue::Hex(digits) => format!("X'{}'", digits),
+        }
+    }
+}
+
+/// Rows inserted by a statement created by `create_import_statement`
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsertedRows {
+    pub table: String,
+    /// Columns given a value by the statement
+    pub columns: Vec<String>,
+    /// Number of inserted rows
+    pub rows: usize,
+}
+
+/// A single SQL statement with `?` placeholders and the values bound to them.
+/// With a database connection, the values are passed as bind parameters,
+/// so they never become part of the SQL text. For the SQL file, the statement
+/// is rendered with the values as escaped literals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    pub sql: String,
+    pub binds: Vec<BindValue>,
+    /// The rows inserted by the statement, `None` if it is no INSERT statement
+    pub inserted: Option<InsertedRows>,
+}
+
+impl Statement {
+    /// Creates a statement without bound values
+    pub fn new(sql: impl Into<String>) -> Self {
+        Statement::with_binds(sql, Vec::new())
+    }
+
+    /// Creates a statement with the values bound to its placeholders
+    pub fn with_binds(sql: impl Into<String>, binds: Vec<BindValue>) -> Self {
+        Statement {
+            sql: sql.into(),
+            binds,
+            inserted: None,
+        }
+    }
+
+    /// Records the rows inserted by the statement
+    pub fn inserting(mut self, inserted: InsertedRows) -> Self {
+        self.inserted = Some(inserted);
+        self
+    }
+
+    /// Binds the next placeholder of the statement to `value`
+    pub fn bind(mut self, value: impl Into<BindValue>) -> Self {
+        self.binds.push(value.into());
+        self
+    }
+
+    /// Renders the statement as SQL text with all placeholders replaced by
+    /// the escaped literals of their values. Question marks inside quoted
+    /// strings or backquoted identifiers of the SQL text are not placeholders.
+    pub fn render(&self) -> String {
+        if self.binds.is_empty() {
+            return self.sql.clone();
+        }
+        let mut rendered = String::with_capacity(self.sql.len());
+        let mut values = self.binds.iter();
+        let mut quote: Option<char> = None;
+        for c in self.sql.chars() {
+            match (quote, c) {
+                (None, '?') => match values.next() {
+                    Some(value) => rendered.push_str(&value.to_literal()),
+                    None => rendered.push(c),
+                },
+                (None, '\'' | '"' | '`') => {
+                    quote = Some(c);
+                    rendered.push(c);
+                }
+                (Some(q), _) if q == c => {
+                    quote = None;
+                    rendered.push(c);
+                }
+                _ => rendered.push(c),
+            }
+        }
+        rendered
+    }
+
+    /// Creates the sqlx query with all values bound, ready to be executed
+    pub fn to_query(&self) -> sqlx::query::Query<'_, MySql, MySqlArguments> {
+        let mut query = sqlx::query(&self.sql);
+        for value in &self.binds {
+            query = match value {
+                BindValue::Int(value) => query.bind(*value),
+                BindValue::Text(value) => query.bind(value.as_str()),
+                BindValue::Number(value) => match (value.parse::<i64>(), value.parse::<u64>()) {
+                    (Ok(int), _) => query.bind(int),
+                    (_, Ok(uint)) => query.bind(uint),
+                    _ => query.bind(value.as_str()),
+                },
+                BindValue::Hex(digits) => query.bind(decode_hex(digits)),
+            };
+        }
+        query
+    }
+}
+
+impl From<String> for Statement {
+    fn from(sql: String) -> Self {
+        Statement::new(sql)
+    }
+}
+
+impl From<&str> for Statement {
+    fn from(sql: &str) -> Self {
+        Statement::new(sql)
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::jobdata::statement::{BindValue, Statement};
+
+    #[test]
+    fn test_render_escapes_values() {
+        let statement = Statement::new("SET @ccid = customer_case_id(?, ?, ?, ?, ?);")
+            .bind("O'Brien's project")
+            .bind("C:\\codes\\namd")
+            .bind("nul\0byte")
+            .bind("'); DROP TABLE runs; --")
+            .bind(1_i64);
+        assert_eq!(
+            statement.render(),
+            "SET @ccid = customer_case_id('O''Brien''s project', 'C:\\\\codes\\\\namd', \
+             'nul\\0byte', '''); DROP TABLE runs; --', 1);"
+        );
+        assert_eq!(statement.binds[4], BindValue::Int(1));
+    }
+
+    #[test]
+    fn test_render_skips_quoted_question_marks() {
+        let statement = Statement::new("SELECT '?', \"?\", `?`, ?;").bind("value?");
+        assert_eq!(statement.render(), "SELECT '?', \"?\", `?`, 'value?';");
+
+        // Statements without values are rendered unchanged
+        let statement = Statement::from("SELECT concat (\"rid = \", @rid) as 'Run ?';");
+        assert_eq!(statement.render(), statement.sql);
+    }
+}
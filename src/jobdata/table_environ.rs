@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use crate::cmdline::CliArgs;
-use crate::jobdata::create_sql::create_import_statement;
+use crate::jobdata::create_sql::{create_import_statement, InsertLimits, SqlValue};
+use crate::jobdata::statement::Statement;
 use crate::jobdata::LmxSummary;
 use crate::sqltypes::SqlTypeHashMap;
 use anyhow::Result;
@@ -41,10 +42,10 @@ pub fn import_into_environ_table(
     lmx_summary: &LmxSummary,
     sqltypes: &SqlTypeHashMap,
     args: &CliArgs,
-) -> Result<Vec<String>> {
+) -> Result<Vec<Statement>> {
     let key_list: Vec<String> = vec!["rid".to_string(), "k".to_string(), "value".to_string()];
-    let mut value_list: Vec<Vec<serde_yaml::Value>> = Vec::new();
-    let mut query_list: Vec<String> = Vec::new();
+    let mut value_list: Vec<Vec<SqlValue>> = Vec::new();
+    let mut query_list: Vec<Statement> = Vec::new();
 
     // Check early if 'environ' table exists in sqltypes to fail fast
     if !sqltypes.contains_key("environ") {
@@ -73,16 +74,16 @@ pub fn import_into_environ_table(
 
                 let str_vec = str_vec?;
                 value_list.push(vec![
-                    serde_yaml::Value::String("@rid".to_string()),
-                    serde_yaml::Value::String(key.to_string()),
-                    serde_yaml::Value::String(str_vec.join("")),
+                    SqlValue::variable("@rid"),
+                    SqlValue::from(key.as_str()),
+                    SqlValue::from(str_vec.join("")),
                 ]);
             }
             serde_yaml::Value::String(s) => {
                 value_list.push(vec![
-                    serde_yaml::Value::String("@rid".to_string()),
-                    serde_yaml::Value::String(key.to_string()),
-                    serde_yaml::Value::String(s.clone()),
+                    SqlValue::variable("@rid"),
+                    SqlValue::from(key.as_str()),
+                    SqlValue::from(s.as_str()),
                 ]);
             }
             _ => {
@@ -96,12 +97,13 @@ pub fn import_into_environ_table(
     }
 
     if !value_list.is_empty() {
-        query_list.push("-- Inserting into environ table;".to_string());
-        query_list.push(create_import_statement(
+        query_list.push("-- Inserting into environ table;".into());
+        query_list.extend(create_import_statement(
             "environ",
             &key_list,
             &value_list,
             sqltypes,
+            &InsertLimits::from_args(args),
         )?);
     }
 
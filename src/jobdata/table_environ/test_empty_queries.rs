@@ -15,8 +15,9 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        cmdline::CliArgs, jobdata::table_environ::import_into_environ_table,
-        sqltypes::read_sqltypes,
+        cmdline::CliArgs,
+        jobdata::table_environ::import_into_environ_table,
+        sqltypes::{read_sqltypes, SqlTypeHashMap},
     };
     use anyhow::Result;
     use sqlx::MySql;
@@ -36,7 +37,7 @@ mod tests {
         };
 
         // Create an empty sqltypes map (no 'environ' table)
-        let sqltypes: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let sqltypes: SqlTypeHashMap = HashMap::new();
 
         let manifest_dir = env!("CARGO_MANIFEST_DIR");
         let lmx_file = std::path::Path::new(manifest_dir)
@@ -14,6 +14,7 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::jobdata::statement::Statement;
     use crate::{
         cmdline::CliArgs, jobdata::table_environ::import_into_environ_table,
         sqltypes::read_sqltypes,
@@ -51,7 +52,10 @@ mod tests {
             serde_yaml::from_str(&std::fs::read_to_string(&lmx_file)?)?;
 
         // Call import_into_environ_table
-        let queries = import_into_environ_table(&lmx_summary, &sqltypes, &args)?;
+        let queries: Vec<String> = import_into_environ_table(&lmx_summary, &sqltypes, &args)?
+            .iter()
+            .map(Statement::render)
+            .collect();
 
         // Should return a comment line and exactly one query
         assert_eq!(
@@ -121,7 +125,10 @@ mod tests {
         lmx_summary.insert("environ".to_string(), environ_section);
 
         // Call import_into_environ_table
-        let queries = import_into_environ_table(&lmx_summary, &sqltypes, &args)?;
+        let queries: Vec<String> = import_into_environ_table(&lmx_summary, &sqltypes, &args)?
+            .iter()
+            .map(Statement::render)
+            .collect();
 
         // Should return a comment line and exactly one query
         assert_eq!(
@@ -183,7 +190,10 @@ mod tests {
             serde_yaml::from_str(&std::fs::read_to_string(&lmx_file)?)?;
 
         // Call import_into_environ_table
-        let queries = import_into_environ_table(&lmx_summary, &sqltypes, &args)?;
+        let queries: Vec<String> = import_into_environ_table(&lmx_summary, &sqltypes, &args)?
+            .iter()
+            .map(Statement::render)
+            .collect();
 
         // Should return a comment line and exactly one query
         assert_eq!(
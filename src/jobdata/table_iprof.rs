@@ -15,7 +15,8 @@
 use crate::cmdline::CliArgs;
 use crate::globbing::find_lmx_type_files;
 use crate::jobdata::base_data::extract_base_data_key;
-use crate::jobdata::create_sql::create_import_statement;
+use crate::jobdata::create_sql::{create_import_statement, InsertLimits, SqlValue};
+use crate::jobdata::statement::Statement;
 use crate::jobdata::{read_lmx_summary, LmxSummary};
 use crate::sqltypes::SqlTypeHashMap;
 use anyhow::{anyhow, bail, Result};
@@ -177,7 +178,7 @@ pub fn extract_full_function_name(
 /// extracts relevant data using `extract_iprof_ticks`, and then generates SQL insert
 /// statements based on the provided `sqltypes` schema mapping. The generated SQL queries
 /// are returned as a vector of strings.
-/// The function returns without error and an empty `Vec<String>` if no interval timer
+/// The function returns without error and an empty `Vec<Statement>` if no interval timer
 /// profile data is found by `find_lmx_type_files`.
 ///
 /// # Arguments
@@ -186,7 +187,7 @@ pub fn extract_full_function_name(
 /// * `args` - Command line arguments including verbosity and dry-run
 ///
 /// # Returns
-/// `Result<Vec<String>>` - A Result containing a vector of SQL insert statements.
+/// `Result<Vec<Statement>>` - A Result containing a vector of SQL insert statements.
 ///
 /// # Errors
 /// * Returns an error if any of the found interval timer profile files cannot be read or parsed.
@@ -195,14 +196,15 @@ pub fn import_into_iprof_table(
     file_name: &str,
     sqltypes: &SqlTypeHashMap,
     args: &CliArgs,
-) -> Result<Vec<String>> {
-    let mut query_list: Vec<String> = Vec::new();
+) -> Result<Vec<Statement>> {
+    let mut query_list: Vec<Statement> = Vec::new();
 
     // Check early if 'iprof' table exists in sqltypes to fail fast
     if !sqltypes.contains_key("iprof") {
         return Ok(query_list);
     }
 
+    let limits = InsertLimits::from_args(args);
     let iprof_files = find_lmx_type_files(file_name, "itimer")?;
     if iprof_files.is_empty() {
         // No interval timer profile files found, return empty query list without error
@@ -233,13 +235,16 @@ pub fn import_into_iprof_table(
             "ticks".to_string(),
         ];
         let values = &[vec![
-            serde_yaml::Value::String("@rid".to_string()),
-            serde_yaml::Value::Number(my_mpi_rank.into()),
-            serde_yaml::Value::String(format!("routine_id('{}','{}')", total, total)),
-            serde_yaml::Value::Number(total_ticks.into()),
+            SqlValue::variable("@rid"),
+            SqlValue::Literal(serde_yaml::Value::Number(my_mpi_rank.into())),
+            SqlValue::function(
+                "routine_id",
+                vec![SqlValue::from(total), SqlValue::from(total)],
+            ),
+            SqlValue::Literal(serde_yaml::Value::Number(total_ticks.into())),
         ]];
-        let sql_query = create_import_statement(table_name, keys, values, sqltypes)?;
-        query_list.push(sql_query);
+        let sql_query = create_import_statement(table_name, keys, values, sqltypes, &limits)?;
+        query_list.extend(sql_query);
         // We check whether iprof_data contains a section "library_histogram" with the expected
         // structure of a non-empty HashMap<String, serde_yaml::Value>, and if so, we
         // loop through its keys and values to create a second SQL import statement
@@ -265,25 +270,22 @@ pub fn import_into_iprof_table(
             continue;
         }
         // Process library_histogram data
-        let mut value_list: Vec<Vec<serde_yaml::Value>> = Vec::new();
+        let mut value_list: Vec<Vec<SqlValue>> = Vec::new();
         for (lib_short_name, lib_data) in histogram {
             let lib_full_name = extract_full_library_name(&iprof_data, lib_short_name)?;
-            // Escape single quotes in names before embedding into SQL string literals
-            let lib_full_name_escaped = lib_full_name.replace('\'', "''");
-            let total_escaped = total.replace('\'', "''");
             let lib_ticks = extract_iprof_ticks(lib_data)?;
             value_list.push(vec![
-                serde_yaml::Value::String("@rid".to_string()),
-                serde_yaml::Value::Number(my_mpi_rank.into()),
-                serde_yaml::Value::String(format!(
-                    "routine_id('{}','{}')",
-                    lib_full_name_escaped, total_escaped
-                )),
-                serde_yaml::Value::Number(lib_ticks.into()),
+                SqlValue::variable("@rid"),
+                SqlValue::Literal(serde_yaml::Value::Number(my_mpi_rank.into())),
+                SqlValue::function(
+                    "routine_id",
+                    vec![SqlValue::from(lib_full_name), SqlValue::from(total)],
+                ),
+                SqlValue::Literal(serde_yaml::Value::Number(lib_ticks.into())),
             ]);
         }
-        let sql_query = create_import_statement(table_name, keys, &value_list, sqltypes)?;
-        query_list.push(sql_query);
+        let sql_query = create_import_statement(table_name, keys, &value_list, sqltypes, &limits)?;
+        query_list.extend(sql_query);
         // We check whether iprof_data contains a section "flat_profile" with the expected
         // structure of a non-empty HashMap<String, HashMap<String, serde_yaml::Value>>, and if so, we
         // loop through its keys and values to create further SQL import statements for the iprof table
@@ -309,7 +311,7 @@ pub fn import_into_iprof_table(
             continue;
         }
         // Process flat_profile data
-        let mut value_list: Vec<Vec<serde_yaml::Value>> = Vec::new();
+        let mut value_list: Vec<Vec<SqlValue>> = Vec::new();
         for (lib_short_name, func_table) in flat_profile {
             let lib_full_name = extract_full_library_name(&iprof_data, lib_short_name)?;
             let func_table_map = match func_table.as_mapping() {
@@ -338,19 +340,21 @@ pub fn import_into_iprof_table(
                 )?;
                 let func_ticks = extract_iprof_ticks(func_data)?;
                 value_list.push(vec![
-                    serde_yaml::Value::String("@rid".to_string()),
-                    serde_yaml::Value::Number(my_mpi_rank.into()),
-                    serde_yaml::Value::String(format!(
-                        "routine_id('{}','{}')",
-                        lib_full_name.replace('\'', "''"),
-                        func_full_name.replace('\'', "''"),
-                    )),
-                    serde_yaml::Value::Number(func_ticks.into()),
+                    SqlValue::variable("@rid"),
+                    SqlValue::Literal(serde_yaml::Value::Number(my_mpi_rank.into())),
+                    SqlValue::function(
+                        "routine_id",
+                        vec![
+                            SqlValue::from(lib_full_name.as_str()),
+                            SqlValue::from(func_full_name),
+                        ],
+                    ),
+                    SqlValue::Literal(serde_yaml::Value::Number(func_ticks.into())),
                 ]);
             }
         }
-        let sql_query = create_import_statement(table_name, keys, &value_list, sqltypes)?;
-        query_list.push(sql_query);
+        let sql_query = create_import_statement(table_name, keys, &value_list, sqltypes, &limits)?;
+        query_list.extend(sql_query);
     }
 
     Ok(query_list)
@@ -14,8 +14,11 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::jobdata::statement::Statement;
     use crate::{
-        cmdline::CliArgs, jobdata::table_iprof::import_into_iprof_table, sqltypes::read_sqltypes,
+        cmdline::CliArgs,
+        jobdata::table_iprof::import_into_iprof_table,
+        sqltypes::{read_sqltypes, SqlTypeHashMap},
     };
     use anyhow::Result;
     use sqlx::MySql;
@@ -44,10 +47,14 @@ mod tests {
         fs::write(&temp_file, "base_data:\n  my_MPI_rank: 0\n")?;
 
         // Create an empty sqltypes map
-        let sqltypes: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let sqltypes: SqlTypeHashMap = HashMap::new();
 
         // Call import_into_iprof_table with a file that has no corresponding itimer files
-        let queries = import_into_iprof_table(temp_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_iprof_table(temp_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return empty vector when no itimer files are found
         assert!(
@@ -98,7 +105,11 @@ library_histogram:
         fs::write(&iprof_file, iprof_content)?;
 
         // Call import_into_iprof_table
-        let queries = import_into_iprof_table(summary_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_iprof_table(summary_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return empty vector when total_ticks is zero
         assert!(
@@ -149,7 +160,11 @@ library_names:
         fs::write(&iprof_file, iprof_content)?;
 
         // Call import_into_iprof_table
-        let queries = import_into_iprof_table(summary_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_iprof_table(summary_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return only the total ticks query, not library histogram queries
         // One query for the total ticks
@@ -209,7 +224,11 @@ library_histogram:
         fs::write(&iprof_file, iprof_content)?;
 
         // Call import_into_iprof_table
-        let queries = import_into_iprof_table(summary_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_iprof_table(summary_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return total ticks query and library histogram query, but not flat_profile queries
         // Two queries: total ticks + library histogram
@@ -271,7 +290,11 @@ library_histogram: {}
         fs::write(&iprof_file, iprof_content)?;
 
         // Call import_into_iprof_table
-        let queries = import_into_iprof_table(summary_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_iprof_table(summary_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return only the total ticks query
         assert_eq!(
@@ -324,7 +347,11 @@ flat_profile: {}
         fs::write(&iprof_file, iprof_content)?;
 
         // Call import_into_iprof_table
-        let queries = import_into_iprof_table(summary_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_iprof_table(summary_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return total ticks and library histogram queries
         assert_eq!(
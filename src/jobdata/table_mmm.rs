@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use crate::cmdline::CliArgs;
-use crate::jobdata::create_sql::create_import_statement;
+use crate::jobdata::create_sql::{create_import_statement, InsertLimits, SqlValue};
+use crate::jobdata::statement::Statement;
 use crate::jobdata::LmxSummary;
 use crate::sqltypes::SqlTypeHashMap;
 use anyhow::Result;
@@ -21,10 +22,12 @@ use anyhow::Result;
 /// Helper function to check whether a parameter of type Option<serde_yaml::Value>
 /// is a sequence (array) and checks whether the second element in that sequence is
 /// a non-zero float.
-/// If the parameter is None, return None.
+/// If the parameter is None or null, return None.
 /// If the parameter is not a sequence or the second element is not a float,
 /// return an error.
 /// If the second element is a float with value zero, return None.
+/// If the second element is null, the two elements are returned as well,
+/// so that the columns are set to NULL.
 /// Otherwise, return a Vec<serde_yaml::Value> containing the first two
 /// elements of the sequence.
 ///
@@ -33,14 +36,18 @@ use anyhow::Result;
 ///
 /// # Returns
 /// * Ok(Some(Vec<serde_yaml::Value>)) if the second element is a non-zero float
-/// * Ok(None) if the parameter is None or the second element is zero
+/// * Ok(Some(Vec<serde_yaml::Value>)) if the second element is null
+/// * Ok(None) if the parameter is None or null or the second element is zero
 /// * Err(anyhow::Error) if the parameter is not a sequence or the second element is not a float
 fn parse_optional_float_sequence(
     param: &Option<serde_yaml::Value>,
 ) -> Result<Option<Vec<serde_yaml::Value>>> {
-    if let Some(value) = param {
+    if let Some(value) = param.as_ref().filter(|value| !value.is_null()) {
         if let serde_yaml::Value::Sequence(seq) = value {
             if seq.len() >= 2 {
+                if seq[1].is_null() {
+                    return Ok(Some(seq[..2].to_vec()));
+                }
                 if let serde_yaml::Value::Number(num) = &seq[1] {
                     if let Some(f) = num.as_f64() {
                         if f != 0.0 {
@@ -113,8 +120,8 @@ pub fn import_into_mmm_table(
     lmx_summary: &LmxSummary,
     sqltypes: &SqlTypeHashMap,
     args: &CliArgs,
-) -> Result<Vec<String>> {
-    let mut queries = Vec::new();
+) -> Result<Vec<Statement>> {
+    let mut queries: Vec<Statement> = Vec::new();
 
     // Check early if 'mmm' table exists in sqltypes to fail fast
     if !sqltypes.contains_key("mmm") {
@@ -130,7 +137,7 @@ pub fn import_into_mmm_table(
     }
     let mmm_section = mmm_section.unwrap();
     let mut columns = vec!["rid".to_string()];
-    let mut values = vec![serde_yaml::Value::String("@rid".to_string())];
+    let mut values = vec![SqlValue::variable("@rid")];
 
     let mappings = vec![
         ("min_comm", ("mintask", "mincomm")),
@@ -150,20 +157,21 @@ pub fn import_into_mmm_table(
     for (key, (col1, col2)) in mappings {
         let param = mmm_section.get(key);
         let parsed = parse_optional_float_sequence(&param.cloned())?;
-        if let Some(mut values_vec) = parsed {
+        if let Some(values_vec) = parsed {
             columns.push(col1.to_string());
             columns.push(col2.to_string());
-            values.append(&mut values_vec);
+            values.extend(values_vec.into_iter().map(SqlValue::from));
         }
     }
 
     if values.len() > 1 {
-        queries.push("-- Inserting into mmm table;".to_string());
-        queries.push(create_import_statement(
+        queries.push("-- Inserting into mmm table;".into());
+        queries.extend(create_import_statement(
             "mmm",
             &columns,
             &[values],
             sqltypes,
+            &InsertLimits::from_args(args),
         )?);
     }
 
@@ -15,7 +15,8 @@
 use crate::cmdline::CliArgs;
 use crate::globbing::find_lmx_type_files;
 use crate::jobdata::base_data::extract_base_data_key;
-use crate::jobdata::create_sql::create_import_statement;
+use crate::jobdata::create_sql::{create_import_statement, InsertLimits, SqlValue};
+use crate::jobdata::statement::Statement;
 use crate::jobdata::{read_lmx_summary, LmxSummary};
 use crate::sqltypes::SqlTypeHashMap;
 use anyhow::{bail, Result};
@@ -52,21 +53,21 @@ pub fn extract_vector_from_serde_yaml(value: &serde_yaml::Value) -> Result<Vec<s
 /// extracts the MPI rank using the `extract_base_data_key` function, and then
 /// parses the section denoted by the provided `section_key` to extract
 /// the relevant data for that MPI rank. It returns these data as
-/// Vec<Vec<serde_yaml::Value>>.
+/// Vec<Vec<SqlValue>>, with the MPI call referenced by stored function mpi_call_id().
 ///
 /// # Arguments
 /// * `mpi_profile` - A reference to the LMX summary data structure.
 /// * `section_key` - The key in the LMX summary that contains the MPI data to be extracted.
 ///
-/// Returns a vector of vectors of serde_yaml::Value containing the extracted MPI data.
+/// Returns a vector of vectors of SqlValue containing the extracted MPI data.
 /// If the section key is not found or if the data cannot be properly extracted, an error
 /// is returned.
 pub fn extract_mpi_data_from_mpi_profile(
     mpi_profile: &LmxSummary,
     section_key: &str,
-) -> Result<Vec<Vec<serde_yaml::Value>>> {
+) -> Result<Vec<Vec<SqlValue>>> {
     let mpi_rank = extract_base_data_key(mpi_profile, "my_MPI_rank")?;
-    let mut result: Vec<Vec<serde_yaml::Value>> = Vec::new();
+    let mut result: Vec<Vec<SqlValue>> = Vec::new();
     let is_detail = section_key.contains("detail");
 
     if let Some(mpi_profiles) = mpi_profile.get(section_key) {
@@ -75,10 +76,12 @@ pub fn extract_mpi_data_from_mpi_profile(
         }
 
         for (key, value) in mpi_profiles.iter() {
-            let mut row: Vec<serde_yaml::Value> = vec![
-                serde_yaml::Value::String("@rid".to_string()),
-                serde_yaml::Value::Number(serde_yaml::Number::from(mpi_rank as u64)),
-                serde_yaml::Value::String(format!("mpi_call_id('{}')", key)),
+            let mut row: Vec<SqlValue> = vec![
+                SqlValue::variable("@rid"),
+                SqlValue::Literal(serde_yaml::Value::Number(serde_yaml::Number::from(
+                    mpi_rank as u64,
+                ))),
+                SqlValue::function("mpi_call_id", vec![SqlValue::from(key.as_str())]),
             ];
 
             if !is_detail {
@@ -91,7 +94,7 @@ pub fn extract_mpi_data_from_mpi_profile(
                         vector
                     );
                 } else {
-                    row.extend(vector);
+                    row.extend(vector.into_iter().map(SqlValue::from));
                     result.push(row);
                 }
             } else {
@@ -107,7 +110,7 @@ pub fn extract_mpi_data_from_mpi_profile(
                             elem_vector
                         );
                     } else {
-                        detail_row.extend(elem_vector);
+                        detail_row.extend(elem_vector.into_iter().map(SqlValue::from));
                         result.push(detail_row);
                     }
                 }
@@ -129,7 +132,7 @@ pub fn extract_mpi_data_from_mpi_profile(
 /// The generated SQL queries are returned as a vector of strings.
 /// The function handles both regular and detail sections of the MPI profile data,
 /// generating appropriate SQL queries for each case. If no MPI profile data is
-/// found by `find_lmx_type_files`, the function returns an empty `Vec<String>`.
+/// found by `find_lmx_type_files`, the function returns an empty `Vec<Statement>`.
 ///
 /// # Arguments
 /// * `file_name` - The reference LMX summary file name to find MPI profile files.
@@ -137,7 +140,7 @@ pub fn extract_mpi_data_from_mpi_profile(
 /// * `args` - Command line arguments including verbosity and dry-run
 ///
 /// # Returns
-/// `Result<Vec<String>>` - A Result containing a vector of SQL insert statements.
+/// `Result<Vec<Statement>>` - A Result containing a vector of SQL insert statements.
 ///
 /// # Errors
 /// * Returns an error if any of the found MPI profile files cannot be read or parsed.
@@ -146,8 +149,8 @@ pub fn import_into_mpi_table(
     file_name: &str,
     sqltypes: &SqlTypeHashMap,
     args: &CliArgs,
-) -> Result<Vec<String>> {
-    let mut query_list: Vec<String> = Vec::new();
+) -> Result<Vec<Statement>> {
+    let mut query_list: Vec<Statement> = Vec::new();
     let mpi_profile_files = find_lmx_type_files(file_name, "MPI")?;
     if mpi_profile_files.is_empty() {
         if args.verbose || args.dry_run {
@@ -159,11 +162,14 @@ pub fn import_into_mpi_table(
         let mpi_profile = read_lmx_summary(&mpi_profile_file)?;
         let mpi_data = extract_mpi_data_from_mpi_profile(&mpi_profile, "MPI_rank_summary")?;
         if !mpi_data.is_empty() {
-            query_list.push(format!(
-                "-- Inserting MPI profile data from file {};",
-                mpi_profile_file
-            ));
-            query_list.push(create_import_statement(
+            query_list.push(
+                format!(
+                    "-- Inserting MPI profile data from file {};",
+                    mpi_profile_file
+                )
+                .into(),
+            );
+            query_list.extend(create_import_statement(
                 "mpi",
                 &[
                     "rid".to_string(),
@@ -175,16 +181,20 @@ pub fn import_into_mpi_table(
                 ],
                 &mpi_data,
                 sqltypes,
+                &InsertLimits::from_args(args),
             )?);
         }
 
         let mpi_detail_data = extract_mpi_data_from_mpi_profile(&mpi_profile, "MPI_rank_details")?;
         if !mpi_detail_data.is_empty() {
-            query_list.push(format!(
-                "-- Inserting MPI profile detail data from file {};",
-                mpi_profile_file
-            ));
-            query_list.push(create_import_statement(
+            query_list.push(
+                format!(
+                    "-- Inserting MPI profile detail data from file {};",
+                    mpi_profile_file
+                )
+                .into(),
+            );
+            query_list.extend(create_import_statement(
                 "mpi_details",
                 &[
                     "rid".to_string(),
@@ -196,6 +206,7 @@ pub fn import_into_mpi_table(
                 ],
                 &mpi_detail_data,
                 sqltypes,
+                &InsertLimits::from_args(args),
             )?);
         }
     }
@@ -15,8 +15,9 @@
 #[cfg(test)]
 mod tests {
     use crate::cmdline::CliArgs;
+    use crate::jobdata::statement::Statement;
     use crate::jobdata::table_mpi::import_into_mpi_table;
-    use crate::sqltypes::SqlTypeHashMap;
+    use crate::sqltypes::{SqlColumn, SqlTypeHashMap};
     use anyhow::Result;
     use std::collections::HashMap;
 
@@ -24,22 +25,28 @@ mod tests {
     fn create_mpi_sqltypes() -> SqlTypeHashMap {
         let mut sqltypes: SqlTypeHashMap = HashMap::new();
 
-        let mut mpi_columns: HashMap<String, String> = HashMap::new();
-        mpi_columns.insert("rid".to_string(), "int(11)".to_string());
-        mpi_columns.insert("tid".to_string(), "int(6)".to_string());
-        mpi_columns.insert("mid".to_string(), "smallint(8)".to_string());
-        mpi_columns.insert("avgbytes".to_string(), "float".to_string());
-        mpi_columns.insert("calls".to_string(), "int(11)".to_string());
-        mpi_columns.insert("time".to_string(), "float".to_string());
+        let mut mpi_columns: HashMap<String, SqlColumn> = HashMap::new();
+        mpi_columns.insert("rid".to_string(), SqlColumn::from_column_type("int(11)"));
+        mpi_columns.insert("tid".to_string(), SqlColumn::from_column_type("int(6)"));
+        mpi_columns.insert(
+            "mid".to_string(),
+            SqlColumn::from_column_type("smallint(8)"),
+        );
+        mpi_columns.insert("avgbytes".to_string(), SqlColumn::from_column_type("float"));
+        mpi_columns.insert("calls".to_string(), SqlColumn::from_column_type("int(11)"));
+        mpi_columns.insert("time".to_string(), SqlColumn::from_column_type("float"));
         sqltypes.insert("mpi".to_string(), mpi_columns);
 
-        let mut mpi_details_columns: HashMap<String, String> = HashMap::new();
-        mpi_details_columns.insert("rid".to_string(), "int(11)".to_string());
-        mpi_details_columns.insert("tid".to_string(), "int(6)".to_string());
-        mpi_details_columns.insert("mid".to_string(), "smallint(8)".to_string());
-        mpi_details_columns.insert("avgbytes".to_string(), "float".to_string());
-        mpi_details_columns.insert("calls".to_string(), "int(11)".to_string());
-        mpi_details_columns.insert("time".to_string(), "float".to_string());
+        let mut mpi_details_columns: HashMap<String, SqlColumn> = HashMap::new();
+        mpi_details_columns.insert("rid".to_string(), SqlColumn::from_column_type("int(11)"));
+        mpi_details_columns.insert("tid".to_string(), SqlColumn::from_column_type("int(6)"));
+        mpi_details_columns.insert(
+            "mid".to_string(),
+            SqlColumn::from_column_type("smallint(8)"),
+        );
+        mpi_details_columns.insert("avgbytes".to_string(), SqlColumn::from_column_type("float"));
+        mpi_details_columns.insert("calls".to_string(), SqlColumn::from_column_type("int(11)"));
+        mpi_details_columns.insert("time".to_string(), SqlColumn::from_column_type("float"));
         sqltypes.insert("mpi_details".to_string(), mpi_details_columns);
 
         sqltypes
@@ -86,7 +93,11 @@ mod tests {
         let lmx_file = std::path::Path::new(manifest_dir)
             .join("tests/data/NAMD/run_0001/LMX_summary.225250.0.yml");
 
-        let queries = import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
         assert!(
             queries.is_empty(),
             "Expected empty query list when no MPI profile files exist"
@@ -109,7 +120,11 @@ mod tests {
         let lmx_file = std::path::Path::new(manifest_dir)
             .join("tests/data/NAMD/run_0001/LMX_summary.225250.0.yml");
 
-        let queries = import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
         assert!(
             queries.is_empty(),
             "Expected empty query list when no MPI profile files exist in verbose mode"
@@ -132,7 +147,11 @@ mod tests {
         let lmx_file = std::path::Path::new(manifest_dir)
             .join("tests/data/NAMD/run_0001/LMX_summary.225250.0.yml");
 
-        let queries = import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
         assert!(
             queries.is_empty(),
             "Expected empty query list when no MPI profile files exist in dry_run mode"
@@ -155,7 +174,11 @@ mod tests {
         let lmx_file = std::path::Path::new(manifest_dir)
             .join("tests/data/NAMD/run_0002/LMX_summary.223561.0.yml");
 
-        let queries = import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
         assert!(
             !queries.is_empty(),
             "Expected non-empty query list when MPI profile files exist"
@@ -178,7 +201,11 @@ mod tests {
         let lmx_file = std::path::Path::new(manifest_dir)
             .join("tests/data/NAMD/run_0002/LMX_summary.223561.0.yml");
 
-        let queries = import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Each MPI profile file produces a comment + INSERT for summary,
         // and a comment + INSERT for details (4 queries per file).
@@ -225,7 +252,11 @@ mod tests {
         let lmx_file = std::path::Path::new(manifest_dir)
             .join("tests/data/NAMD/run_0002/LMX_summary.223561.0.yml");
 
-        let queries = import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_mpi_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // For each MPI profile file, the first INSERT should be into the mpi table
         // and the second INSERT should be into the mpi_details table
@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cmdline::CliArgs;
-use crate::jobdata::create_sql::{create_import_statement, create_update_statement};
+use crate::archive::display_path;
+use crate::cmdline::{CliArgs, DuplicatePolicy};
+use crate::jobdata::create_sql::{
+    create_import_statement, create_update_statement, InsertLimits, SqlValue,
+};
+use crate::jobdata::statement::Statement;
 use crate::jobdata::table_runs::find_file::extract_directory_path;
+use crate::jobdata::table_runs::foreign_keys::{RunsForeignKeys, NEW_RUN};
 use crate::jobdata::table_runs::misc_columns::{
     determine_misc_columns, determine_settings_columns,
 };
@@ -39,29 +44,33 @@ pub(crate) mod toolchain;
 /// * `file_name` - Path to the LMX summary file
 /// * `pool` - Optional reference to a MySQL connection pool
 /// * `lmx_summary` - Reference to the parsed LMX summary data
+/// * `foreign_keys` - The data of the project file of the run
 /// * `sqltypes` - Reference to the SQL types mapping for the database schema
+/// * `warnings` - Collects the problems which do not stop the import
 /// * `args` - Reference to command line arguments controlling behavior
 ///
-/// Returns `Result<Vec<String>>` containing the list of SQL queries to execute
+/// Returns `Result<Vec<Statement>>` containing the list of SQL statements to execute
 ///
 pub async fn import_into_runs_table(
     file_name: &str,
     pool: &Option<sqlx::Pool<MySql>>,
     lmx_summary: &LmxSummary,
+    foreign_keys: &RunsForeignKeys,
     sqltypes: &SqlTypeHashMap,
+    warnings: &mut Vec<String>,
     args: &CliArgs,
-) -> Result<Vec<String>> {
-    // Collect the SQL queries into a Vec<String> and process them later.
-    let mut query_list: Vec<String> = Vec::new();
+) -> Result<Vec<Statement>> {
+    // Collect the SQL statements into a Vec<Statement> and process them later.
+    let mut query_list: Vec<Statement> = Vec::new();
 
-    query_list.push("-- Inserting into runs table;".to_string());
+    query_list.push("-- Inserting into runs table;".into());
     if args.verbose || args.dry_run {
         println!("Generating SQL for runs table from file: {}", file_name);
     }
 
     // Generate SQL queries for foreign keys
     query_list.extend(
-        foreign_keys::generate_foreign_key_queries(file_name, pool, lmx_summary, args).await?,
+        foreign_keys::generate_foreign_key_queries(foreign_keys, pool, lmx_summary, args).await?,
     );
 
     // List the columns in the runs table
@@ -71,43 +80,31 @@ pub async fn import_into_runs_table(
 
     // Prepare the data for insertion into the 'runs' table
     // Start with mandatory foreign key columns
-    let mut column_data: Vec<(String, serde_yaml::Value)> = vec![
-        (
-            "ccid".to_string(),
-            serde_yaml::Value::String("@ccid".to_string()),
-        ),
-        (
-            "pid".to_string(),
-            serde_yaml::Value::String("@pid".to_string()),
-        ),
-        (
-            "clid".to_string(),
-            serde_yaml::Value::String("@clid".to_string()),
-        ),
-        (
-            "fsid".to_string(),
-            serde_yaml::Value::String("@fsid".to_string()),
-        ),
+    let mut column_data: Vec<(String, SqlValue)> = vec![
+        ("ccid".to_string(), SqlValue::variable("@ccid")),
+        ("pid".to_string(), SqlValue::variable("@pid")),
+        ("clid".to_string(), SqlValue::variable("@clid")),
+        ("fsid".to_string(), SqlValue::variable("@fsid")),
     ];
     // Add the required dirname column as the absolute path
-    // of the directory containing the LMX_summary file
+    // of the directory containing the LMX_summary file.
+    // For runs extracted from an archive, this is the archive member path.
     if args.verbose || args.dry_run {
         println!("Adding dirname column to runs table data");
     }
     column_data.push((
         "dirname".to_string(),
-        serde_yaml::Value::String(
-            extract_directory_path(file_name)?
+        SqlValue::from(
+            display_path(&extract_directory_path(file_name)?)
                 .to_str()
-                .unwrap()
-                .to_string(),
+                .unwrap(),
         ),
     ));
     // Populate column_data from lmx_summary base_data section
     if let Some(runs_section) = lmx_summary.get("base_data") {
         for (key, value) in runs_section {
             if runs_columns.contains_key(key) {
-                column_data.push((key.clone(), value.clone()));
+                column_data.push((key.clone(), SqlValue::from(value.clone())));
             }
         }
     }
@@ -115,34 +112,51 @@ pub async fn import_into_runs_table(
     // This needs to be done before creating the import statement
     // because the runs table doesn't allow default values for these columns.
     // So we need to provide explicit values, even if they are "n/a".
-    let current_toolchain = import_toolchain_data(file_name, lmx_summary, args);
-    column_data.extend(current_toolchain);
+    let current_toolchain = import_toolchain_data(file_name, lmx_summary, warnings, args);
+    column_data.extend(
+        current_toolchain
+            .into_iter()
+            .map(|(k, v)| (k, SqlValue::from(v))),
+    );
     // Convert to new API format
     let keys: Vec<String> = column_data.iter().map(|(k, _)| k.clone()).collect();
-    let values: Vec<Vec<serde_yaml::Value>> =
-        vec![column_data.iter().map(|(_, v)| v.clone()).collect()];
-    let import_sql = create_import_statement("runs", &keys, &values, sqltypes)?;
-    query_list.push(import_sql);
+    let values: Vec<Vec<SqlValue>> = vec![column_data.iter().map(|(_, v)| v.clone()).collect()];
+    let import_sql = create_import_statement(
+        "runs",
+        &keys,
+        &values,
+        sqltypes,
+        &InsertLimits::from_args(args),
+    )?;
+    query_list.extend(import_sql);
 
     // Set @rid for further use
     if args.verbose || args.dry_run {
         println!("Generating rid for current run ");
     }
-    query_list.push("SET @rid = LAST_INSERT_ID();".to_string());
+    // An existing run skipped by the guard gets no rid, so that the
+    // statements of the other tables do not refer to a previous run
+    if args.on_duplicate == DuplicatePolicy::Skip {
+        query_list.push(format!("SET @rid = IF({}, LAST_INSERT_ID(), NULL);", NEW_RUN).into());
+    } else {
+        query_list.push("SET @rid = LAST_INSERT_ID();".into());
+    }
     // Create progress indicator only for pool.is_none()
     // i.e., when *not* connected to a real database
     // This is useful when importing a file import.sql via
     // the mariadb command line client.
     if pool.is_none() {
-        query_list
-            .push("SELECT concat (\"       rid = \", @rid) as 'Processing run :';".to_string());
+        query_list.push("SELECT concat (\"       rid = \", @rid) as 'Processing run :';".into());
     }
 
     // Compute and import timing information
     if args.verbose || args.dry_run {
         println!("Generating timing information for current run ");
     }
-    let timing_data: Vec<(String, serde_yaml::Value)> = import_timing_data(lmx_summary)?;
+    let timing_data: Vec<(String, SqlValue)> = import_timing_data(lmx_summary)?
+        .into_iter()
+        .map(|(k, v)| (k, SqlValue::from(v)))
+        .collect();
     // Call create_update_statement for timing table
     let timing_sql = create_update_statement("runs", &timing_data, "rid = @rid", sqltypes)?;
     query_list.push(timing_sql);
@@ -154,6 +168,10 @@ pub async fn import_into_runs_table(
     let mut misc_columns = determine_misc_columns(file_name)?;
     // Append columns from settings file (if any)
     misc_columns.extend(determine_settings_columns(file_name, runs_columns, args));
+    let misc_columns: Vec<(String, SqlValue)> = misc_columns
+        .into_iter()
+        .map(|(k, v)| (k, SqlValue::from(v)))
+        .collect();
     // Create update statement for misc columns
     let misc_sql = create_update_statement("runs", &misc_columns, "rid = @rid", sqltypes)?;
     query_list.push(misc_sql);
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::archive::is_archive_root;
 use crate::cmdline::CliArgs;
 use anyhow::Result;
 use std::collections::HashMap;
@@ -78,7 +79,8 @@ pub fn find_config_file(file_name: &str, args: &CliArgs, is_project_file: bool)
             return Ok(config_file_path);
         }
 
-        if !current_dir.pop() {
+        // The root of an extracted archive is the top of the search
+        if is_archive_root(&current_dir) || !current_dir.pop() {
             return Err(anyhow::anyhow!(
                 "Required {} file '{}' not found in directory tree",
                 config_name,
@@ -173,6 +173,7 @@ pub async fn test_import_single_lmx_file(
     process_lmx_file(
         lmx_summary_pathbuf.to_str().unwrap(),
         &Some(pool.clone()),
+        None,
         &sqltypes,
         args,
     )
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cmdline::CliArgs;
+use crate::cmdline::{CliArgs, DuplicatePolicy};
+use crate::jobdata::statement::{BindValue, Statement};
 use crate::jobdata::table_runs::find_file::find_project_file;
 use crate::jobdata::LmxSummary;
 use anyhow::{bail, Result};
@@ -25,6 +26,13 @@ pub(crate) mod read_project_file;
 #[cfg(test)]
 pub(crate) mod test_foreign_keys;
 
+/// Cluster assumed if the project file does not specify one
+pub const DEFAULT_CLUSTER: &str = "Lenox";
+
+/// Condition of the statements of a run with `--on-duplicate skip`,
+/// true unless `@run_exists` found the run in the database
+pub const NEW_RUN: &str = "NOT @run_exists";
+
 /// Struct to hold foreign key data for the runs table
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
 pub struct RunsForeignKeys {
@@ -37,10 +45,11 @@ pub struct RunsForeignKeys {
 }
 
 /// Helper function to execute a query with parameters pool: &Option<sqlx::Pool<MySql>>,
-/// query: &String and args: &CliArgs returning Result<()> to execute a query if pool is Some.
+/// query: &Statement and args: &CliArgs returning Result<()> to execute a query if pool is Some.
 /// If pool is None and args.dry_run or args.verbose is set, it prints an informative message.
 /// If pool is None and neither args.dry_run nor args.verbose is set, it returns OK without any action.
-/// If pool is Some, it executes the query against the database and propagates any query error
+/// If pool is Some, it executes the query with its bound values against the database
+/// and propagates any query error
 /// using the `?` operator.
 /// If the query execution is successful, it reads the first column as an `Option<i32>` and checks
 /// whether the value is `None`. If so, it `anyhow::bail!`s with an error message. Otherwise, it
@@ -48,34 +57,34 @@ pub struct RunsForeignKeys {
 ///
 /// # Arguments
 /// * `pool` - Optional reference to a MySQL connection pool
-/// * `query` - Reference to the SQL query string to execute
+/// * `query` - Reference to the SQL statement to execute
 /// * `args` - Reference to command line arguments controlling behavior
 ///
 /// Returns `Result<()>` indicating success or failure of the operation
 ///
 pub async fn execute_query_if_pool(
     pool: &Option<sqlx::Pool<MySql>>,
-    query: &str,
+    query: &Statement,
     args: &CliArgs,
 ) -> Result<()> {
     if !args.dry_run && pool.is_some() {
         let db_pool = pool.as_ref().unwrap();
         if args.verbose {
-            println!("Executing query:\n{}", query);
+            println!("Executing query:\n{}", query.render());
         }
-        let row = sqlx::query(query).fetch_one(db_pool).await?;
+        let row = query.to_query().fetch_one(db_pool).await?;
         let fetched: Option<i32> = row.try_get(0)?;
         if fetched.is_none() {
             bail!(
                 "Foreign key validation failed: query returned no result or NULL.\nQuery: {}",
-                query
+                query.render()
             );
         }
         Ok(())
     } else {
         if args.verbose || args.dry_run {
             if pool.is_some() {
-                println!("Dry run mode: would execute query:\n{}", query);
+                println!("Dry run mode: would execute query:\n{}", query.render());
             } else {
                 println!("No database pool provided. Skipping execution of foreign key test");
             }
@@ -86,37 +95,40 @@ pub async fn execute_query_if_pool(
 
 /// Generates SQL queries to set up foreign keys for the runs table based on the provided
 /// LMX summary and project file data.
-/// This function reads the project file to extract foreign key information,
-/// then constructs SQL statements to set up the necessary foreign keys for the runs table.
+/// This function takes the foreign key information read from the project file
+/// and constructs SQL statements to set up the necessary foreign keys for the runs table.
 /// It generates SQL for cluster, person, customer case, filesystem, and duplicate run handling.
+/// Values from the project file and the LMX summary are never spliced into the SQL text,
+/// but bound to placeholders of the statements.
 ///
 /// # Arguments
-/// * `file_name` - Path to the LMX summary file
+/// * `foreign_keys` - The data of the project file of the run, see `read_project_file`
 /// * `pool` - Optional reference to a MySQL connection pool
 /// * `lmx_summary` - Reference to the parsed LMX summary data
 /// * `args` - Reference to command line arguments controlling behavior
 ///
 /// # Returns
-/// Returns a vector of SQL query strings to set up foreign keys
+/// Returns a vector of SQL statements to set up foreign keys
 ///
 /// # Errors
-/// Returns an `anyhow::Error` if there are issues reading the project file or generating queries
+/// Returns an `anyhow::Error` if the foreign keys cannot be verified or the
+/// LMX summary lacks the filesystem data or start dates
 ///
 pub async fn generate_foreign_key_queries(
-    file_name: &str,
+    foreign_keys: &RunsForeignKeys,
     pool: &Option<sqlx::Pool<MySql>>,
     lmx_summary: &LmxSummary,
     args: &CliArgs,
-) -> Result<Vec<String>> {
-    // Collect the SQL queries into a Vec<String> and process them later.
-    let mut query_list: Vec<String> = Vec::new();
-
-    // Find a project file and read its RunsForeignKeys
-    let foreign_keys = read_project_file(file_name, args)?;
+) -> Result<Vec<Statement>> {
+    // Collect the SQL statements into a Vec<Statement> and process them later.
+    let mut query_list: Vec<Statement> = Vec::new();
 
     // Generate SQL statement for cluster foreign key
-    let do_import = if args.do_import { "1" } else { "0" };
-    let cluster = foreign_keys.cluster.unwrap_or_else(|| "Lenox".to_string());
+    let do_import = i64::from(args.do_import);
+    let cluster = foreign_keys
+        .cluster
+        .clone()
+        .unwrap_or_else(|| DEFAULT_CLUSTER.to_string());
     if args.verbose || args.dry_run {
         println!("Generating cluster id for cluster: {}", cluster);
     }
@@ -124,66 +136,42 @@ pub async fn generate_foreign_key_queries(
     // Before generating the SQL statement, verify the cluster exists if pool is Some
     execute_query_if_pool(
         pool,
-        &format!("SELECT cluster_id('{}', {});", cluster, do_import),
+        &Statement::new("SELECT cluster_id(?, ?);")
+            .bind(cluster.as_str())
+            .bind(do_import),
         args,
     )
     .await?;
-    query_list.push(format!(
-        "SET @clid = cluster_id('{}', {});",
-        cluster, do_import
-    ));
+    query_list.push(
+        Statement::new("SET @clid = cluster_id(?, ?);")
+            .bind(cluster.as_str())
+            .bind(do_import),
+    );
 
     // Generate SQL statement for person foreign key
-    // First extract USER from the LMX summary environment section
-    // Fallback to "unknown_user" if not found
-    // USER is expected to be a sequence of strings
-    // which we join together to form the user id
-    let user_id_owned = lmx_summary
-        .get("environ")
-        .and_then(|m| m.get("USER"))
-        .and_then(|v| v.as_sequence())
-        .map(|seq| {
-            seq.iter()
-                .filter_map(|v| v.as_str())
-                .collect::<Vec<_>>()
-                .join("")
-        });
-    let user_id = user_id_owned.as_deref().unwrap_or("unknown_user");
-    // Now generate the SQL statement for person foreign key
     // If person is specified in the project file, use that
     // Otherwise, use the user id from the LMX summary
-    if let Some(person) = &foreign_keys.person {
-        if args.verbose || args.dry_run {
+    if args.verbose || args.dry_run {
+        if let Some(person) = &foreign_keys.person {
             println!("Generating person id for person: {}", person);
+        } else {
+            println!(
+                "Generating person id for user: {}",
+                extract_user_id(lmx_summary)
+            );
         }
-        execute_query_if_pool(
-            pool,
-            &format!("SELECT person_id('{}', {});", person, do_import),
-            args,
-        )
-        .await?;
-        query_list.push(format!(
-            "SET @pid = person_id('{}', {});",
-            person, do_import
-        ));
-    } else {
-        if args.verbose || args.dry_run {
-            println!("Generating person id for user: {}", user_id);
-        }
-        execute_query_if_pool(
-            pool,
-            &format!(
-                "SELECT person_id_for_uid('{}', cluster_id('{}', {}));",
-                user_id, cluster, do_import
-            ),
-            args,
-        )
-        .await?;
-        query_list.push(format!(
-            "SET @pid = person_id_for_uid('{}', cluster_id('{}', {}));",
-            user_id, cluster, do_import
-        ));
     }
+    let (person_id, person_binds) = person_id_call(foreign_keys, lmx_summary, &cluster, do_import);
+    execute_query_if_pool(
+        pool,
+        &Statement::with_binds(format!("SELECT {};", person_id), person_binds.clone()),
+        args,
+    )
+    .await?;
+    query_list.push(Statement::with_binds(
+        format!("SET @pid = {};", person_id),
+        person_binds,
+    ));
 
     // Generate SQL statement for customer case foreign key
     if args.verbose || args.dry_run {
@@ -192,26 +180,25 @@ pub async fn generate_foreign_key_queries(
             args.project_file
         );
     }
+    let customer_case_binds: Vec<BindValue> = vec![
+        foreign_keys.project.as_str().into(),
+        foreign_keys.code.as_str().into(),
+        foreign_keys.code_version.as_str().into(),
+        foreign_keys.test_case.as_str().into(),
+        do_import.into(),
+    ];
     execute_query_if_pool(
         pool,
-        &format!(
-            "SELECT customer_case_id('{}', '{}', '{}', '{}', {});",
-            foreign_keys.project,
-            foreign_keys.code,
-            foreign_keys.code_version,
-            foreign_keys.test_case,
-            do_import
+        &Statement::with_binds(
+            "SELECT customer_case_id(?, ?, ?, ?, ?);",
+            customer_case_binds.clone(),
         ),
         args,
     )
     .await?;
-    query_list.push(format!(
-        "SET @ccid = customer_case_id('{}', '{}', '{}', '{}', {});",
-        foreign_keys.project,
-        foreign_keys.code,
-        foreign_keys.code_version,
-        foreign_keys.test_case,
-        do_import
+    query_list.push(Statement::with_binds(
+        "SET @ccid = customer_case_id(?, ?, ?, ?, ?);",
+        customer_case_binds,
     ));
 
     // Generate SQL statements for filesystem id
@@ -238,21 +225,162 @@ pub async fn generate_foreign_key_queries(
         .ok_or_else(|| anyhow::anyhow!("'blocksize' is not an integer"))?;
     execute_query_if_pool(
         pool,
-        &format!("SELECT filesystem_id('{}', '{}', {});", fstype, m_pt, bsize),
+        &Statement::new("SELECT filesystem_id(?, ?, ?);")
+            .bind(fstype)
+            .bind(m_pt)
+            .bind(bsize),
         args,
     )
     .await?;
-    query_list.push(format!(
-        "SET @fsid = filesystem_id('{}', '{}', {});",
-        fstype, m_pt, bsize
-    ));
+    query_list.push(
+        Statement::new("SET @fsid = filesystem_id(?, ?, ?);")
+            .bind(fstype)
+            .bind(m_pt)
+            .bind(bsize),
+    );
+
+    // Generate SQL statements for duplicate runs according to args.on_duplicate.
+    // Duplicate runs are identified by person_id and timestamps from
+    // the LMX summary base_data.
+    if args.verbose || args.dry_run {
+        println!(
+            "Generating statements for duplicate runs with policy {:?}",
+            args.on_duplicate
+        );
+    }
+    let (start_date, start_date_n) = extract_start_date(lmx_summary)?;
+    let existing_run = format!(
+        "SELECT 1 FROM runs WHERE pid = @pid AND start_date = {} AND start_date_n = {}",
+        start_date, start_date_n
+    );
+    match args.on_duplicate {
+        DuplicatePolicy::Replace => {
+            query_list.push(
+                Statement::new("CALL drop_run_by_user_start_date(@pid, ?, ?);")
+                    .bind(start_date)
+                    .bind(start_date_n),
+            );
+        }
+        DuplicatePolicy::KeepBoth => {}
+        // With a database connection, existing runs have already been
+        // detected by `run_exists` before any SQL was generated.
+        // The INSERT statements of the run are guarded by `NEW_RUN` in addition,
+        // which is required for the SQL file and covers runs imported meanwhile.
+        DuplicatePolicy::Skip => {
+            query_list.push(format!("SET @run_exists = EXISTS ({});", existing_run).into());
+        }
+        DuplicatePolicy::Error if pool.is_none() => {
+            query_list.push("DELIMITER //".into());
+            query_list.push(
+                format!(
+                    "IF EXISTS ({}) THEN SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = \
+                     'Run already exists (start_date = {}, start_date_n = {})'; END IF //",
+                    existing_run, start_date, start_date_n
+                )
+                .into(),
+            );
+            query_list.push("DELIMITER ;".into());
+        }
+        DuplicatePolicy::Error => {}
+    }
+
+    // Return the list of generated SQL queries
+    Ok(query_list)
+}
 
-    // Generate SQL drop statement for duplicate runs
-    // This uses person_id and timestamps from LMX summary base_data
-    // to identify duplicate runs
+/// Checks whether the run described by the LMX summary already exists in the database.
+/// A run is identified by its person id together with start_date and start_date_n.
+/// The person id is looked up without inserting unknown persons, so an unknown
+/// person never matches an existing run.
+///
+/// # Arguments
+/// * `foreign_keys` - The data of the project file of the run
+/// * `pool` - Reference to a MySQL connection pool
+/// * `lmx_summary` - Reference to the parsed LMX summary data
+/// * `args` - Reference to command line arguments controlling behavior
+///
+/// # Returns
+/// Returns `true` if a matching run exists
+///
+/// # Errors
+/// Returns an `anyhow::Error` if the LMX summary lacks the start dates or the query fails
+///
+pub async fn run_exists(
+    foreign_keys: &RunsForeignKeys,
+    pool: &sqlx::Pool<MySql>,
+    lmx_summary: &LmxSummary,
+    args: &CliArgs,
+) -> Result<bool> {
+    let cluster = foreign_keys
+        .cluster
+        .clone()
+        .unwrap_or_else(|| DEFAULT_CLUSTER.to_string());
+    let (person_id, person_binds) = person_id_call(foreign_keys, lmx_summary, &cluster, 0);
+    let (start_date, start_date_n) = extract_start_date(lmx_summary)?;
+    let query = Statement::with_binds(
+        format!(
+            "SELECT COUNT(*) FROM runs WHERE pid = {} AND start_date = ? AND start_date_n = ?;",
+            person_id
+        ),
+        person_binds,
+    )
+    .bind(start_date)
+    .bind(start_date_n);
     if args.verbose || args.dry_run {
-        println!("Generating drop statement for duplicate runs");
+        println!("Checking for existing run:\n{}", query.render());
     }
+    let count: i64 = query.to_query().fetch_one(pool).await?.try_get(0)?;
+    Ok(count > 0)
+}
+
+/// Extracts the user id from the `USER` entry of the environ section.
+/// USER is expected to be a sequence of strings which are joined together.
+/// Falls back to "unknown_user" if not found.
+pub fn extract_user_id(lmx_summary: &LmxSummary) -> String {
+    lmx_summary
+        .get("environ")
+        .and_then(|m| m.get("USER"))
+        .and_then(|v| v.as_sequence())
+        .map(|seq| {
+            seq.iter()
+                .filter_map(|v| v.as_str())
+                .collect::<Vec<_>>()
+                .join("")
+        })
+        .unwrap_or_else(|| "unknown_user".to_string())
+}
+
+/// Returns the stored function call determining the person id, either from
+/// the person in the project file or from the user id in the LMX summary,
+/// together with the values bound to its placeholders.
+fn person_id_call(
+    foreign_keys: &RunsForeignKeys,
+    lmx_summary: &LmxSummary,
+    cluster: &str,
+    do_import: i64,
+) -> (&'static str, Vec<BindValue>) {
+    if let Some(person) = &foreign_keys.person {
+        (
+            "person_id(?, ?)",
+            vec![person.as_str().into(), do_import.into()],
+        )
+    } else {
+        (
+            "person_id_for_uid(?, cluster_id(?, ?))",
+            vec![
+                extract_user_id(lmx_summary).into(),
+                cluster.into(),
+                do_import.into(),
+            ],
+        )
+    }
+}
+
+/// Extracts start_date and start_date_n from the LMX summary base_data.
+pub fn extract_start_date(lmx_summary: &LmxSummary) -> Result<(i64, i64)> {
+    let base_data = lmx_summary
+        .get("base_data")
+        .ok_or_else(|| anyhow::anyhow!("Missing 'base_data' in LMX summary"))?;
     let start_date = base_data
         .get("start_date")
         .ok_or_else(|| anyhow::anyhow!("Missing 'start_time' in base_data"))?
@@ -263,13 +391,7 @@ pub async fn generate_foreign_key_queries(
         .ok_or_else(|| anyhow::anyhow!("Missing 'start_time_n' in base_data"))?
         .as_i64()
         .ok_or_else(|| anyhow::anyhow!("'start_time_n' is not an integer"))?;
-    query_list.push(format!(
-        "CALL drop_run_by_user_start_date(@pid, {}, {});",
-        start_date, start_date_n
-    ));
-
-    // Return the list of generated SQL queries
-    Ok(query_list)
+    Ok((start_date, start_date_n))
 }
 
 /// Reads and parses the project file to extract RunsForeignKeys.
@@ -14,15 +14,24 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::cmdline::CliArgs;
+    use crate::cmdline::{CliArgs, DuplicatePolicy};
+    use crate::jobdata::statement::{BindValue, Statement};
     use crate::jobdata::table_runs::find_file::project_mockup::{
-        setup_cliargs_with_project_file_name, setup_tmp_project_directory,
+        setup_cliargs_with_project_file, setup_cliargs_with_project_file_name,
+        setup_tmp_project_directory,
+    };
+    use crate::jobdata::table_runs::foreign_keys::{
+        generate_foreign_key_queries, read_project_file, RunsForeignKeys,
     };
-    use crate::jobdata::table_runs::foreign_keys::generate_foreign_key_queries;
     use crate::jobdata::{read_lmx_summary, LmxSummary};
     use anyhow::Result;
     use std::fs::remove_dir_all;
 
+    /// Renders the statements as they are written to the SQL file
+    fn render_all(statements: Vec<Statement>) -> Vec<String> {
+        statements.iter().map(Statement::render).collect()
+    }
+
     // Test generating foreign key queries when userID is given and do_import is false.
     // We test with pool = None to avoid actual DB operations
     #[tokio::test]
@@ -39,14 +48,14 @@ mod tests {
 
         // Call the generate_foreign_key_queries function
         let sql_queries = generate_foreign_key_queries(
-            lmx_summary_pathbuf.to_str().unwrap(),
+            &read_project_file(lmx_summary_pathbuf.to_str().unwrap(), &args)?,
             &None,
             &lmx_summary,
             &args,
         )
         .await;
         assert!(sql_queries.is_ok(), "{}", sql_queries.as_ref().unwrap_err());
-        let sql_queries = sql_queries.unwrap();
+        let sql_queries = render_all(sql_queries.unwrap());
         assert_eq!(sql_queries.len(), 5);
         assert_eq!(sql_queries[0], "SET @clid = cluster_id('Lenox', 0);");
         assert_eq!(
@@ -94,14 +103,14 @@ mod tests {
 
         // Call the generate_foreign_key_queries function
         let sql_queries = generate_foreign_key_queries(
-            lmx_summary_pathbuf.to_str().unwrap(),
+            &read_project_file(lmx_summary_pathbuf.to_str().unwrap(), &args)?,
             &None,
             &lmx_summary,
             &args,
         )
         .await;
         assert!(sql_queries.is_ok(), "{}", sql_queries.as_ref().unwrap_err());
-        let sql_queries = sql_queries.unwrap();
+        let sql_queries = render_all(sql_queries.unwrap());
         assert_eq!(sql_queries.len(), 5);
         assert_eq!(sql_queries[0], "SET @clid = cluster_id('Lenox', 1);");
         assert_eq!(
@@ -125,4 +134,119 @@ mod tests {
         remove_dir_all(temp_dir)?;
         Ok(())
     }
+
+    // Test the duplicate handling statements in SQL file mode for all policies.
+    // We test with pool = None to avoid actual DB operations
+    #[tokio::test]
+    pub async fn test_duplicate_policies_with_simple_namd_data() -> Result<()> {
+        // Create a temporary project file for testing
+        let temp_dir = setup_tmp_project_directory("tests/data/NAMD")?;
+        let project_file = temp_dir.join("project.yml");
+        let mut args = setup_cliargs_with_project_file_name(project_file.to_str().unwrap())?;
+
+        // Set the LMX_summary file path and read its contents
+        let lmx_summary_pathbuf = temp_dir.join("run_0001/LMX_summary.225250.0.yml");
+        let lmx_summary: LmxSummary = read_lmx_summary(lmx_summary_pathbuf.to_str().unwrap())
+            .expect("Failed to read LMX summary");
+        let existing_run = "SELECT 1 FROM runs WHERE pid = @pid \
+                            AND start_date = 1764250902 AND start_date_n = 199871237";
+
+        // keep-both: no statement for duplicate runs at all
+        args.on_duplicate = DuplicatePolicy::KeepBoth;
+        let sql_queries = generate_foreign_key_queries(
+            &read_project_file(lmx_summary_pathbuf.to_str().unwrap(), &args)?,
+            &None,
+            &lmx_summary,
+            &args,
+        )
+        .await
+        .map(render_all)?;
+        assert_eq!(sql_queries.len(), 4);
+
+        // skip: record whether the run exists for the guarded statements of the run
+        args.on_duplicate = DuplicatePolicy::Skip;
+        let sql_queries = generate_foreign_key_queries(
+            &read_project_file(lmx_summary_pathbuf.to_str().unwrap(), &args)?,
+            &None,
+            &lmx_summary,
+            &args,
+        )
+        .await
+        .map(render_all)?;
+        assert_eq!(sql_queries.len(), 5);
+        assert_eq!(
+            sql_queries[4],
+            format!("SET @run_exists = EXISTS ({});", existing_run)
+        );
+
+        // error: signal an error for an existing run
+        args.on_duplicate = DuplicatePolicy::Error;
+        let sql_queries = generate_foreign_key_queries(
+            &read_project_file(lmx_summary_pathbuf.to_str().unwrap(), &args)?,
+            &None,
+            &lmx_summary,
+            &args,
+        )
+        .await
+        .map(render_all)?;
+        assert_eq!(sql_queries.len(), 7);
+        assert_eq!(sql_queries[4], "DELIMITER //");
+        assert!(sql_queries[5].starts_with(&format!("IF EXISTS ({}) THEN SIGNAL", existing_run)));
+        assert_eq!(sql_queries[6], "DELIMITER ;");
+
+        // Clean up the temporary project file and directory
+        remove_dir_all(temp_dir)?;
+        Ok(())
+    }
+
+    // Test that values from the project file are bound instead of spliced into the SQL text,
+    // and escaped when rendered for the SQL file.
+    #[tokio::test]
+    pub async fn test_project_values_with_quotes() -> Result<()> {
+        // Create a temporary project file with quotes and backslashes in its values
+        let temp_dir = setup_tmp_project_directory("tests/data/NAMD")?;
+        let project_file = temp_dir.join("project.yml");
+        let foreign_keys = RunsForeignKeys {
+            project: "O'Brien's paper".to_string(),
+            code: "NAMD".to_string(),
+            code_version: "3.0.2".to_string(),
+            test_case: "STMV\\'); DROP TABLE runs; --".to_string(),
+            cluster: None,
+            person: Some("Conan O'Brien".to_string()),
+        };
+        let args = setup_cliargs_with_project_file(project_file.to_str().unwrap(), &foreign_keys)?;
+
+        // Set the LMX_summary file path and read its contents
+        let lmx_summary_pathbuf = temp_dir.join("run_0001/LMX_summary.225250.0.yml");
+        let lmx_summary: LmxSummary = read_lmx_summary(lmx_summary_pathbuf.to_str().unwrap())
+            .expect("Failed to read LMX summary");
+
+        let sql_queries = generate_foreign_key_queries(
+            &read_project_file(lmx_summary_pathbuf.to_str().unwrap(), &args)?,
+            &None,
+            &lmx_summary,
+            &args,
+        )
+        .await?;
+        assert_eq!(sql_queries[1].sql, "SET @pid = person_id(?, ?);");
+        assert_eq!(
+            sql_queries[2].sql,
+            "SET @ccid = customer_case_id(?, ?, ?, ?, ?);"
+        );
+        assert_eq!(
+            sql_queries[2].binds[3],
+            BindValue::Text("STMV\\'); DROP TABLE runs; --".to_string())
+        );
+        let sql_queries = render_all(sql_queries);
+        assert_eq!(sql_queries[1], "SET @pid = person_id('Conan O''Brien', 0);");
+        assert_eq!(
+            sql_queries[2],
+            "SET @ccid = customer_case_id('O''Brien''s paper', 'NAMD', '3.0.2', \
+             'STMV\\\\''); DROP TABLE runs; --', 0);"
+        );
+
+        // Clean up the temporary project file and directory
+        remove_dir_all(temp_dir)?;
+        Ok(())
+    }
 }
@@ -15,6 +15,7 @@
 #[cfg(test)]
 mod tests {
     use crate::cmdline::CliArgs;
+    use crate::jobdata::statement::Statement;
     use crate::jobdata::table_runs::foreign_keys::execute_query_if_pool;
     use anyhow::Result;
     use sqlx::{MySql, Pool};
@@ -31,7 +32,7 @@ mod tests {
             do_import: true,
             ..Default::default()
         };
-        let query = "SELECT 1 + 1 AS sum;";
+        let query = &Statement::new("SELECT 1 + 1 AS sum;");
         let result = execute_query_if_pool(&Some(pool.clone()), query, &args).await;
         assert!(result.is_ok(), "{}", result.as_ref().unwrap_err());
         Ok(())
@@ -49,7 +50,7 @@ mod tests {
             do_import: true,
             ..Default::default()
         };
-        let query = "SELECT 1 + 1 AS sum;";
+        let query = &Statement::new("SELECT 1 + 1 AS sum;");
         let result = execute_query_if_pool(&Some(pool.clone()), query, &args).await;
         assert!(result.is_ok(), "{}", result.as_ref().unwrap_err());
         Ok(())
@@ -64,7 +65,7 @@ mod tests {
             do_import: true,
             ..Default::default()
         };
-        let query = "SELECT 1 + 1 AS sum;";
+        let query = &Statement::new("SELECT 1 + 1 AS sum;");
         let result = execute_query_if_pool(&None, query, &args).await;
         assert!(result.is_ok(), "{}", result.as_ref().unwrap_err());
         Ok(())
@@ -82,7 +83,9 @@ mod tests {
             do_import: false,
             ..Default::default()
         };
-        let query = "SELECT cluster_id('Lenox', 0);";
+        let query = &Statement::new("SELECT cluster_id(?, ?);")
+            .bind("Lenox")
+            .bind(0_i64);
         let result = execute_query_if_pool(&Some(pool.clone()), query, &args).await;
         assert!(
             result.is_err(),
@@ -103,7 +106,9 @@ mod tests {
             do_import: true,
             ..Default::default()
         };
-        let query = "SELECT cluster_id('Lenox', 1);";
+        let query = &Statement::new("SELECT cluster_id(?, ?);")
+            .bind("Lenox")
+            .bind(1_i64);
         let result = execute_query_if_pool(&Some(pool.clone()), query, &args).await;
         assert!(
             result.is_ok(),
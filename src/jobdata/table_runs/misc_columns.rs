@@ -15,6 +15,7 @@
 use crate::cmdline::CliArgs;
 use crate::globbing::find_lmx_type_files;
 use crate::jobdata::table_runs::find_file::find_and_read_settings_file;
+use crate::sqltypes::SqlColumn;
 use anyhow::Result;
 use std::collections::HashMap;
 
@@ -76,7 +77,7 @@ pub fn determine_misc_columns(file_name: &str) -> Result<Vec<(String, serde_yaml
 /// Errors if there are issues reading or parsing the settings file.
 pub fn determine_settings_columns(
     file_name: &str,
-    runs_columns: &HashMap<String, String>,
+    runs_columns: &HashMap<String, SqlColumn>,
     args: &CliArgs,
 ) -> Vec<(String, serde_yaml::Value)> {
     if args.verbose || args.dry_run {
@@ -16,6 +16,7 @@
 mod tests {
     use crate::cmdline::CliArgs;
     use crate::jobdata::table_runs::misc_columns::determine_settings_columns;
+    use crate::sqltypes::SqlColumn;
     use anyhow::Result;
     use std::collections::HashMap;
     use uuid::Uuid;
@@ -58,9 +59,9 @@ memory_per_node: 64
 "#;
         std::fs::write(&settings_file, settings_content)?;
         let runs_columns = HashMap::from([
-            ("gpus".to_string(), "".to_string()),
-            ("comment".to_string(), "".to_string()),
-            ("compiler".to_string(), "".to_string()),
+            ("gpus".to_string(), SqlColumn::default()),
+            ("comment".to_string(), SqlColumn::default()),
+            ("compiler".to_string(), SqlColumn::default()),
         ]);
         let args = CliArgs {
             verbose: false,
@@ -41,6 +41,7 @@ pub type ToolChainMap = HashMap<String, ToolChain>;
 /// Arguments:
 /// * `file_name` - The name of the job file
 /// * `lmx_summary` - The LMX summary data
+/// * `warnings` - Collects the reason if no toolchain data is available
 /// * `args` - Command line arguments
 ///
 /// Returns:
@@ -48,11 +49,12 @@ pub type ToolChainMap = HashMap<String, ToolChain>;
 ///
 /// Errors:
 /// This function will never return an error - any errors encountered during
-/// file reading or parsing are caught and logged, and an empty ToolChain is returned instead.
+/// file reading or parsing are added to `warnings`, and an empty ToolChain is used instead.
 ///
 pub fn import_toolchain_data(
     file_name: &str,
     lmx_summary: &LmxSummary,
+    warnings: &mut Vec<String>,
     args: &CliArgs,
 ) -> Vec<(String, serde_yaml::Value)> {
     // Initialize default or returned data
@@ -69,6 +71,7 @@ pub fn import_toolchain_data(
             if args.verbose || args.dry_run {
                 println!("Ignoring: {}", e);
             }
+            warnings.push(format!("Toolchain data not available: {}", e));
             empty_toolchain.clone()
         }
     };
@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use crate::cmdline::CliArgs;
-use crate::jobdata::create_sql::create_import_statement;
+use crate::jobdata::create_sql::{create_import_statement, InsertLimits, SqlValue};
+use crate::jobdata::statement::Statement;
 use crate::jobdata::table_runs::find_file::find_and_read_settings_file;
 use crate::sqltypes::SqlTypeHashMap;
 use anyhow::Result;
@@ -44,10 +45,10 @@ pub fn import_into_settings_table(
     file_name: &str,
     sqltypes: &SqlTypeHashMap,
     args: &CliArgs,
-) -> Result<Vec<String>> {
+) -> Result<Vec<Statement>> {
     let key_list: Vec<String> = vec!["rid".to_string(), "k".to_string(), "value".to_string()];
-    let mut value_list: Vec<Vec<serde_yaml::Value>> = Vec::new();
-    let mut query_list: Vec<String> = Vec::new();
+    let mut value_list: Vec<Vec<SqlValue>> = Vec::new();
+    let mut query_list: Vec<Statement> = Vec::new();
 
     // Check early if 'settings' table exists in sqltypes to fail fast
     if !sqltypes.contains_key("settings") {
@@ -70,18 +71,19 @@ pub fn import_into_settings_table(
             continue;
         }
         value_list.push(vec![
-            serde_yaml::Value::String("@rid".to_string()),
-            serde_yaml::Value::String(key.clone()),
-            value.clone(),
+            SqlValue::variable("@rid"),
+            SqlValue::from(key.as_str()),
+            SqlValue::from(value.clone()),
         ]);
     }
     if !value_list.is_empty() {
-        query_list.push("-- Inserting into settings table;".to_string());
-        query_list.push(create_import_statement(
+        query_list.push("-- Inserting into settings table;".into());
+        query_list.extend(create_import_statement(
             "settings",
             &key_list,
             &value_list,
             sqltypes,
+            &InsertLimits::from_args(args),
         )?);
     }
     Ok(query_list)
@@ -14,10 +14,12 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::jobdata::statement::Statement;
     use crate::{
         cmdline::CliArgs,
         jobdata::table_runs::find_file::project_mockup::setup_tmp_project_directory,
-        jobdata::table_settings::import_into_settings_table, sqltypes::read_sqltypes,
+        jobdata::table_settings::import_into_settings_table,
+        sqltypes::{read_sqltypes, SqlTypeHashMap},
     };
     use anyhow::Result;
     use sqlx::MySql;
@@ -37,14 +39,18 @@ mod tests {
         };
 
         // Create an empty sqltypes map (no 'settings' table)
-        let sqltypes: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let sqltypes: SqlTypeHashMap = HashMap::new();
 
         let manifest_dir = env!("CARGO_MANIFEST_DIR");
         let lmx_file = std::path::Path::new(manifest_dir)
             .join("tests/data/GROMACS/run_64/LMX_summary.376231.0.yml");
 
         // Call import_into_settings_table with no 'settings' table in sqltypes
-        let queries = import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return empty vector without reading the settings file
         assert!(
@@ -80,7 +86,11 @@ mod tests {
             .join("tests/data/GROMACS/run_64/LMX_summary.376231.0.yml");
 
         // Call import_into_settings_table (no await - function is not async)
-        let queries = import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return a comment line and exactly one query with filtered keys
         assert_eq!(
@@ -153,7 +163,11 @@ mod tests {
             .join("tests/data/NAMD/run_0001/LMX_summary.225250.0.yml");
 
         // Call import_into_settings_table (no await - function is not async)
-        let queries = import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return empty vector since settings.yml doesn't exist
         assert!(
@@ -204,7 +218,11 @@ perf_value: 319.366
         let lmx_file = temp_dir.join("LMX_summary.376231.0.yml");
 
         // Call import_into_settings_table (no await - function is not async)
-        let queries = import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return empty vector since all keys are filtered
         assert!(
@@ -255,7 +273,11 @@ compiler: "Clang"
         let lmx_file = temp_dir.join("LMX_summary.376231.0.yml");
 
         // Call import_into_settings_table (no await - function is not async)
-        let queries = import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return a comment line and exactly one query with filtered keys
         assert_eq!(
@@ -330,7 +352,11 @@ feature_flag: false
         let lmx_file = temp_dir.join("LMX_summary.376231.0.yml");
 
         // Call import_into_settings_table (no await - function is not async)
-        let queries = import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should return a comment line and exactly one query with various value types
         assert_eq!(
@@ -413,7 +439,11 @@ feature_flag: false
             .join("tests/data/GROMACS/run_64/LMX_summary.376231.0.yml");
 
         // Call import_into_settings_table (no await - function is not async)
-        let queries = import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?;
+        let queries: Vec<String> =
+            import_into_settings_table(lmx_file.to_str().unwrap(), &sqltypes, &args)?
+                .iter()
+                .map(Statement::render)
+                .collect();
 
         // Should still return queries
         assert!(
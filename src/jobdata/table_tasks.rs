@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use crate::cmdline::CliArgs;
-use crate::jobdata::create_sql::create_import_statement;
+use crate::jobdata::create_sql::{create_import_statement, InsertLimits, SqlValue};
+use crate::jobdata::statement::Statement;
 use crate::jobdata::LmxSummary;
 use crate::sqltypes::SqlTypeHashMap;
 use anyhow::Result;
@@ -175,7 +176,7 @@ fn parse_optional_string_array(
 /// a Vec<serde_yaml::Value>. Only the first element of this sequence
 /// is to be inserted into column `loadimb`.
 ///
-/// The function returns a Vec<String> containing SQL statements for the tasks table.
+/// The function returns a Vec<Statement> containing SQL statements for the tasks table.
 /// If the tasks table doesn't exist in sqltypes, an empty Vec is returned.
 /// Otherwise, the Vec contains exactly 3 SQL statements:
 /// - The first statement is a comment "-- Inserting into tasks table;".to_string(),
@@ -197,8 +198,8 @@ pub fn import_into_tasks_table(
     lmx_summary: &LmxSummary,
     sqltypes: &SqlTypeHashMap,
     args: &CliArgs,
-) -> Result<Vec<String>> {
-    let mut queries = Vec::new();
+) -> Result<Vec<Statement>> {
+    let mut queries: Vec<Statement> = Vec::new();
 
     // Check early if 'tasks' table exists in sqltypes to fail fast
     if !sqltypes.contains_key("tasks") {
@@ -262,32 +263,36 @@ pub fn import_into_tasks_table(
             "CPU_affinity section is empty. At least one MPI rank configuration is required."
         ));
     }
-    let mut value_vector: Vec<Vec<serde_yaml::Value>> = Vec::new();
+    let mut value_vector: Vec<Vec<SqlValue>> = Vec::new();
     for i in 0..num_tasks {
         let rank_str = i.to_string();
         // Extract affinity values
         let aff_values =
             parse_optional_string_array(&aff_section.get(&rank_str), &rank_str, "affinity")?;
         // Start building the values for this task
-        let mut values: Vec<serde_yaml::Value> = vec![
-            serde_yaml::Value::String("@rid".to_string()),
-            serde_yaml::Value::Number(serde_yaml::Number::from(i as i64)),
+        let node_name = aff_values[0].as_str().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Expected string value for affinity[0] in rank {}, but got: {:?}",
+                rank_str,
+                aff_values[0]
+            )
+        })?;
+        let mut values: Vec<SqlValue> = vec![
+            SqlValue::variable("@rid"),
+            SqlValue::Literal(serde_yaml::Value::Number(serde_yaml::Number::from(
+                i as i64,
+            ))),
             // lid is processed by stored function location_id().
-            // The node name must be a string literal in SQL.
-            // Escape single quotes in the node name to prevent SQL injection.
-            serde_yaml::Value::String({
-                let node_name = aff_values[0].as_str().ok_or_else(|| {
-                    anyhow::anyhow!(
-                        "Expected string value for affinity[0] in rank {}, but got: {:?}",
-                        rank_str,
-                        aff_values[0]
-                    )
-                })?;
-                let escaped_node_name = node_name.replace('\'', "''");
-                format!("location_id('{}', @cl_name, 'nodes')", escaped_node_name)
-            }),
+            SqlValue::function(
+                "location_id",
+                vec![
+                    SqlValue::from(node_name),
+                    SqlValue::variable("@cl_name"),
+                    SqlValue::from("nodes"),
+                ],
+            ),
             // affinity
-            aff_values[1].clone(),
+            SqlValue::from(aff_values[1].clone()),
         ];
         // Extract rank_summary values
         let rank_sum_values = parse_optional_float_array(
@@ -296,7 +301,7 @@ pub fn import_into_tasks_table(
             "rank_summary",
         )?;
         // Append rank_summary values
-        values.extend(rank_sum_values);
+        values.extend(rank_sum_values.into_iter().map(SqlValue::from));
         // Extract communication_times values if present
         if let Some(comm_section) = comm_times_section {
             let comm_values = parse_optional_float_array(
@@ -312,8 +317,8 @@ pub fn import_into_tasks_table(
                 ));
             }
             // Append comm (first element) and mpiio (third element)
-            values.push(comm_values[0].clone());
-            values.push(comm_values[2].clone());
+            values.push(SqlValue::from(comm_values[0].clone()));
+            values.push(SqlValue::from(comm_values[2].clone()));
         }
         // Extract load_imbalance_times values if present
         if let Some(loadimb_section) = loadimb_times_section {
@@ -323,18 +328,19 @@ pub fn import_into_tasks_table(
                 "load_imbalance_times",
             )?;
             // Append loadimb (first element)
-            values.push(loadimb_values[0].clone());
+            values.push(SqlValue::from(loadimb_values[0].clone()));
         }
         value_vector.push(values);
     }
 
-    queries.push("-- Inserting into tasks table;".to_string());
-    queries.push("SET @cl_name = cluster_name(@clid);".to_string());
-    queries.push(create_import_statement(
+    queries.push("-- Inserting into tasks table;".into());
+    queries.push("SET @cl_name = cluster_name(@clid);".into());
+    queries.extend(create_import_statement(
         "tasks",
         &keys,
         &value_vector,
         sqltypes,
+        &InsertLimits::from_args(args),
     )?);
 
     Ok(queries)
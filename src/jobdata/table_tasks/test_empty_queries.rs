@@ -14,7 +14,9 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::{cmdline::CliArgs, jobdata::table_tasks::import_into_tasks_table};
+    use crate::{
+        cmdline::CliArgs, jobdata::table_tasks::import_into_tasks_table, sqltypes::SqlTypeHashMap,
+    };
     use anyhow::Result;
     use std::collections::HashMap;
 
@@ -32,7 +34,7 @@ mod tests {
         };
 
         // Create an empty sqltypes map (no 'tasks' table)
-        let sqltypes: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let sqltypes: SqlTypeHashMap = HashMap::new();
 
         // Create a minimal LMX summary with required sections
         let manifest_dir = env!("CARGO_MANIFEST_DIR");
@@ -14,6 +14,7 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::jobdata::statement::Statement;
     use crate::{
         cmdline::CliArgs, jobdata::table_tasks::import_into_tasks_table, sqltypes::read_sqltypes,
     };
@@ -87,7 +88,10 @@ mod tests {
 
         // Call import_into_tasks_table in verbose mode
         // Should print messages about missing sections (not testing stdout here)
-        let queries = import_into_tasks_table(&lmx_summary, &sqltypes, &args)?;
+        let queries: Vec<String> = import_into_tasks_table(&lmx_summary, &sqltypes, &args)?
+            .iter()
+            .map(Statement::render)
+            .collect();
 
         // Should still return queries
         assert!(
@@ -204,7 +208,10 @@ mod tests {
         lmx_summary.insert("load_imbalance_times".to_string(), loadimb_times);
 
         // Call import_into_tasks_table
-        let queries = import_into_tasks_table(&lmx_summary, &sqltypes, &args)?;
+        let queries: Vec<String> = import_into_tasks_table(&lmx_summary, &sqltypes, &args)?
+            .iter()
+            .map(Statement::render)
+            .collect();
 
         // Should return queries with all columns
         assert_eq!(
@@ -293,7 +300,10 @@ mod tests {
 
         // Call import_into_tasks_table in dry_run mode
         // Should print messages about missing sections (not testing stdout here)
-        let queries = import_into_tasks_table(&lmx_summary, &sqltypes, &args)?;
+        let queries: Vec<String> = import_into_tasks_table(&lmx_summary, &sqltypes, &args)?
+            .iter()
+            .map(Statement::render)
+            .collect();
 
         // Should still return queries
         assert!(
@@ -356,7 +366,10 @@ mod tests {
         lmx_summary.insert("rank_summary".to_string(), rank_summary);
 
         // Call import_into_tasks_table
-        let queries = import_into_tasks_table(&lmx_summary, &sqltypes, &args)?;
+        let queries: Vec<String> = import_into_tasks_table(&lmx_summary, &sqltypes, &args)?
+            .iter()
+            .map(Statement::render)
+            .collect();
 
         // Verify that single quotes are properly escaped (doubled)
         assert!(
@@ -20,11 +20,13 @@ pub(crate) mod check_namd_data;
 #[cfg(test)]
 mod tests {
     use crate::{
-        cmdline::CliArgs,
+        cmdline::{CliArgs, DuplicatePolicy},
         jobdata::table_runs::find_file::project_mockup::test_import_single_lmx_file,
         jobdata::test_import::{
             check_gromacs_data::check_gromacs_data, check_namd_data::check_namd_data,
         },
+        jobdata::{process_lmx_file, ImportStatus},
+        sqltypes::read_sqltypes,
     };
     use anyhow::Result;
     use sqlx::MySql;
@@ -183,4 +185,71 @@ mod tests {
 
         Ok(())
     }
+
+    #[sqlx::test(fixtures(
+        "../../tests/fixtures/tables.sql",
+        "../../tests/fixtures/functs4test.sql"
+    ))]
+    pub async fn test_skip_existing_namd_run(pool: sqlx::Pool<MySql>) -> Result<()> {
+        // Create CliArgs skipping runs that already exist
+        let args = CliArgs {
+            project_file: "project.yml".to_string(),
+            settings_file: "settings.yml".to_string(),
+            module_file: "modules.yml".to_string(),
+            do_import: true,
+            on_duplicate: DuplicatePolicy::Skip,
+            ..Default::default()
+        };
+        let file_name = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/data/NAMD/run_0001/LMX_summary.225250.0.yml");
+        let sqltypes = read_sqltypes(Some(pool.clone()), &args).await?;
+
+        // The first import inserts the run, the second one skips it
+        let outcome = process_lmx_file(
+            file_name.to_str().unwrap(),
+            &Some(pool.clone()),
+            None,
+            &sqltypes,
+            &args,
+        )
+        .await?;
+        assert_eq!(outcome.status, ImportStatus::Imported);
+        assert!(
+            outcome.rid.is_some(),
+            "Expected a run id for a direct import"
+        );
+        assert_eq!(outcome.row_counts["runs"], 1);
+        let outcome = process_lmx_file(
+            file_name.to_str().unwrap(),
+            &Some(pool.clone()),
+            None,
+            &sqltypes,
+            &args,
+        )
+        .await?;
+        assert_eq!(outcome.status, ImportStatus::Skipped);
+
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM `runs`;")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(count.0, 1, "Expected exactly one run, but got {}", count.0);
+
+        // With policy error, the existing run is reported as an error
+        let args = CliArgs {
+            on_duplicate: DuplicatePolicy::Error,
+            ..args
+        };
+        let result = process_lmx_file(
+            file_name.to_str().unwrap(),
+            &Some(pool.clone()),
+            None,
+            &sqltypes,
+            &args,
+        )
+        .await;
+        assert!(result.is_err(), "Expected an error for an existing run");
+
+        check_namd_data(&pool).await?;
+        Ok(())
+    }
 }
@@ -65,7 +65,15 @@ mod test {
             "UPDATE test_table SET col2 = 'val3' WHERE col1 = 'val1';".to_string(),
         ];
         // Call the function to test
-        process_sql_queries(query_list, &pool, &args).await?;
+        process_sql_queries(
+            "LMX_summary.yml",
+            query_list.into_iter().map(Into::into).collect(),
+            &pool,
+            None,
+            None,
+            &args,
+        )
+        .await?;
         // Read and verify the output file
         test_process_sql_file(
             &args.sql_file,
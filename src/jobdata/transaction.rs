@@ -0,0 +1,221 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cmdline::{CliArgs, TxMode};
+use crate::jobdata::statement::Statement;
+use anyhow::{anyhow, Result};
+use sqlx::{Acquire, MySql, Pool, Transaction};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+#[cfg(test)]
+pub(crate) mod test_transaction;
+
+/// Open transaction of a group and the files of the runs it holds
+struct GroupState {
+    tx: Option<Transaction<'static, MySql>>,
+    files: Vec<String>,
+    /// Files of runs executed successfully, but rolled back with their group,
+    /// together with the error of the group
+    failed: HashMap<String, String>,
+}
+
+impl GroupState {
+    /// Drops the open transaction, which rolls back all runs of the group,
+    /// and records `error` for each of their files
+    fn fail_group(&mut self, error: &anyhow::Error) {
+        self.tx = None;
+        for file_name in self.files.drain(..) {
+            self.failed.insert(file_name, format!("{:#}", error));
+        }
+    }
+}
+
+/// Transaction shared by the runs of a group in the grouped transaction modes
+/// `all` and `batch:N`. Runs are executed one at a time, each inside a savepoint
+/// of the shared transaction, so that a failing run is rolled back alone.
+pub struct TransactionGroup {
+    pool: Pool<MySql>,
+    /// Number of runs committed together, `None` for all runs of the batch
+    group_size: Option<usize>,
+    state: Mutex<GroupState>,
+}
+
+impl TransactionGroup {
+    /// Creates the transaction group for `args.tx_mode`.
+    ///
+    /// # Arguments
+    /// * `pool` - Optional MySQL connection pool
+    /// * `args` - Command line arguments with the transaction mode
+    ///
+    /// # Returns
+    /// Returns `None` if each run uses a transaction of its own, i.e. for
+    /// `TxMode::Run`, without a database connection or in dry-run mode
+    ///
+    pub fn new(pool: &Option<Pool<MySql>>, args: &CliArgs) -> Option<Self> {
+        let group_size = match args.tx_mode {
+            TxMode::Run => return None,
+            TxMode::All => None,
+            TxMode::Batch(runs) => Some(runs),
+        };
+        if args.dry_run {
+            return None;
+        }
+        Some(TransactionGroup {
+            pool: pool.clone()?,
+            group_size,
+            state: Mutex::new(GroupState {
+                tx: None,
+                files: Vec::new(),
+                failed: HashMap::new(),
+            }),
+        })
+    }
+
+    /// Executes the queries of a single run inside a savepoint of the group's
+    /// transaction. The transaction is started with the first run of a group
+    /// and committed once the group is complete.
+    ///
+    /// # Arguments
+    /// * `file_name` - The LMX_summary file of the run
+    /// * `query_list` - SQL statements of the run
+    /// * `args` - Command line arguments controlling verbosity
+    ///
+    /// # Returns
+    /// Returns the value of `@rid` if the queries set it, `None` otherwise
+    ///
+    /// # Errors
+    /// Returns the error of the first failing query, after rolling back to the
+    /// savepoint of the run, or an error if the transaction cannot be started or committed.
+    /// If the transaction of the group is lost, i.e. the savepoint cannot be rolled back
+    /// or the commit fails, all runs of the group are recorded as failed, see `take_failed`.
+    ///
+    pub async fn execute_run(
+        &self,
+        file_name: &str,
+        query_list: &[Statement],
+        args: &CliArgs,
+    ) -> Result<Option<i64>> {
+        let mut state = self.state.lock().await;
+        let mut tx = match state.tx.take() {
+            Some(tx) => tx,
+            None => {
+                if args.verbose {
+                    println!("Starting transaction for a group of runs");
+                }
+                self.pool.begin().await?
+            }
+        };
+        let rid = match Self::execute_in_savepoint(&mut tx, query_list, args).await {
+            Ok(result) => {
+                state.tx = Some(tx);
+                result?
+            }
+            Err(e) => {
+                let error = e.context(format!(
+                    "Transaction of a group of {} runs rolled back",
+                    state.files.len() + 1
+                ));
+                state.fail_group(&error);
+                return Err(error);
+            }
+        };
+
+        state.files.push(file_name.to_string());
+        if self
+            .group_size
+            .is_some_and(|size| state.files.len() >= size)
+        {
+            Self::commit_state(&mut state, args).await?;
+        }
+        Ok(rid)
+    }
+
+    /// Executes the queries of a run inside a savepoint, which is released on
+    /// success and rolled back if a query fails.
+    ///
+    /// # Returns
+    /// Returns the result of the run, an error if one of its queries failed
+    ///
+    /// # Errors
+    /// Returns an error if the savepoint cannot be created, rolled back or released,
+    /// e.g. after InnoDB rolled back the whole transaction because of a deadlock
+    ///
+    async fn execute_in_savepoint(
+        tx: &mut Transaction<'static, MySql>,
+        query_list: &[Statement],
+        args: &CliArgs,
+    ) -> Result<Result<Option<i64>>> {
+        let sets_rid = query_list.iter().any(|q| q.sql.starts_with("SET @rid ="));
+        // A nested transaction is a savepoint of the group's transaction
+        let mut savepoint = tx.begin().await?;
+        for query in query_list {
+            if args.verbose {
+                println!("Executing query: {}", query.render());
+            }
+            if let Err(e) = query.to_query().execute(&mut *savepoint).await {
+                savepoint
+                    .rollback()
+                    .await
+                    .map_err(|rollback| anyhow!("{}, then {}", e, rollback))?;
+                return Ok(Err(e.into()));
+            }
+        }
+        let mut rid = None;
+        if sets_rid {
+            rid = sqlx::query_scalar("SELECT CAST(@rid AS SIGNED);")
+                .fetch_one(&mut *savepoint)
+                .await?;
+        }
+        savepoint.commit().await?;
+        Ok(Ok(rid))
+    }
+
+    /// Commits the transaction of the last, possibly incomplete group.
+    ///
+    /// # Errors
+    /// Returns an error if the commit fails, then all runs of the group are rolled back
+    /// and recorded as failed
+    ///
+    pub async fn commit(&self, args: &CliArgs) -> Result<()> {
+        let mut state = self.state.lock().await;
+        Self::commit_state(&mut state, args).await
+    }
+
+    /// Returns the files of runs that were executed successfully, but rolled back
+    /// with their group afterwards, together with the error of the group.
+    /// The runs are forgotten by the group.
+    pub async fn take_failed(&self) -> HashMap<String, String> {
+        std::mem::take(&mut self.state.lock().await.failed)
+    }
+
+    async fn commit_state(state: &mut GroupState, args: &CliArgs) -> Result<()> {
+        if let Some(tx) = state.tx.take() {
+            let runs = state.files.len();
+            if args.verbose {
+                println!("Committing transaction for a group of {} runs", runs);
+            }
+            match tx.commit().await {
+                Ok(()) => state.files.clear(),
+                Err(e) => {
+                    let error = anyhow::Error::new(e)
+                        .context(format!("Failed to commit a group of {} runs", runs));
+                    state.fail_group(&error);
+                    return Err(error);
+                }
+            }
+        }
+        Ok(())
+    }
+}
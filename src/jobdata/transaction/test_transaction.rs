@@ -0,0 +1,191 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::{CliArgs, TxMode};
+    use crate::globbing::find_lmx_summary_files;
+    use crate::jobdata::process_sql_queries;
+    use crate::jobdata::transaction::TransactionGroup;
+    use crate::sqltypes::read_sqltypes;
+    use crate::workers::process_lmx_files;
+    use anyhow::Result;
+    use sqlx::mysql::MySqlPool;
+    use sqlx::MySql;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_group_only_for_grouped_modes() -> Result<()> {
+        // The pool is never connected, creating the group must not access the database
+        let pool = Some(MySqlPool::connect_lazy("mysql://localhost/lmxdb")?);
+        let args = |tx_mode| CliArgs {
+            tx_mode,
+            ..Default::default()
+        };
+        assert!(TransactionGroup::new(&pool, &args(TxMode::Run)).is_none());
+        assert!(TransactionGroup::new(&None, &args(TxMode::All)).is_none());
+        let dry_run = CliArgs {
+            dry_run: true,
+            ..args(TxMode::All)
+        };
+        assert!(TransactionGroup::new(&pool, &dry_run).is_none());
+
+        let group = TransactionGroup::new(&pool, &args(TxMode::Batch(5))).unwrap();
+        assert_eq!(group.group_size, Some(5));
+        let group = TransactionGroup::new(&pool, &args(TxMode::All)).unwrap();
+        assert_eq!(group.group_size, None);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../../tests/fixtures/tables.sql"))]
+    async fn test_failing_run_rolls_back_alone(pool: sqlx::Pool<MySql>) -> Result<()> {
+        let args = CliArgs {
+            tx_mode: TxMode::All,
+            ..Default::default()
+        };
+        let db_pool = Some(pool.clone());
+        let group = TransactionGroup::new(&db_pool, &args).unwrap();
+        let run = |cluster: &str, valid: bool| {
+            let mut query_list = vec![format!(
+                "INSERT INTO clusters (name) VALUES ('{}');",
+                cluster
+            )];
+            if !valid {
+                query_list.push("INSERT INTO no_such_table VALUES (1);".to_string());
+            }
+            query_list.into_iter().map(Into::into).collect::<Vec<_>>()
+        };
+
+        process_sql_queries(
+            "first",
+            run("first", true),
+            &db_pool,
+            Some(&group),
+            None,
+            &args,
+        )
+        .await?;
+        let failed = process_sql_queries(
+            "second",
+            run("second", false),
+            &db_pool,
+            Some(&group),
+            None,
+            &args,
+        )
+        .await;
+        assert!(failed.is_err());
+        process_sql_queries(
+            "third",
+            run("third", true),
+            &db_pool,
+            Some(&group),
+            None,
+            &args,
+        )
+        .await?;
+
+        // Nothing is visible outside of the transaction before the commit
+        let count = |name: &'static str| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM clusters WHERE name = ?")
+                    .bind(name)
+                    .fetch_one(&pool)
+                    .await
+            }
+        };
+        assert_eq!(count("first").await?, 0);
+        group.commit(&args).await?;
+        assert_eq!(count("first").await?, 1);
+        assert_eq!(count("second").await?, 0);
+        assert_eq!(count("third").await?, 1);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("../../../tests/fixtures/tables.sql"))]
+    async fn test_failed_commit_fails_all_runs(pool: sqlx::Pool<MySql>) -> Result<()> {
+        let args = CliArgs {
+            tx_mode: TxMode::All,
+            ..Default::default()
+        };
+        let db_pool = Some(pool.clone());
+        let group = TransactionGroup::new(&db_pool, &args).unwrap();
+        // The rid of a run is the connection of the group's transaction
+        let run = |cluster: &str| {
+            vec![
+                format!("INSERT INTO clusters (name) VALUES ('{}');", cluster),
+                "SET @rid = CONNECTION_ID();".to_string(),
+            ]
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<_>>()
+        };
+
+        let connection =
+            process_sql_queries("first", run("first"), &db_pool, Some(&group), None, &args)
+                .await?
+                .expect("Missing connection id");
+        process_sql_queries("second", run("second"), &db_pool, Some(&group), None, &args).await?;
+        assert!(group.take_failed().await.is_empty());
+
+        // Losing the connection lets the commit fail
+        sqlx::query(&format!("KILL CONNECTION {}", connection))
+            .execute(&pool)
+            .await?;
+        assert!(group.commit(&args).await.is_err());
+        let failed = group.take_failed().await;
+        assert_eq!(failed.len(), 2);
+        assert!(failed["first"].starts_with("Failed to commit a group of 2 runs"));
+        assert!(failed.contains_key("second"));
+
+        let clusters: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM clusters WHERE name IN ('first', 'second')")
+                .fetch_one(&pool)
+                .await?;
+        assert_eq!(clusters, 0);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(
+        "../../../tests/fixtures/tables.sql",
+        "../../../tests/fixtures/functs4test.sql"
+    ))]
+    async fn test_import_in_batches(pool: sqlx::Pool<MySql>) -> Result<()> {
+        let args = CliArgs {
+            project_file: "project.yml".to_string(),
+            settings_file: "settings.yml".to_string(),
+            module_file: "modules.yml".to_string(),
+            do_import: true,
+            tx_mode: TxMode::Batch(2),
+            jobs: 2,
+            ..Default::default()
+        };
+        let db_pool = Some(pool.clone());
+        let sqltypes = read_sqltypes(db_pool.clone(), &args).await?;
+        let list_of_files =
+            find_lmx_summary_files(&vec!["tests/data/NAMD".to_string()], &args)?.files;
+        let results =
+            process_lmx_files(list_of_files, &db_pool, Arc::new(sqltypes), Arc::new(args)).await?;
+        for (file_name, result) in &results {
+            let outcome = result.as_ref().expect("Processing failed");
+            assert!(outcome.rid.is_some(), "Missing run id for {}", file_name);
+        }
+        let runs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM runs")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(runs, 3);
+        Ok(())
+    }
+}
@@ -13,37 +13,88 @@
 // limitations under the License.
 
 use anyhow::Result;
-use connect::{connect_to_database, disconnect_from_database};
+use connect::{connect_to_database, disconnect_from_database, read_max_allowed_packet};
 use sqltypes::SqlTypeHashMap;
 use sqlx::{MySql, Pool};
+use std::process::ExitCode;
+use std::sync::Arc;
+use summary::{ImportSummary, EXIT_CONFIG_ERROR};
 
-use crate::globbing::find_lmx_summary_files;
+use crate::cmdline::Command;
+use crate::globbing::{find_lmx_summary_files, read_file_list};
 
+pub(crate) mod apply;
+pub(crate) mod archive;
 pub(crate) mod cmdline;
+pub(crate) mod config;
 pub(crate) mod connect;
+pub(crate) mod datetime;
+pub(crate) mod db;
 pub(crate) mod globbing;
 pub(crate) mod jobdata;
+pub(crate) mod report;
+pub(crate) mod schema;
 pub(crate) mod sqltypes;
+pub(crate) mod summary;
+pub(crate) mod watch;
+pub(crate) mod workers;
 
 #[cfg(test)]
 mod test_env;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let args = cmdline::parse_args();
+async fn main() -> ExitCode {
+    // Errors that stop lmx2db before any file is processed
+    // are considered configuration errors.
+    let result = run().await;
+    match result {
+        Ok(exit_status) => ExitCode::from(exit_status),
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::from(EXIT_CONFIG_ERROR)
+        }
+    }
+}
+
+/// Runs lmx2db and returns the process exit status
+async fn run() -> Result<u8> {
+    let mut args = cmdline::parse_args()?;
     cmdline::echo_args(&args);
 
-    // Find LMX_SUMMARY files early, before connecting to the database
-    let list_of_files = find_lmx_summary_files(&args.directories)?;
-    if list_of_files.is_empty() && !args.create_sqltypes {
-        println!("No LMX_summary files found in the specified directories.");
-        return Ok(());
+    // Find LMX_SUMMARY files early, before connecting to the database.
+    // In watch mode, the files are searched for repeatedly later on.
+    let mut list_of_files = Vec::new();
+    // Archives are extracted until the files have been processed
+    let mut _archives = Vec::new();
+    if args.command.is_none() {
+        let mut input_paths = args.directories.clone();
+        if let Some(files_from) = &args.files_from {
+            input_paths.extend(read_file_list(files_from)?);
+        }
+        let found = find_lmx_summary_files(&input_paths, &args)?;
+        list_of_files = found.files;
+        _archives = found.archives;
+        if list_of_files.is_empty() && !args.create_sqltypes {
+            println!("No LMX_summary files found in the specified paths.");
+            return Ok(0);
+        }
+    }
+
+    // Sqltypes files from DDL are created without a database connection
+    if let Some(Command::Sqltypes(sqltypes_command)) = &args.command {
+        sqltypes::sqltypes_command(sqltypes_command, &args)?;
+        return Ok(0);
     }
 
     // Connect to the database
     let database_url: String = args.db_url.clone();
     let pool: Option<Pool<MySql>> = if database_url.is_empty() {
-        if !args.create_sqltypes {
+        if !args.create_sqltypes
+            && !matches!(
+                args.command,
+                Some(Command::Schema(_)) | Some(Command::Db(_))
+            )
+        {
             println!("No database URL provided. Will output to file instead.");
         }
         None
@@ -51,12 +102,33 @@ async fn main() -> Result<()> {
         connect_to_database(&database_url).await
     };
 
+    // Load a SQL file written earlier instead of importing LMX files
+    if let Some(Command::Apply(apply_args)) = &args.command {
+        let result = apply::apply_sql_file(apply_args, &pool, &args).await;
+        disconnect_from_database(pool).await;
+        return result;
+    }
+
+    // Create or update the database schema instead of importing LMX files
+    if let Some(Command::Db(db_command)) = &args.command {
+        let result = db::db_command(db_command, &pool, &args).await;
+        disconnect_from_database(pool).await;
+        return result;
+    }
+
+    // Inspect the schemas instead of importing LMX files
+    if let Some(Command::Schema(schema_command)) = &args.command {
+        let result = schema::schema_command(schema_command, &pool, &args).await;
+        disconnect_from_database(pool).await;
+        return result;
+    }
+
     // If create_sqltypes flag is set, create the sqltype file
     // from the database and exit
     if args.create_sqltypes {
         sqltypes::create_sqltype_file(pool.clone(), &args).await?;
         disconnect_from_database(pool).await;
-        return Ok(());
+        return Ok(0);
     }
     // Normal operation: read sqltypes and proceed
     let sqltypes: SqlTypeHashMap = sqltypes::read_sqltypes(pool.clone(), &args).await?;
@@ -64,17 +136,57 @@ async fn main() -> Result<()> {
         println!("Read {} sqltypes from database/file", sqltypes.len());
     }
 
-    // Main loop: process all LMX_SUMMARY files
-    for file_name in list_of_files {
-        println!("Processing file: {}", file_name);
-        let return_code = jobdata::process_lmx_file(&file_name, &pool, &sqltypes, &args).await;
-        match return_code {
-            Ok(_) => {}
-            Err(e) => println!("Ignoring {} because of error:\n     {}", file_name, e),
+    // INSERT statements must not exceed the packet size limit of the server
+    if let Some(db_pool) = pool.as_ref() {
+        let max_allowed_packet = read_max_allowed_packet(db_pool).await?;
+        args.max_insert_bytes = Some(
+            args.max_insert_bytes
+                .map_or(max_allowed_packet, |max_bytes| {
+                    max_bytes.min(max_allowed_packet)
+                }),
+        );
+        if args.verbose || args.dry_run {
+            println!(
+                "Server max_allowed_packet: {} bytes, using at most {} bytes per INSERT",
+                max_allowed_packet,
+                args.max_insert_bytes.unwrap_or_default()
+            );
+        }
+    }
+
+    // A new SQL file starts with a header recording the schema it was checked against
+    if pool.is_none() {
+        let mut sources = match &args.command {
+            Some(Command::Watch(watch_args)) => watch_args.directories.clone(),
+            _ => args.directories.clone(),
+        };
+        if let Some(files_from) = &args.files_from {
+            sources.push(format!("files from {}", files_from));
         }
+        jobdata::sql_output::write_sql_file_header(&sqltypes, &sources, &args)?;
     }
 
+    let args = Arc::new(args);
+    if let Some(Command::Watch(watch_args)) = &args.command {
+        let result =
+            watch::watch_directories(watch_args, &pool, Arc::new(sqltypes), Arc::clone(&args))
+                .await;
+        disconnect_from_database(pool).await;
+        return result.map(|_| 0);
+    }
+
+    // Main loop: process all LMX_SUMMARY files with up to `args.jobs` concurrent workers
+    let results =
+        workers::process_lmx_files(list_of_files, &pool, Arc::new(sqltypes), Arc::clone(&args))
+            .await?;
+
     // Explicit disconnect from the database
     disconnect_from_database(pool).await;
-    Ok(())
+
+    let summary = ImportSummary::from_results(&results);
+    summary.print();
+    if let Some(report_file) = &args.report_file {
+        report::write_report(report_file, &results, &args)?;
+    }
+    Ok(summary.exit_status())
 }
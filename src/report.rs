@@ -0,0 +1,137 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::archive::display_name;
+use crate::cmdline::CliArgs;
+use crate::jobdata::table_runs::foreign_keys::DEFAULT_CLUSTER;
+use crate::jobdata::{ImportOutcome, RunStatus};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+pub(crate) mod test_report;
+
+/// Machine-readable report of all processed LMX_summary files
+#[derive(Serialize, Debug)]
+pub struct ImportReport {
+    pub lmx2db_version: String,
+    pub entries: Vec<ReportEntry>,
+}
+
+/// Report entry for a single LMX_summary file
+#[derive(Serialize, Debug)]
+pub struct ReportEntry {
+    pub file: String,
+    pub status: RunStatus,
+    pub project: Option<String>,
+    pub code: Option<String>,
+    pub code_version: Option<String>,
+    pub test_case: Option<String>,
+    pub cluster: Option<String>,
+    pub person: Option<String>,
+    /// User id from the environ section, used if no person is given
+    pub user: Option<String>,
+    /// Run id assigned by the database, only known for direct imports
+    pub rid: Option<i64>,
+    pub row_counts: BTreeMap<String, usize>,
+    pub warnings: Vec<String>,
+    /// Error chain, outermost error first
+    pub errors: Vec<String>,
+}
+
+/// Creates the report entry for a single LMX_summary file from the data
+/// collected during its import. For failed files, only the errors are known.
+///
+/// # Arguments
+/// * `file_name` - Path to the LMX summary file
+/// * `result` - Result of processing the file
+///
+/// # Returns
+/// Returns the `ReportEntry` for the file
+///
+pub fn create_report_entry(file_name: &str, result: &Result<ImportOutcome>) -> ReportEntry {
+    let mut entry = ReportEntry {
+        file: display_name(file_name),
+        status: RunStatus::of(result),
+        project: None,
+        code: None,
+        code_version: None,
+        test_case: None,
+        cluster: None,
+        person: None,
+        user: None,
+        rid: None,
+        row_counts: BTreeMap::new(),
+        warnings: Vec::new(),
+        errors: Vec::new(),
+    };
+    match result {
+        Ok(outcome) => {
+            if let Some(foreign_keys) = outcome.foreign_keys.clone() {
+                entry.project = Some(foreign_keys.project);
+                entry.code = Some(foreign_keys.code);
+                entry.code_version = Some(foreign_keys.code_version);
+                entry.test_case = Some(foreign_keys.test_case);
+                entry.cluster = Some(
+                    foreign_keys
+                        .cluster
+                        .unwrap_or_else(|| DEFAULT_CLUSTER.to_string()),
+                );
+                entry.person = foreign_keys.person;
+            }
+            entry.user = outcome.user.clone();
+            entry.rid = outcome.rid;
+            entry.row_counts = outcome.row_counts.clone();
+            entry.warnings = outcome.warnings.clone();
+        }
+        Err(e) => {
+            entry.errors = e.chain().map(|cause| cause.to_string()).collect();
+        }
+    }
+    entry
+}
+
+/// Writes the JSON report for all processed LMX_summary files.
+///
+/// # Arguments
+/// * `report_file` - Path of the JSON file to write
+/// * `results` - Pairs of file name and result as returned by the worker pool
+/// * `args` - Reference to command line arguments controlling behavior
+///
+/// # Errors
+/// Returns an error if the report cannot be serialized or written
+///
+pub fn write_report(
+    report_file: &str,
+    results: &[(String, Result<ImportOutcome>)],
+    args: &CliArgs,
+) -> Result<()> {
+    let report = ImportReport {
+        lmx2db_version: env!("CARGO_PKG_VERSION").to_string(),
+        entries: results
+            .iter()
+            .map(|(file_name, result)| create_report_entry(file_name, result))
+            .collect(),
+    };
+    std::fs::write(report_file, serde_json::to_string_pretty(&report)? + "\n")?;
+    if args.verbose || args.dry_run {
+        println!(
+            "Wrote report with {} entries to {}",
+            report.entries.len(),
+            report_file
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,106 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::CliArgs;
+    use crate::jobdata::{process_lmx_file, RunStatus};
+    use crate::report::{create_report_entry, write_report};
+    use crate::sqltypes::read_sqltypes;
+    use anyhow::{anyhow, Result};
+    use tempfile::NamedTempFile;
+
+    fn report_args(sql_file: &str) -> CliArgs {
+        CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            sql_file: sql_file.to_string(),
+            project_file: "project.yml".to_string(),
+            settings_file: "settings.yml".to_string(),
+            module_file: "modules.yml".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_report_entry_for_imported_file() -> Result<()> {
+        // Keep temp_file in scope to prevent automatic deletion until test completes
+        let temp_file = NamedTempFile::new()?;
+        let args = CliArgs {
+            module_file: "not_there.yml".to_string(),
+            ..report_args(temp_file.path().to_str().unwrap())
+        };
+        let sqltypes = read_sqltypes(None, &args).await?;
+        let file_name = "tests/data/NAMD/run_0002/LMX_summary.223561.0.yml";
+
+        let result = process_lmx_file(file_name, &None, None, &sqltypes, &args).await;
+        let entry = create_report_entry(file_name, &result);
+
+        assert_eq!(entry.status, RunStatus::Imported);
+        assert_eq!(entry.project.as_deref(), Some("4paper_2025"));
+        assert_eq!(entry.code.as_deref(), Some("NAMD"));
+        assert_eq!(entry.test_case.as_deref(), Some("STMV"));
+        assert_eq!(entry.cluster.as_deref(), Some("Lenox"));
+        assert_eq!(entry.person.as_deref(), Some("Christoph Pospiech"));
+        // No database connection, hence no run id
+        assert_eq!(entry.rid, None);
+        assert_eq!(entry.row_counts["runs"], 1);
+        assert!(entry.row_counts["mpi"] > 0);
+        assert_eq!(entry.row_counts.len(), 8);
+        // The missing modules file is reported as a warning
+        assert!(
+            entry
+                .warnings
+                .iter()
+                .any(|w| w.contains("Required module file 'not_there.yml' not found")),
+            "Unexpected warnings: {:?}",
+            entry.warnings
+        );
+        assert!(entry.errors.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_file_for_failed_file() -> Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let args = CliArgs {
+            project_file: "not_there.yml".to_string(),
+            ..report_args("import.sql")
+        };
+        let file_name = "tests/data/NAMD/run_0001/LMX_summary.225250.0.yml";
+        let results = vec![(
+            file_name.to_string(),
+            Err(anyhow!("Required project file 'not_there.yml' not found")
+                .context("Cannot import run")),
+        )];
+
+        write_report(temp_file.path().to_str().unwrap(), &results, &args)?;
+
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(temp_file.path())?)?;
+        assert_eq!(report["lmx2db_version"], env!("CARGO_PKG_VERSION"));
+        let entry = &report["entries"][0];
+        assert_eq!(entry["file"], file_name);
+        assert_eq!(entry["status"], "failed");
+        assert!(entry["project"].is_null());
+        assert!(entry["rid"].is_null());
+        assert_eq!(
+            entry["errors"],
+            serde_json::json!([
+                "Cannot import run",
+                "Required project file 'not_there.yml' not found"
+            ])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,516 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cmdline::{CliArgs, SchemaCommand, SchemaDiffArgs, SchemaDumpArgs};
+use crate::sqltypes::{parse_sqltypes, read_sqltypes_from_db, SqlColumn, SqlColumnMap};
+use crate::summary::EXIT_BREAKING_CHANGES;
+use anyhow::{bail, Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sqlx::{MySql, Pool};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub(crate) mod ddl;
+#[cfg(test)]
+pub(crate) mod test_schema;
+
+/// Name of the sqltypes file shipped with lmx2db
+pub const BUNDLED_SQLTYPES_NAME: &str = "sqltypes.yml";
+
+/// The sqltypes file shipped with lmx2db, compiled into the binary. It is used
+/// if the default sqltypes file is not found.
+pub const BUNDLED_SQLTYPES: &str = include_str!("../sqltypes.yml");
+
+/// The tables of the schema shipped with lmx2db, compiled into the binary
+pub const BUNDLED_TABLES_SQL: &str = include_str!("../schema/tables.sql");
+
+/// The files of subdirectory `schema`, compiled into the binary,
+/// in the order they are loaded into a new database
+pub const BUNDLED_SCHEMA_FILES: &[(&str, &str)] = &[
+    ("tables.sql", BUNDLED_TABLES_SQL),
+    ("functions.sql", include_str!("../schema/functions.sql")),
+    ("view.sql", include_str!("../schema/view.sql")),
+    ("mpi_names.sql", include_str!("../schema/mpi_names.sql")),
+    (
+        "minimal_data.sql",
+        include_str!("../schema/minimal_data.sql"),
+    ),
+];
+
+/// Returns the contents of a file compiled into the binary, the sqltypes
+/// file or a file of the schema, with or without the directory `schema/`
+pub fn bundled_file(name: &str) -> Option<&'static str> {
+    if name == BUNDLED_SQLTYPES_NAME {
+        return Some(BUNDLED_SQLTYPES);
+    }
+    let name = name.strip_prefix("schema/").unwrap_or(name);
+    BUNDLED_SCHEMA_FILES
+        .iter()
+        .find(|(file_name, _)| *file_name == name)
+        .map(|(_, content)| *content)
+}
+
+/// Returns the path of a bundled file below the output directory of `schema dump`,
+/// the same as in the lmx2db repository
+fn bundled_file_path(output_dir: &Path, name: &str) -> PathBuf {
+    if name == BUNDLED_SQLTYPES_NAME {
+        output_dir.join(name)
+    } else {
+        output_dir.join("schema").join(name)
+    }
+}
+
+/// Tables lmx2db inserts into directly. Changes of the other tables only
+/// concern the stored functions of the schema.
+pub const WRITTEN_TABLES: &[&str] = &[
+    "runs",
+    "environ",
+    "settings",
+    "tasks",
+    "mpi",
+    "mpi_details",
+    "mmm",
+    "iprof",
+];
+
+/// A source of column descriptions for `schema diff`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaSource {
+    /// The live database given by `-u, --db-url`
+    Database,
+    /// The schema shipped with lmx2db, compiled into the binary
+    Bundled,
+    /// A sqltypes YAML file or, if the name ends in `.sql`, a DDL file
+    File(PathBuf),
+}
+
+impl fmt::Display for SchemaSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaSource::Database => write!(f, "database"),
+            SchemaSource::Bundled => write!(f, "bundled schema"),
+            SchemaSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Parses the value of a schema source: `db`, `bundled` or a file name
+pub fn parse_schema_source(value: &str) -> Result<SchemaSource, String> {
+    match value {
+        "" => Err("The schema source must not be empty".to_string()),
+        "db" => Ok(SchemaSource::Database),
+        "bundled" => Ok(SchemaSource::Bundled),
+        path => Ok(SchemaSource::File(PathBuf::from(path))),
+    }
+}
+
+/// Reads the column descriptions of a schema source
+///
+/// # Arguments
+/// * `source` - The schema source
+/// * `pool` - Optional MySQL connection pool, required for the database
+/// * `args` - Command line arguments controlling verbosity and dry-run mode
+///
+/// # Errors
+/// Returns an error if there is no database connection for the database
+/// source, or the file cannot be read or parsed
+///
+pub async fn read_schema(
+    source: &SchemaSource,
+    pool: &Option<Pool<MySql>>,
+    args: &CliArgs,
+) -> Result<SqlColumnMap> {
+    if args.verbose || args.dry_run {
+        println!("Reading schema from {}", source);
+    }
+    match source {
+        SchemaSource::Database => match pool {
+            Some(pool) => read_sqltypes_from_db(pool.clone(), args).await,
+            None => bail!("Reading the schema from the database requires a database connection"),
+        },
+        SchemaSource::Bundled => ddl::parse_ddl(BUNDLED_TABLES_SQL),
+        SchemaSource::File(path) => read_schema_file(path),
+    }
+}
+
+/// Reads a DDL file ending in `.sql` or a sqltypes YAML file
+fn read_schema_file(path: &Path) -> Result<SqlColumnMap> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read schema file {}", path.display()))?;
+    if path.extension().is_some_and(|extension| extension == "sql") {
+        ddl::parse_ddl(&content)
+    } else {
+        parse_sqltypes(&content)
+    }
+    .with_context(|| format!("Failed to parse schema file {}", path.display()))
+}
+
+/// A difference between two schemas
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaChange {
+    pub table: String,
+    /// The changed column, `None` if the whole table was added or removed
+    pub column: Option<String>,
+    pub description: String,
+    /// Whether the change breaks the imports of lmx2db
+    pub breaking: bool,
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.breaking {
+            "breaking"
+        } else {
+            "compatible"
+        };
+        match &self.column {
+            Some(column) => write!(
+                f,
+                "[{}] {}.{}: {}",
+                kind, self.table, column, self.description
+            ),
+            None => write!(f, "[{}] {}: {}", kind, self.table, self.description),
+        }
+    }
+}
+
+/// Splits an integer type into its rank by size and whether it is unsigned
+fn integer_rank(sql_type: &str) -> Option<(usize, bool)> {
+    let name = sql_type.split(['(', ' ']).next().unwrap_or_default();
+    let rank = ["tinyint", "smallint", "mediumint", "int", "bigint"]
+        .iter()
+        .position(|integer| *integer == name)?;
+    Some((rank, sql_type.contains("unsigned")))
+}
+
+/// Types with a length, e.g. `varchar(32)`
+static SIZED_TYPE_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(varchar|char|varbinary|binary)\((\d+)\)$").unwrap());
+
+/// Types `decimal(p)` and `decimal(p,s)`, optionally unsigned
+static DECIMAL_TYPE_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^decimal\((\d+)(?:,\s*(\d+))?\)( unsigned)?$").unwrap());
+
+/// Whether a column of type `from` can be changed to `to` without breaking
+/// the values lmx2db writes: wider integers of the same signedness, longer
+/// strings or binaries, `double` instead of `float`, decimals with at least as
+/// many integer and fractional digits, and enums with additional members.
+pub fn is_compatible_type_change(from: &str, to: &str) -> bool {
+    // Enum members are compared case-sensitively
+    let enum_members = |sql_type: &str| {
+        SqlColumn {
+            sql_type: sql_type.to_string(),
+            ..Default::default()
+        }
+        .enum_members()
+    };
+    if let (Some(from_members), Some(to_members)) = (enum_members(from), enum_members(to)) {
+        return from_members
+            .iter()
+            .all(|member| to_members.contains(member));
+    }
+    let from = from.to_lowercase();
+    let to = to.to_lowercase();
+    if from == to {
+        return true;
+    }
+    if let (Some((from_rank, from_unsigned)), Some((to_rank, to_unsigned))) =
+        (integer_rank(&from), integer_rank(&to))
+    {
+        return from_unsigned == to_unsigned && from_rank <= to_rank;
+    }
+    if from == "float" && to == "double" {
+        return true;
+    }
+    if let (Some(from_caps), Some(to_caps)) = (
+        SIZED_TYPE_PATTERN.captures(&from),
+        SIZED_TYPE_PATTERN.captures(&to),
+    ) {
+        let from_length: usize = from_caps[2].parse().unwrap();
+        let to_length: usize = to_caps[2].parse().unwrap();
+        let same_kind = from_caps[1] == to_caps[1]
+            || (&from_caps[1] == "char" && &to_caps[1] == "varchar")
+            || (&from_caps[1] == "binary" && &to_caps[1] == "varbinary");
+        return same_kind && from_length <= to_length;
+    }
+    if let (Some(from_caps), Some(to_caps)) = (
+        DECIMAL_TYPE_PATTERN.captures(&from),
+        DECIMAL_TYPE_PATTERN.captures(&to),
+    ) {
+        // Precision and scale, the scale defaults to 0
+        let digits = |caps: &regex::Captures| -> (usize, usize) {
+            let precision: usize = caps[1].parse().unwrap();
+            let scale: usize = caps.get(2).map_or(0, |m| m.as_str().parse().unwrap());
+            (precision, scale)
+        };
+        let (from_precision, from_scale) = digits(&from_caps);
+        let (to_precision, to_scale) = digits(&to_caps);
+        return from_caps.get(3).is_some() == to_caps.get(3).is_some()
+            && from_scale <= to_scale
+            && from_precision.saturating_sub(from_scale) <= to_precision.saturating_sub(to_scale);
+    }
+    false
+}
+
+/// Compares the column descriptions of two schemas. Changes of tables lmx2db
+/// writes (see `WRITTEN_TABLES`) are breaking if lmx2db could not write the
+/// same data to the schema `to` as to the schema `from`: removed tables or
+/// columns, new columns requiring a value, narrower types, columns no
+/// longer accepting NULL and columns requiring a value after losing their
+/// default or `auto_increment`. Defaults and extras are not compared for
+/// flat sqltypes files, which lack them.
+///
+/// # Arguments
+/// * `from` - The schema lmx2db was checked against, e.g. the sqltypes file
+/// * `to` - The schema to compare with, e.g. the live database
+///
+/// # Returns
+/// Returns the changes, sorted by table and column
+///
+pub fn diff_schemas(from: &SqlColumnMap, to: &SqlColumnMap) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    let mut change = |table: &str, column: Option<&str>, description: String, breaking: bool| {
+        changes.push(SchemaChange {
+            table: table.to_string(),
+            column: column.map(str::to_string),
+            description,
+            breaking: breaking && WRITTEN_TABLES.contains(&table),
+        });
+    };
+
+    let mut tables: Vec<&String> = from.keys().chain(to.keys()).collect();
+    tables.sort();
+    tables.dedup();
+    for table in tables {
+        let (from_columns, to_columns) = match (from.get(table), to.get(table)) {
+            (Some(from_columns), Some(to_columns)) => (from_columns, to_columns),
+            (Some(_), None) => {
+                change(table, None, "table removed".to_string(), true);
+                continue;
+            }
+            (None, Some(_)) => {
+                change(table, None, "table added".to_string(), false);
+                continue;
+            }
+            (None, None) => continue,
+        };
+        let mut columns: Vec<&String> = from_columns.keys().chain(to_columns.keys()).collect();
+        columns.sort();
+        columns.dedup();
+        for column in columns {
+            match (from_columns.get(column), to_columns.get(column)) {
+                (Some(_), None) => change(table, Some(column), "column removed".to_string(), true),
+                (None, Some(added)) => change(
+                    table,
+                    Some(column),
+                    format!("column added with type {}", describe(added)),
+                    added.is_required(),
+                ),
+                (Some(old), Some(new)) => {
+                    if old.sql_type != new.sql_type {
+                        change(
+                            table,
+                            Some(column),
+                            format!("type changed from {} to {}", old.sql_type, new.sql_type),
+                            !is_compatible_type_change(&old.sql_type, &new.sql_type),
+                        );
+                    }
+                    if old.nullable != new.nullable {
+                        let description = if new.nullable {
+                            "now accepts NULL"
+                        } else {
+                            "no longer accepts NULL"
+                        };
+                        change(table, Some(column), description.to_string(), !new.nullable);
+                    }
+                    // Flat sqltypes files do not record defaults and extras
+                    if old.flat || new.flat {
+                        continue;
+                    }
+                    // A column losing its default or auto_increment must be filled by lmx2db
+                    let now_required = new.is_required() && !old.is_required();
+                    if old.default != new.default {
+                        change(
+                            table,
+                            Some(column),
+                            format!(
+                                "default changed from {} to {}",
+                                describe_default(old),
+                                describe_default(new)
+                            ),
+                            now_required,
+                        );
+                    }
+                    if old.extra != new.extra {
+                        change(
+                            table,
+                            Some(column),
+                            format!("extra changed from '{}' to '{}'", old.extra, new.extra),
+                            now_required,
+                        );
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+    }
+    changes
+}
+
+/// Describes the default of a column, `none` if there is none
+fn describe_default(column: &SqlColumn) -> String {
+    match &column.default {
+        Some(default) => format!("'{}'", default),
+        None => "none".to_string(),
+    }
+}
+
+/// Describes a column by its type, nullability and default
+fn describe(column: &SqlColumn) -> String {
+    let mut text = column.sql_type.clone();
+    text.push_str(if column.nullable {
+        " NULL"
+    } else {
+        " NOT NULL"
+    });
+    if let Some(default) = &column.default {
+        text.push_str(&format!(" DEFAULT '{}'", default));
+    }
+    if !column.extra.is_empty() {
+        text.push_str(&format!(" {}", column.extra));
+    }
+    text
+}
+
+/// Compares two schema sources and prints the changes
+///
+/// # Returns
+/// Returns the exit status: 0 if there are no breaking changes,
+/// `EXIT_BREAKING_CHANGES` otherwise
+///
+pub async fn diff_command(
+    diff_args: &SchemaDiffArgs,
+    pool: &Option<Pool<MySql>>,
+    args: &CliArgs,
+) -> Result<u8> {
+    let from = read_schema(&diff_args.from, pool, args).await?;
+    let to = read_schema(&diff_args.to, pool, args).await?;
+    let changes = diff_schemas(&from, &to);
+    if changes.is_empty() {
+        println!(
+            "No differences between {} and {}",
+            diff_args.from, diff_args.to
+        );
+        return Ok(0);
+    }
+    println!("Changes from {} to {}:", diff_args.from, diff_args.to);
+    for change in &changes {
+        println!("  {}", change);
+    }
+    let breaking = changes.iter().filter(|change| change.breaking).count();
+    println!(
+        "{} changes, {} breaking for the tables written by lmx2db",
+        changes.len(),
+        breaking
+    );
+    Ok(if breaking > 0 {
+        EXIT_BREAKING_CHANGES
+    } else {
+        0
+    })
+}
+
+/// Prints the files compiled into the binary or, with `--output-dir`, writes
+/// them into the output directory with the layout of the lmx2db repository.
+/// Without file names, all bundled files are dumped.
+///
+/// # Errors
+/// Returns an error for unknown file names, and if a file cannot be written
+/// or already exists in the output directory
+///
+pub fn dump_command(dump_args: &SchemaDumpArgs, args: &CliArgs) -> Result<()> {
+    let mut names: Vec<String> = dump_args.files.clone();
+    if names.is_empty() {
+        names.push(BUNDLED_SQLTYPES_NAME.to_string());
+        names.extend(
+            BUNDLED_SCHEMA_FILES
+                .iter()
+                .map(|(name, _)| format!("schema/{}", name)),
+        );
+    }
+    let mut files = Vec::new();
+    for name in &names {
+        let Some(content) = bundled_file(name) else {
+            bail!(
+                "Unknown bundled file {}, available are {} and {}",
+                name,
+                BUNDLED_SQLTYPES_NAME,
+                BUNDLED_SCHEMA_FILES
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        };
+        files.push((name.strip_prefix("schema/").unwrap_or(name), content));
+    }
+
+    let Some(output_dir) = &dump_args.output_dir else {
+        for (name, content) in files {
+            // Separate several files by a comment naming them
+            if names.len() > 1 {
+                let comment = if name == BUNDLED_SQLTYPES_NAME {
+                    "#"
+                } else {
+                    "--"
+                };
+                println!("{} File {}", comment, name);
+            }
+            print!("{}", content);
+        }
+        return Ok(());
+    };
+    for (name, content) in files {
+        let path = bundled_file_path(Path::new(output_dir), name);
+        if path.exists() {
+            bail!("File {} already exists, not overwritten", path.display());
+        }
+        if args.dry_run {
+            println!("Dry run enabled, not writing {}", path.display());
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        if args.verbose {
+            println!("Wrote {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Runs a subcommand of `schema`
+pub async fn schema_command(
+    command: &SchemaCommand,
+    pool: &Option<Pool<MySql>>,
+    args: &CliArgs,
+) -> Result<u8> {
+    match command {
+        SchemaCommand::Diff(diff_args) => diff_command(diff_args, pool, args).await,
+        SchemaCommand::Dump(dump_args) => dump_command(dump_args, args).map(|_| 0),
+    }
+}
@@ -0,0 +1,528 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::sqltypes::{SqlColumn, SqlColumnMap};
+use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+pub(crate) mod test_ddl;
+
+/// A token of SQL DDL text
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A keyword, an unquoted identifier or a number
+    Word(String),
+    /// An identifier in backquotes
+    Quoted(String),
+    /// A string literal in single or double quotes
+    Str(String),
+    /// Any other character, e.g. parentheses, commas and semicolons
+    Punct(char),
+}
+
+impl Token {
+    /// Whether the token is the keyword `keyword`, ignoring case
+    fn is(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Returns the name of an identifier, quoted or not
+    fn identifier(&self) -> Option<&str> {
+        match self {
+            Token::Word(name) | Token::Quoted(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Splits DDL text into tokens. Comments are skipped, including the
+/// version comments `/*!...*/` written by `mysqldump`.
+fn tokenize(ddl: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = ddl.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_' || c == '$' || c == '.';
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '#' || (c == '-' && next == Some('-')) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            if i >= chars.len() {
+                bail!("Unterminated comment in DDL");
+            }
+            i += 2;
+        } else if c == '`' || c == '\'' || c == '"' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                let Some(&d) = chars.get(i) else {
+                    bail!("Unterminated quote {} in DDL", c);
+                };
+                if d == c {
+                    if chars.get(i + 1) == Some(&c) {
+                        text.push(c);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    break;
+                }
+                if d == '\\' && c != '`' {
+                    if let Some(&escaped) = chars.get(i + 1) {
+                        text.push(match escaped {
+                            'n' => '\n',
+                            't' => '\t',
+                            '0' => '\0',
+                            _ => escaped,
+                        });
+                        i += 2;
+                        continue;
+                    }
+                }
+                text.push(d);
+                i += 1;
+            }
+            tokens.push(if c == '`' {
+                Token::Quoted(text)
+            } else {
+                Token::Str(text)
+            });
+        } else if (is_word_char(c) && c != '.')
+            || ((c == '-' || c == '+') && next.is_some_and(|d| d.is_ascii_digit()))
+        {
+            let start = i;
+            i += 1;
+            // A dot continues a word only within qualified names and numbers
+            while i < chars.len()
+                && is_word_char(chars[i])
+                && (chars[i] != '.' || chars.get(i + 1).is_some_and(|&d| is_word_char(d)))
+            {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else {
+            tokens.push(Token::Punct(c));
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+/// Splits `tokens` at the separator `separator` outside of parentheses
+fn split_top_level(tokens: &[Token], separator: char) -> Vec<&[Token]> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Punct('(') => depth += 1,
+            Token::Punct(')') => depth -= 1,
+            Token::Punct(c) if *c == separator && depth == 0 => {
+                parts.push(&tokens[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&tokens[start..]);
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Returns the tokens inside the parentheses starting at `tokens[open]`
+/// and the index after the closing parenthesis
+fn parenthesized(tokens: &[Token], open: usize) -> Result<(&[Token], usize)> {
+    if tokens.get(open) != Some(&Token::Punct('(')) {
+        bail!("Expected '(' in DDL");
+    }
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Token::Punct('(') => depth += 1,
+            Token::Punct(')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok((&tokens[open + 1..i], i + 1));
+                }
+            }
+            _ => {}
+        }
+    }
+    bail!("Unbalanced parentheses in DDL")
+}
+
+/// Returns the column names of an index or foreign key, i.e. the first
+/// identifier of each element of the parenthesized list at `tokens[open]`.
+/// Prefix lengths and sort orders are ignored.
+fn column_list(tokens: &[Token], open: usize) -> Result<(Vec<String>, usize)> {
+    let (inner, end) = parenthesized(tokens, open)?;
+    let columns = split_top_level(inner, ',')
+        .iter()
+        .filter_map(|part| part[0].identifier().map(str::to_string))
+        .collect();
+    Ok((columns, end))
+}
+
+/// Renders tokens of a type argument list or an expression as SQL text
+fn render(tokens: &[Token]) -> String {
+    let mut text = String::new();
+    for token in tokens {
+        match token {
+            Token::Word(word) => {
+                if text.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                    text.push(' ');
+                }
+                text.push_str(word);
+            }
+            Token::Quoted(name) => text.push_str(&format!("`{}`", name)),
+            Token::Str(value) => text.push_str(&format!("'{}'", value.replace('\'', "''"))),
+            Token::Punct(c) => text.push(*c),
+        }
+    }
+    text
+}
+
+/// Normalizes a column type the way `SHOW COLUMNS` displays it: lower case
+/// type names, aliases resolved and the default display width of integers
+fn normalize_type(name: &str, arguments: Option<String>, unsigned: bool, zerofill: bool) -> String {
+    let name = name.to_lowercase();
+    let (name, arguments) = match name.as_str() {
+        "bool" | "boolean" => ("tinyint".to_string(), Some("1".to_string())),
+        "integer" => ("int".to_string(), arguments),
+        "real" | "double precision" => ("double".to_string(), arguments),
+        "dec" | "numeric" | "fixed" => (
+            "decimal".to_string(),
+            arguments.or_else(|| Some("10,0".to_string())),
+        ),
+        "decimal" => (name, arguments.or_else(|| Some("10,0".to_string()))),
+        _ => (name, arguments),
+    };
+    // Display widths of integer types without an explicit one
+    let unsigned = unsigned || zerofill;
+    let arguments = arguments.or_else(|| {
+        let width = match (name.as_str(), unsigned) {
+            ("tinyint", false) => 4,
+            ("tinyint", true) => 3,
+            ("smallint", false) => 6,
+            ("smallint", true) => 5,
+            ("mediumint", false) => 9,
+            ("mediumint", true) => 8,
+            ("int", false) => 11,
+            ("int", true) => 10,
+            ("bigint", _) => 20,
+            _ => return None,
+        };
+        Some(width.to_string())
+    });
+    let mut sql_type = match arguments {
+        Some(arguments) => format!("{}({})", name, arguments),
+        None => name,
+    };
+    if unsigned {
+        sql_type.push_str(" unsigned");
+    }
+    if zerofill {
+        sql_type.push_str(" zerofill");
+    }
+    sql_type
+}
+
+/// Parses a column definition, e.g.
+/// `` `clid` int(11) NOT NULL DEFAULT 1 COMMENT 'Foreign key from clusters' ``
+fn parse_column(definition: &[Token]) -> Result<(String, SqlColumn)> {
+    let name = definition[0]
+        .identifier()
+        .ok_or_else(|| anyhow::anyhow!("Expected a column name in DDL"))?
+        .to_string();
+    let Some(Token::Word(type_name)) = definition.get(1) else {
+        bail!("Expected the type of column {} in DDL", name);
+    };
+    let mut type_name = type_name.clone();
+    let mut i = 2;
+    if type_name.eq_ignore_ascii_case("double")
+        && definition.get(i).is_some_and(|t| t.is("PRECISION"))
+    {
+        type_name.push_str(" precision");
+        i += 1;
+    }
+    let mut arguments = None;
+    if definition.get(i) == Some(&Token::Punct('(')) {
+        let (inner, end) = parenthesized(definition, i)?;
+        arguments = Some(render(inner));
+        i = end;
+    }
+    let mut unsigned = false;
+    let mut zerofill = false;
+    loop {
+        match definition.get(i) {
+            Some(token) if token.is("UNSIGNED") => unsigned = true,
+            Some(token) if token.is("SIGNED") => {}
+            Some(token) if token.is("ZEROFILL") => zerofill = true,
+            _ => break,
+        }
+        i += 1;
+    }
+    let mut column = SqlColumn {
+        sql_type: normalize_type(&type_name, arguments, unsigned, zerofill),
+        nullable: true,
+        ..Default::default()
+    };
+
+    while i < definition.len() {
+        let token = &definition[i];
+        i += 1;
+        if token.is("NOT") && definition.get(i).is_some_and(|t| t.is("NULL")) {
+            column.nullable = false;
+            i += 1;
+        } else if token.is("NULL") {
+            column.nullable = true;
+        } else if token.is("DEFAULT") {
+            match definition.get(i) {
+                Some(Token::Str(value)) => column.default = Some(value.clone()),
+                Some(token) if token.is("NULL") => column.default = None,
+                Some(Token::Word(word)) => {
+                    column.default = Some(match word.to_uppercase().as_str() {
+                        "CURRENT_TIMESTAMP" | "NOW" | "LOCALTIMESTAMP" => {
+                            "current_timestamp()".to_string()
+                        }
+                        _ => word.clone(),
+                    });
+                    // Skip the arguments of functions like current_timestamp()
+                    if definition.get(i + 1) == Some(&Token::Punct('(')) {
+                        i = parenthesized(definition, i + 1)?.1 - 1;
+                    }
+                }
+                Some(Token::Punct('(')) => {
+                    let (inner, end) = parenthesized(definition, i)?;
+                    column.default = Some(render(inner));
+                    i = end - 1;
+                }
+                _ => bail!("Expected a default value of column {} in DDL", name),
+            }
+            i += 1;
+        } else if token.is("AUTO_INCREMENT") {
+            column.extra = "auto_increment".to_string();
+        } else if token.is("ON") && definition.get(i).is_some_and(|t| t.is("UPDATE")) {
+            // ON UPDATE CURRENT_TIMESTAMP[(precision)]
+            i += 2;
+            if definition.get(i) == Some(&Token::Punct('(')) {
+                i = parenthesized(definition, i)?.1;
+            }
+            column.extra = "on update current_timestamp()".to_string();
+        } else if token.is("PRIMARY") {
+            column.key = "PRI".to_string();
+            column.nullable = false;
+            if definition.get(i).is_some_and(|t| t.is("KEY")) {
+                i += 1;
+            }
+        } else if token.is("UNIQUE") {
+            if column.key.is_empty() {
+                column.key = "UNI".to_string();
+            }
+            if definition.get(i).is_some_and(|t| t.is("KEY")) {
+                i += 1;
+            }
+        } else if token.is("COMMENT")
+            || token.is("COLLATE")
+            || token.is("CHARSET")
+            || token.is("CHARACTER")
+        {
+            // Skip the value, after SET for CHARACTER SET
+            if token.is("CHARACTER") {
+                i += 1;
+            }
+            i += 1;
+        } else if token.is("REFERENCES") {
+            column.references = definition
+                .get(i)
+                .and_then(Token::identifier)
+                .map(table_name);
+            i += 1;
+        }
+    }
+    Ok((name, column))
+}
+
+/// Returns the table name of a possibly qualified name `database.table`
+fn table_name(name: &str) -> String {
+    name.rsplit('.').next().unwrap_or(name).to_string()
+}
+
+/// Parses the body of a CREATE TABLE statement, i.e. the column and index
+/// definitions between the outer parentheses
+fn parse_table_body(table: &str, body: &[Token]) -> Result<BTreeMap<String, SqlColumn>> {
+    let mut columns: BTreeMap<String, SqlColumn> = BTreeMap::new();
+    // Indexes are applied after all columns are known, in the order of
+    // precedence of `SHOW COLUMNS`: PRI, UNI, MUL
+    let mut primary: Vec<String> = Vec::new();
+    let mut unique: Vec<Vec<String>> = Vec::new();
+    let mut multiple: Vec<String> = Vec::new();
+    let mut references: Vec<(String, String)> = Vec::new();
+
+    for definition in split_top_level(body, ',') {
+        let mut start = 0;
+        if definition[0].is("CONSTRAINT") {
+            // The constraint name is optional
+            start = if definition.get(1).is_some_and(|t| {
+                t.is("PRIMARY") || t.is("UNIQUE") || t.is("FOREIGN") || t.is("CHECK")
+            }) {
+                1
+            } else {
+                2
+            };
+        }
+        let Some(first) = definition.get(start) else {
+            bail!("Incomplete constraint in table {}", table);
+        };
+        let open = definition
+            .iter()
+            .position(|t| *t == Token::Punct('('))
+            .unwrap_or(definition.len());
+        if first.is("PRIMARY") {
+            primary.extend(column_list(definition, open)?.0);
+        } else if first.is("UNIQUE") {
+            unique.push(column_list(definition, open)?.0);
+        } else if first.is("KEY")
+            || first.is("INDEX")
+            || first.is("FULLTEXT")
+            || first.is("SPATIAL")
+        {
+            multiple.extend(column_list(definition, open)?.0.into_iter().take(1));
+        } else if first.is("FOREIGN") {
+            let (foreign, end) = column_list(definition, open)?;
+            let Some(referenced) = definition
+                .get(end + 1)
+                .filter(|_| definition.get(end).is_some_and(|t| t.is("REFERENCES")))
+                .and_then(Token::identifier)
+            else {
+                bail!("Expected REFERENCES in foreign key of table {}", table);
+            };
+            // InnoDB creates an index for the foreign key if there is none
+            multiple.extend(foreign.iter().take(1).cloned());
+            for column in foreign {
+                references.push((column, table_name(referenced)));
+            }
+        } else if first.is("CHECK") || first.is("PERIOD") {
+            continue;
+        } else if start == 0 {
+            let (name, column) = parse_column(definition)?;
+            columns.insert(name, column);
+        }
+    }
+
+    let mut set_key = |column: &str, key: &str| -> Result<()> {
+        let Some(description) = columns.get_mut(column) else {
+            bail!("Index on unknown column {} in table {}", column, table);
+        };
+        if description.key.is_empty() {
+            description.key = key.to_string();
+        }
+        if key == "PRI" {
+            description.nullable = false;
+        }
+        Ok(())
+    };
+    for column in &primary {
+        set_key(column, "PRI")?;
+    }
+    for index in &unique {
+        let key = if index.len() == 1 { "UNI" } else { "MUL" };
+        set_key(&index[0], key)?;
+    }
+    for column in &multiple {
+        set_key(column, "MUL")?;
+    }
+    for (column, referenced) in references {
+        let Some(description) = columns.get_mut(&column) else {
+            bail!(
+                "Foreign key on unknown column {} in table {}",
+                column,
+                table
+            );
+        };
+        description.references = Some(referenced);
+    }
+    Ok(columns)
+}
+
+/// Parses the `CREATE TABLE` statements of SQL DDL, e.g. `schema/tables.sql`
+/// or the output of `mysqldump --no-data`, into column descriptions as
+/// `SHOW COLUMNS` would return them. All other statements are ignored.
+///
+/// # Arguments
+/// * `ddl` - The SQL text
+///
+/// # Returns
+/// Returns the column descriptions of all created tables
+///
+/// # Errors
+/// Returns an error if a CREATE TABLE statement cannot be parsed
+///
+pub fn parse_ddl(ddl: &str) -> Result<SqlColumnMap> {
+    let tokens = tokenize(ddl)?;
+    let mut tables: SqlColumnMap = BTreeMap::new();
+    for statement in split_top_level(&tokens, ';') {
+        let mut i = 0;
+        if !statement[i].is("CREATE") {
+            continue;
+        }
+        i += 1;
+        if statement.get(i).is_some_and(|t| t.is("OR"))
+            && statement.get(i + 1).is_some_and(|t| t.is("REPLACE"))
+        {
+            i += 2;
+        }
+        if statement.get(i).is_some_and(|t| t.is("TEMPORARY")) {
+            continue;
+        }
+        if !statement.get(i).is_some_and(|t| t.is("TABLE")) {
+            continue;
+        }
+        i += 1;
+        if statement.get(i).is_some_and(|t| t.is("IF")) {
+            // IF NOT EXISTS
+            i += 3;
+        }
+        let Some(mut name) = statement.get(i).and_then(Token::identifier).map(table_name) else {
+            bail!("Expected a table name after CREATE TABLE");
+        };
+        i += 1;
+        // `database`.`table`
+        if statement.get(i) == Some(&Token::Punct('.')) {
+            name = statement
+                .get(i + 1)
+                .and_then(Token::identifier)
+                .map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("Expected a table name after CREATE TABLE"))?;
+            i += 2;
+        }
+        if statement.get(i) != Some(&Token::Punct('(')) {
+            bail!("Table {} is not created by column definitions", name);
+        }
+        let (body, _) = parenthesized(statement, i)?;
+        let columns = parse_table_body(&name, body)?;
+        tables.insert(name, columns);
+    }
+    Ok(tables)
+}
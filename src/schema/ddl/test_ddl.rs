@@ -0,0 +1,119 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::ddl::parse_ddl;
+    use crate::sqltypes::sqltype_hashmap::column_types;
+    use crate::sqltypes::{parse_sqltypes, SqlColumn};
+    use anyhow::Result;
+
+    const DDL: &str = r#"
+-- Comment with a ; semicolon
+/*!40101 SET NAMES utf8mb4 */;
+DROP TABLE IF EXISTS `runs`;
+CREATE TABLE IF NOT EXISTS lmxdb.`runs` (
+  `rid` int NOT NULL AUTO_INCREMENT,
+  `clid` int(11) NOT NULL DEFAULT 1 COMMENT 'Foreign key, from clusters',
+  `name` varchar(32) CHARACTER SET utf8mb3 COLLATE utf8mb3_general_ci NOT NULL DEFAULT 'it''s',
+  `flag` BOOLEAN DEFAULT NULL,
+  `count` INTEGER UNSIGNED NOT NULL DEFAULT -0,
+  `price` DECIMAL(10, 2) DEFAULT '0.00',
+  `kind` enum('a,b','c') NOT NULL,
+  `created` timestamp NOT NULL DEFAULT current_timestamp() ON UPDATE current_timestamp(),
+  `md5sum` binary(16) DEFAULT NULL,
+  PRIMARY KEY (`rid`),
+  UNIQUE KEY `name_inx` (`name`),
+  KEY `kind_inx` (`kind`, `name`(10)),
+  CONSTRAINT `runs_ibfk_1` FOREIGN KEY (`clid`) REFERENCES `clusters` (`id`) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb3;
+"#;
+
+    #[test]
+    fn test_parse_ddl_columns() -> Result<()> {
+        let tables = parse_ddl(DDL)?;
+        assert_eq!(tables.keys().collect::<Vec<_>>(), vec!["runs"]);
+        let runs = &tables["runs"];
+        assert_eq!(
+            runs["rid"],
+            SqlColumn {
+                sql_type: "int(11)".to_string(),
+                nullable: false,
+                key: "PRI".to_string(),
+                extra: "auto_increment".to_string(),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            runs["clid"],
+            SqlColumn {
+                sql_type: "int(11)".to_string(),
+                nullable: false,
+                default: Some("1".to_string()),
+                key: "MUL".to_string(),
+                references: Some("clusters".to_string()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(runs["name"].sql_type, "varchar(32)");
+        assert_eq!(runs["name"].default.as_deref(), Some("it's"));
+        assert_eq!(runs["name"].key, "UNI");
+        assert_eq!(runs["flag"].sql_type, "tinyint(1)");
+        assert!(runs["flag"].nullable);
+        assert_eq!(runs["flag"].default, None);
+        assert_eq!(runs["count"].sql_type, "int(10) unsigned");
+        assert_eq!(runs["count"].default.as_deref(), Some("-0"));
+        assert_eq!(runs["price"].sql_type, "decimal(10,2)");
+        assert_eq!(runs["kind"].sql_type, "enum('a,b','c')");
+        assert_eq!(runs["kind"].key, "MUL");
+        assert_eq!(
+            runs["created"].default.as_deref(),
+            Some("current_timestamp()")
+        );
+        assert_eq!(runs["created"].extra, "on update current_timestamp()");
+        assert!(runs["md5sum"].nullable);
+        assert!(runs["md5sum"].key.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ddl_errors() {
+        assert!(parse_ddl("CREATE TABLE t (a int").is_err());
+        assert!(parse_ddl("CREATE TABLE t (a int, KEY k (b))").is_err());
+        assert!(parse_ddl("CREATE TABLE t LIKE s;").is_err());
+        assert!(parse_ddl("CREATE TABLE t (a varchar(8) DEFAULT 'x);").is_err());
+        // Statements other than CREATE TABLE are ignored
+        assert!(
+            parse_ddl("CREATE VIEW v AS SELECT 1; INSERT INTO t VALUES (1);")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_parse_bundled_schema() -> Result<()> {
+        let tables = parse_ddl(&std::fs::read_to_string("schema/tables.sql")?)?;
+        // The shipped sqltypes file was read from a database created from
+        // the bundled schema, it only records types and nullability
+        let shipped = parse_sqltypes(&std::fs::read_to_string("sqltypes.yml")?)?;
+        assert_eq!(column_types(&tables), column_types(&shipped));
+        assert!(tables["runs"]["perf_unit"].nullable);
+        assert_eq!(tables["runs"]["md5sum_exe"].sql_type, "binary(16)");
+        assert_eq!(
+            tables["mpi_details"]["rid"].references.as_deref(),
+            Some("runs")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::{CliArgs, SchemaDumpArgs};
+    use crate::schema::ddl::parse_ddl;
+    use crate::schema::{
+        bundled_file, diff_schemas, dump_command, is_compatible_type_change, parse_schema_source,
+        read_schema, SchemaSource, BUNDLED_SCHEMA_FILES,
+    };
+    use anyhow::Result;
+    use std::path::PathBuf;
+
+    const OLD: &str = "
+CREATE TABLE runs (
+  rid int(11) NOT NULL AUTO_INCREMENT,
+  name varchar(32) NOT NULL,
+  jobid varchar(64) DEFAULT NULL,
+  nodes int(11) NOT NULL,
+  queue varchar(8) NOT NULL DEFAULT 'a',
+  status varchar(8) NOT NULL DEFAULT 'ok',
+  PRIMARY KEY (rid)
+);
+CREATE TABLE files (id int(11) NOT NULL, name varchar(32) NOT NULL);
+CREATE TABLE mpi (rid int(11) NOT NULL, calls int(11) NOT NULL);
+";
+
+    const NEW: &str = "
+CREATE TABLE runs (
+  rid int(11) NOT NULL AUTO_INCREMENT,
+  name varchar(64) NOT NULL,
+  jobid varchar(64) NOT NULL,
+  nodes smallint(6) NOT NULL,
+  comment varchar(256) NOT NULL DEFAULT '',
+  owner varchar(32) NOT NULL,
+  queue varchar(8) NOT NULL DEFAULT 'b',
+  status varchar(8) NOT NULL,
+  PRIMARY KEY (rid)
+);
+CREATE TABLE files (id int(11) NOT NULL);
+CREATE TABLE power (rid int(11) NOT NULL);
+";
+
+    #[test]
+    fn test_diff_schemas() -> Result<()> {
+        let changes = diff_schemas(&parse_ddl(OLD)?, &parse_ddl(NEW)?);
+        let lines: Vec<String> = changes.iter().map(|change| change.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "[compatible] files.name: column removed",
+                "[breaking] mpi: table removed",
+                "[compatible] power: table added",
+                "[compatible] runs.comment: column added with type varchar(256) NOT NULL DEFAULT ''",
+                "[breaking] runs.jobid: no longer accepts NULL",
+                "[compatible] runs.name: type changed from varchar(32) to varchar(64)",
+                "[breaking] runs.nodes: type changed from int(11) to smallint(6)",
+                "[breaking] runs.owner: column added with type varchar(32) NOT NULL",
+                "[compatible] runs.queue: default changed from 'a' to 'b'",
+                "[breaking] runs.status: default changed from 'ok' to none",
+            ]
+        );
+        assert!(diff_schemas(&parse_ddl(OLD)?, &parse_ddl(OLD)?).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compatible_type_changes() {
+        assert!(is_compatible_type_change("int(11)", "bigint(20)"));
+        assert!(is_compatible_type_change(
+            "int(11) unsigned",
+            "int(10) unsigned"
+        ));
+        assert!(!is_compatible_type_change("int(11) unsigned", "bigint(20)"));
+        assert!(is_compatible_type_change("float", "double"));
+        assert!(!is_compatible_type_change("double", "float"));
+        assert!(is_compatible_type_change("char(8)", "varchar(8)"));
+        assert!(is_compatible_type_change(
+            "varbinary(16)",
+            "varbinary(4096)"
+        ));
+        assert!(!is_compatible_type_change("varbinary(16)", "varchar(16)"));
+        assert!(is_compatible_type_change(
+            "enum('AC','DC')",
+            "enum('AC','DC','other')"
+        ));
+        assert!(!is_compatible_type_change("enum('AC','DC')", "enum('AC')"));
+        // Members with commas and quotes are compared as a whole
+        assert!(is_compatible_type_change(
+            "enum('a,b','it''s')",
+            "enum('it''s','a,b','c')"
+        ));
+        assert!(!is_compatible_type_change("enum('a,b')", "enum('a','b')"));
+        assert!(!is_compatible_type_change("enum('AC')", "enum('ac')"));
+        assert!(is_compatible_type_change("decimal(10,2)", "decimal(12,2)"));
+        assert!(is_compatible_type_change("decimal(10)", "decimal(12,2)"));
+        assert!(!is_compatible_type_change("decimal(12,2)", "decimal(10,2)"));
+        assert!(!is_compatible_type_change("decimal(10,2)", "decimal(10,4)"));
+        assert!(!is_compatible_type_change(
+            "decimal(10,2)",
+            "decimal(12,2) unsigned"
+        ));
+        assert!(!is_compatible_type_change("varchar(32)", "int(11)"));
+    }
+
+    #[tokio::test]
+    async fn test_read_schema_sources() -> Result<()> {
+        assert_eq!(parse_schema_source("db"), Ok(SchemaSource::Database));
+        assert_eq!(parse_schema_source("bundled"), Ok(SchemaSource::Bundled));
+        assert!(parse_schema_source("").is_err());
+
+        let args = CliArgs::default();
+        let bundled = read_schema(&SchemaSource::Bundled, &None, &args).await?;
+        let shipped =
+            read_schema(&parse_schema_source("sqltypes.yml").unwrap(), &None, &args).await?;
+        // The shipped sqltypes file matches the bundled schema
+        let changes = diff_schemas(&shipped, &bundled);
+        assert!(changes.is_empty(), "{:?}", changes);
+
+        let err = read_schema(&SchemaSource::Database, &None, &args)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Reading the schema from the database requires a database connection"
+        );
+        let missing = SchemaSource::File(PathBuf::from("does_not_exist.sql"));
+        assert!(read_schema(&missing, &None, &args).await.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundled_files() -> Result<()> {
+        assert_eq!(
+            bundled_file("sqltypes.yml"),
+            Some(std::fs::read_to_string("sqltypes.yml")?.as_str())
+        );
+        for (name, content) in BUNDLED_SCHEMA_FILES {
+            let path = format!("schema/{}", name);
+            assert_eq!(std::fs::read_to_string(&path)?, *content);
+            assert_eq!(bundled_file(&path), Some(*content));
+            assert_eq!(bundled_file(name), Some(*content));
+        }
+        assert_eq!(bundled_file("schema/sqltypes.yml"), None);
+        assert_eq!(bundled_file("Cargo.toml"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dump_to_directory() -> Result<()> {
+        let output_dir = tempfile::tempdir()?;
+        let dump_args = SchemaDumpArgs {
+            output_dir: Some(output_dir.path().to_string_lossy().to_string()),
+            files: Vec::new(),
+        };
+        let args = CliArgs::default();
+        dump_command(&dump_args, &args)?;
+        assert_eq!(
+            std::fs::read_to_string(output_dir.path().join("sqltypes.yml"))?,
+            std::fs::read_to_string("sqltypes.yml")?
+        );
+        for (name, content) in BUNDLED_SCHEMA_FILES {
+            let path = output_dir.path().join("schema").join(name);
+            assert_eq!(std::fs::read_to_string(path)?, *content);
+        }
+
+        // Existing files are not overwritten
+        let err = dump_command(&dump_args, &args).unwrap_err();
+        assert!(err.to_string().ends_with("already exists, not overwritten"));
+
+        let unknown = SchemaDumpArgs {
+            output_dir: None,
+            files: vec!["tables.sql".to_string(), "other.sql".to_string()],
+        };
+        let err = dump_command(&unknown, &args).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "Unknown bundled file other.sql, available are sqltypes.yml and tables.sql"
+        ));
+        Ok(())
+    }
+}
@@ -12,17 +12,224 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cmdline::CliArgs;
-use anyhow::Result;
+use crate::cmdline::{CliArgs, SqltypesCommand};
+use crate::db::SCHEMA_VERSION_TABLE;
+use crate::jobdata::statement::quote_string;
+use crate::schema::ddl::parse_ddl;
+use crate::schema::BUNDLED_SQLTYPES;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::{MySql, Pool};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 
-pub type SqlTypeHashMap = HashMap<String, HashMap<String, String>>;
+/// Column descriptions by table and column name, as used for the type checks
+pub type SqlTypeHashMap = HashMap<String, HashMap<String, SqlColumn>>;
+
+/// Version of the sqltypes file format written by `create_sqltype_file`
+/// and `create_sqltype_file_from_ddl`.
+/// Files without a version are flat `table: {column: type}` maps.
+pub const SQLTYPES_VERSION: u32 = 2;
+
+/// Description of a single column as given by `SHOW COLUMNS`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SqlColumn {
+    /// SQL type of the column, e.g. `int(11) unsigned`
+    #[serde(rename = "type")]
+    pub sql_type: String,
+    /// Whether the column accepts NULL
+    #[serde(default)]
+    pub nullable: bool,
+    /// Default value, `None` if there is no default or the default is NULL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Index of the column: `PRI`, `UNI` or `MUL`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub key: String,
+    /// Extra information, e.g. `auto_increment`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub extra: String,
+    /// Table referenced by a foreign key on this column
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub references: Option<String>,
+    /// Whether the column comes from a flat sqltypes file,
+    /// which lacks the default, key, extra and references
+    #[serde(skip)]
+    pub flat: bool,
+}
+
+impl SqlColumn {
+    /// Creates a column of a flat sqltypes file from its type,
+    /// i.e. the SQL type possibly followed by `NULLABLE_SUFFIX`
+    pub fn from_column_type(column_type: &str) -> Self {
+        let (sql_type, nullable) = split_nullable(column_type);
+        SqlColumn {
+            sql_type: sql_type.to_string(),
+            nullable,
+            flat: true,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the column type as written to flat sqltypes files,
+    /// i.e. the SQL type followed by `NULLABLE_SUFFIX` if the column accepts NULL
+    pub fn column_type(&self) -> String {
+        if self.nullable {
+            format!("{}{}", self.sql_type, NULLABLE_SUFFIX)
+        } else {
+            self.sql_type.clone()
+        }
+    }
+
+    /// Whether an INSERT must give a value for the column: it does not
+    /// accept NULL, has no default and is not filled by `auto_increment`.
+    /// Columns from flat sqltypes files never have a default, so they
+    /// are not considered required.
+    pub fn is_required(&self) -> bool {
+        !self.flat
+            && !self.nullable
+            && self.default.is_none()
+            && !self.extra.contains("auto_increment")
+    }
+
+    /// Returns the members of an enum type like `enum('AC','DC')`,
+    /// `None` if the type is not an enum. Quotes inside members are
+    /// doubled, as in the output of `SHOW COLUMNS`.
+    pub fn enum_members(&self) -> Option<Vec<String>> {
+        let lower = self.sql_type.to_lowercase();
+        if !lower.starts_with("enum(") {
+            return None;
+        }
+        let mut members = Vec::new();
+        let mut chars = self.sql_type["enum(".len()..].chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '\'' {
+                continue;
+            }
+            let mut member = String::new();
+            while let Some(c) = chars.next() {
+                if c == '\'' {
+                    if chars.peek() == Some(&'\'') {
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                member.push(c);
+            }
+            members.push(member);
+        }
+        Some(members)
+    }
+}
+
+/// Column descriptions by table and column name
+pub type SqlColumnMap = BTreeMap<String, BTreeMap<String, SqlColumn>>;
+
+/// Contents of a versioned sqltypes file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SqlTypesFile {
+    pub version: u32,
+    pub tables: SqlColumnMap,
+}
+
+/// Converts the column descriptions into the map used for the type checks
+pub fn to_sqltype_hashmap(columns: &SqlColumnMap) -> SqlTypeHashMap {
+    columns
+        .iter()
+        .map(|(table, table_columns)| {
+            let table_columns = table_columns
+                .iter()
+                .map(|(column, description)| (column.clone(), description.clone()))
+                .collect();
+            (table.clone(), table_columns)
+        })
+        .collect()
+}
+
+/// Parses the contents of a sqltypes file, either versioned or a flat
+/// `table: {column: type}` map.
+///
+/// # Errors
+/// Returns an error if the YAML is invalid or the version is not supported
+///
+pub fn parse_sqltypes(yaml_string: &str) -> Result<SqlColumnMap> {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(yaml_string)?;
+    if yaml.get("version").is_some() {
+        let file: SqlTypesFile = serde_yaml::from_value(yaml)?;
+        if file.version > SQLTYPES_VERSION {
+            bail!(
+                "Sqltypes file version {} is not supported, the latest known version is {}",
+                file.version,
+                SQLTYPES_VERSION
+            );
+        }
+        return Ok(file.tables);
+    }
+    let flat: HashMap<String, HashMap<String, String>> = serde_yaml::from_value(yaml)?;
+    Ok(flat
+        .into_iter()
+        .map(|(table, types)| {
+            let table_columns = types
+                .into_iter()
+                .map(|(column, column_type)| (column, SqlColumn::from_column_type(&column_type)))
+                .collect();
+            (table, table_columns)
+        })
+        .collect())
+}
+
+/// Returns the required columns (see `SqlColumn::is_required`) of `table`
+/// which are missing in `columns`, sorted by name. The database rejects
+/// an INSERT of only `columns` in strict mode.
+///
+/// # Arguments
+/// * `table` - The name of the table
+/// * `columns` - The columns given a value by an INSERT
+/// * `sqltypes` - The column descriptions of the database schema
+///
+pub fn unfilled_columns(table: &str, columns: &[String], sqltypes: &SqlTypeHashMap) -> Vec<String> {
+    let Some(table_columns) = sqltypes.get(table) else {
+        return Vec::new();
+    };
+    let mut unfilled: Vec<String> = table_columns
+        .iter()
+        .filter(|(column, description)| description.is_required() && !columns.contains(column))
+        .map(|(column, _)| column.clone())
+        .collect();
+    unfilled.sort();
+    unfilled
+}
+
+/// Suffix of the column type in flat sqltypes files and schema fingerprints
+/// marking a column that accepts NULL, e.g. `varchar(32) null`
+pub const NULLABLE_SUFFIX: &str = " null";
+
+/// Splits a column type of a flat sqltypes file into the SQL type and its nullability.
+///
+/// # Arguments
+/// * `column_type` - The column type, possibly ending in `NULLABLE_SUFFIX`
+///
+/// # Returns
+/// Returns the SQL type without the suffix and whether the column accepts NULL
+///
+pub fn split_nullable(column_type: &str) -> (&str, bool) {
+    match column_type.strip_suffix(NULLABLE_SUFFIX) {
+        Some(sql_type) => (sql_type, true),
+        None => (column_type, false),
+    }
+}
 
 #[cfg(test)]
 pub(crate) mod create_sqltypes;
 #[cfg(test)]
 pub(crate) mod sqltype_hashmap;
+#[cfg(test)]
+pub(crate) mod test_fingerprint;
+#[cfg(test)]
+pub(crate) mod test_sqltypes_file;
 
 /// Creates an sqltype file from the database
 pub async fn create_sqltype_file(pool: Option<Pool<MySql>>, args: &CliArgs) -> Result<()> {
@@ -32,14 +239,48 @@ pub async fn create_sqltype_file(pool: Option<Pool<MySql>>, args: &CliArgs) -> R
         println!("Creating sqltype file: {}", args.sqltypes_file);
     }
     // Get file contents from database
-    let hashmap: SqlTypeHashMap = read_sqltypes_from_db(pool, args).await?;
+    let columns: SqlColumnMap = read_sqltypes_from_db(pool, args).await?;
     if args.dry_run {
         println!("Dry run enabled, not writing to file.");
-        println!("{:#?}", hashmap);
+        println!("{:#?}", columns);
         std::process::exit(0);
     }
-    // Write hashmap to YAML file
-    let yaml_string = serde_yaml::to_string(&hashmap).expect("Failed to serialize hashmap to YAML");
+    write_sqltypes_file(columns, args)
+}
+
+/// Creates an sqltype file from the CREATE TABLE statements of a DDL file,
+/// e.g. `schema/tables.sql`, without a database connection
+///
+/// # Errors
+/// Returns an error if the DDL file cannot be read or parsed
+///
+pub fn create_sqltype_file_from_ddl(ddl_file: &str, args: &CliArgs) -> Result<()> {
+    if args.verbose || args.dry_run {
+        println!(
+            "Creating sqltype file {} from DDL file {}",
+            args.sqltypes_file, ddl_file
+        );
+    }
+    let ddl = std::fs::read_to_string(ddl_file)
+        .with_context(|| format!("Failed to read DDL file {}", ddl_file))?;
+    let columns =
+        parse_ddl(&ddl).with_context(|| format!("Failed to parse DDL file {}", ddl_file))?;
+    if args.dry_run {
+        println!("Dry run enabled, not writing to file.");
+        println!("{:#?}", columns);
+        return Ok(());
+    }
+    write_sqltypes_file(columns, args)
+}
+
+/// Writes the column descriptions as versioned sqltypes file `args.sqltypes_file`
+fn write_sqltypes_file(columns: SqlColumnMap, args: &CliArgs) -> Result<()> {
+    let sqltypes_file = SqlTypesFile {
+        version: SQLTYPES_VERSION,
+        tables: columns,
+    };
+    let yaml_string =
+        serde_yaml::to_string(&sqltypes_file).expect("Failed to serialize sqltypes to YAML");
 
     std::fs::write(&args.sqltypes_file, "---\n".to_string() + &yaml_string)
         .expect("Failed to write YAML to file");
@@ -51,24 +292,32 @@ pub async fn create_sqltype_file(pool: Option<Pool<MySql>>, args: &CliArgs) -> R
     Ok(())
 }
 
-/// Reads sqltypes from database or file and returns its contents
+/// Runs a subcommand of `sqltypes`
+pub fn sqltypes_command(command: &SqltypesCommand, args: &CliArgs) -> Result<()> {
+    match command {
+        SqltypesCommand::FromDdl(from_ddl_args) => {
+            create_sqltype_file_from_ddl(&from_ddl_args.ddl_file, args)
+        }
+    }
+}
+
+/// Reads sqltypes from database or file and returns its contents.
 pub async fn read_sqltypes(pool: Option<Pool<MySql>>, args: &CliArgs) -> Result<SqlTypeHashMap> {
-    let pool = match pool {
-        Some(p) => p,
+    let columns = match pool {
+        Some(pool) => read_sqltypes_from_db(pool, args).await?,
         None => {
             eprintln!(
                 "No database connection available, reading from file: {}",
                 args.sqltypes_file
             );
-            return read_sqltypes_from_file(args).await;
+            read_sqltypes_from_file(args).await?
         }
     };
-    read_sqltypes_from_db(pool, args).await
+    Ok(to_sqltype_hashmap(&columns))
 }
 
-/// Reads sqltypes from database and returns its contents
-/// panics on error
-pub async fn read_sqltypes_from_db(pool: Pool<MySql>, args: &CliArgs) -> Result<SqlTypeHashMap> {
+/// Reads the column descriptions of all tables from the database
+pub async fn read_sqltypes_from_db(pool: Pool<MySql>, args: &CliArgs) -> Result<SqlColumnMap> {
     if args.verbose || args.dry_run {
         println!("Reading sqltypes from database");
     }
@@ -78,20 +327,46 @@ pub async fn read_sqltypes_from_db(pool: Pool<MySql>, args: &CliArgs) -> Result<
             .fetch_all(&pool)
             .await?;
 
-    // Build the result HashMap
-    let mut result: SqlTypeHashMap = HashMap::new();
+    // Tables referenced by foreign keys, by table and column
+    let references: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT TABLE_NAME, COLUMN_NAME, REFERENCED_TABLE_NAME \
+         FROM information_schema.KEY_COLUMN_USAGE \
+         WHERE TABLE_SCHEMA = DATABASE() AND REFERENCED_TABLE_NAME IS NOT NULL",
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    // Build the result map
+    let mut result: SqlColumnMap = BTreeMap::new();
 
     for table_name in tables {
+        // The schema versions of `db init` and `db migrate` are not written by imports
+        if table_name == SCHEMA_VERSION_TABLE {
+            continue;
+        }
         if args.verbose || args.dry_run {
             println!("Processing table: {}", table_name);
         }
 
-        // Get columns for this table
         let query = format!("SHOW COLUMNS FROM `{}`", table_name);
-        let rows: Vec<(String, String)> = sqlx::query_as(&query).fetch_all(&pool).await?;
-        let mut columns: HashMap<String, String> = HashMap::new();
-        for (field, field_type) in rows {
-            columns.insert(field, field_type);
+        let rows: Vec<(String, String, String, String, Option<String>, String)> =
+            sqlx::query_as(&query).fetch_all(&pool).await?;
+        let mut columns: BTreeMap<String, SqlColumn> = BTreeMap::new();
+        for (field, field_type, null, key, default, extra) in rows {
+            let referenced_table = references
+                .iter()
+                .find(|(table, column, _)| *table == table_name && *column == field)
+                .map(|(_, _, referenced)| referenced.clone());
+            let column = SqlColumn {
+                sql_type: field_type,
+                nullable: null == "YES",
+                default,
+                key,
+                extra,
+                references: referenced_table,
+                flat: false,
+            };
+            columns.insert(field, column);
         }
 
         result.insert(table_name, columns);
@@ -100,15 +375,88 @@ pub async fn read_sqltypes_from_db(pool: Pool<MySql>, args: &CliArgs) -> Result<
     Ok(result)
 }
 
-/// Reads sqltypes from file and returns its contents
-/// panics on error
-pub async fn read_sqltypes_from_file(args: &CliArgs) -> Result<SqlTypeHashMap> {
+/// Reads the column descriptions from a sqltypes file, versioned or flat.
+/// If the default sqltypes file does not exist, the one compiled into the
+/// binary is used. A sqltypes file given explicitly must exist.
+pub async fn read_sqltypes_from_file(args: &CliArgs) -> Result<SqlColumnMap> {
+    let is_default = args
+        .sources
+        .get("sqltypes_file")
+        .is_none_or(|source| source == "default");
+    if is_default && !Path::new(&args.sqltypes_file).exists() {
+        if args.verbose || args.dry_run {
+            println!(
+                "Sqltypes file {} not found, using the sqltypes embedded in lmx2db",
+                args.sqltypes_file
+            );
+        }
+        return parse_sqltypes(BUNDLED_SQLTYPES);
+    }
     if args.verbose || args.dry_run {
         println!("Reading sqltypes from file: {}", args.sqltypes_file);
     }
-    let yaml_string = std::fs::read_to_string(&args.sqltypes_file)?;
+    let yaml_string = std::fs::read_to_string(&args.sqltypes_file)
+        .with_context(|| format!("Failed to read sqltypes file {}", args.sqltypes_file))?;
+    parse_sqltypes(&yaml_string)
+}
 
-    let hashmap: HashMap<String, HashMap<String, String>> = serde_yaml::from_str(&yaml_string)?;
+/// Computes the fingerprint of the column types in `sqltypes`: the SHA-256 hash
+/// (hex digits) of the lines `table.column type`, sorted by table and column name
+/// and separated by newlines. The type includes the suffix of nullable columns.
+/// `fingerprint_guard` computes the same hash in the database.
+pub fn schema_fingerprint(sqltypes: &SqlTypeHashMap) -> String {
+    let mut columns: Vec<(&String, &String, String)> = sqltypes
+        .iter()
+        .flat_map(|(table, columns)| {
+            columns
+                .iter()
+                .map(move |(column, description)| (table, column, description.column_type()))
+        })
+        .collect();
+    // Sort like `ORDER BY` the binary table and column names in the guard,
+    // not by the whole line
+    columns.sort();
+    let lines: Vec<String> = columns
+        .into_iter()
+        .map(|(table, column, column_type)| format!("{}.{} {}", table, column, column_type))
+        .collect();
+    Sha256::digest(lines.join("\n").as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
 
-    Ok(hashmap)
+/// Creates the statements stopping the load of a SQL file if the column types
+/// of the tables in `sqltypes` differ from the ones the file was created with.
+/// The fingerprint is recomputed from `information_schema.COLUMNS` of the current database.
+///
+/// # Arguments
+/// * `sqltypes` - The column types the SQL file was checked against
+///
+/// # Returns
+/// Returns the SQL statements of the guard
+///
+pub fn fingerprint_guard(sqltypes: &SqlTypeHashMap) -> Vec<String> {
+    let mut tables: Vec<&String> = sqltypes.keys().collect();
+    tables.sort();
+    let table_list = tables
+        .iter()
+        .map(|table| quote_string(table))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let fingerprint = schema_fingerprint(sqltypes);
+    vec![
+        "SET SESSION group_concat_max_len = 16777216;".to_string(),
+        "DELIMITER //".to_string(),
+        format!(
+            "IF NOT (SELECT SHA2(GROUP_CONCAT(CONCAT(TABLE_NAME, '.', COLUMN_NAME, ' ', COLUMN_TYPE, \
+             IF(IS_NULLABLE = 'YES', '{}', '')) \
+             ORDER BY CAST(TABLE_NAME AS BINARY), CAST(COLUMN_NAME AS BINARY) SEPARATOR '\\n'), 256) \
+             FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME IN ({})) <=> '{}' \
+             THEN SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = \
+             'Column types of the database do not match schema fingerprint {}'; END IF //",
+            NULLABLE_SUFFIX, table_list, fingerprint, fingerprint
+        ),
+        "DELIMITER ;".to_string(),
+    ]
 }
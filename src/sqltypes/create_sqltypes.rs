@@ -15,8 +15,8 @@
 #[cfg(test)]
 mod tests {
     use crate::cmdline::CliArgs;
-    use crate::sqltypes::create_sqltype_file;
-    use crate::sqltypes::sqltype_hashmap::check_sqltypes_file;
+    use crate::sqltypes::sqltype_hashmap::{check_sqltypes_file, column_types};
+    use crate::sqltypes::{create_sqltype_file, create_sqltype_file_from_ddl, parse_sqltypes};
     use sqlx::{MySql, Pool};
     use tempfile::NamedTempFile;
 
@@ -78,4 +78,29 @@ mod tests {
             "Expected error when no database pool is provided"
         );
     }
+
+    #[test]
+    fn test_create_sqltype_file_from_ddl() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let args = CliArgs {
+            sqltypes_file: temp_file.path().to_str().unwrap().to_string(),
+            ..Default::default()
+        };
+        create_sqltype_file_from_ddl("schema/tables.sql", &args)
+            .expect("Failed to create sqltype file from DDL");
+
+        // The types agree with the shipped sqltypes file, which was
+        // read from a database created from the bundled schema
+        let created =
+            parse_sqltypes(&std::fs::read_to_string(&args.sqltypes_file).unwrap()).unwrap();
+        let shipped = parse_sqltypes(&std::fs::read_to_string("sqltypes.yml").unwrap()).unwrap();
+        assert_eq!(column_types(&created), column_types(&shipped));
+        assert!(created["runs"]["rid"].extra.contains("auto_increment"));
+
+        let err = create_sqltype_file_from_ddl("does_not_exist.sql", &args).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to read DDL file does_not_exist.sql"
+        );
+    }
 }
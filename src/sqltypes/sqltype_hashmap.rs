@@ -12,11 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::sqltypes::SqlTypeHashMap;
-use std::collections::HashMap;
+use crate::sqltypes::{
+    parse_sqltypes, to_sqltype_hashmap, SqlColumnMap, SqlTypeHashMap, SQLTYPES_VERSION,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Returns the column types of `columns` as written to flat sqltypes files,
+/// i.e. only the SQL types and nullability
+pub fn column_types(columns: &SqlColumnMap) -> BTreeMap<String, BTreeMap<String, String>> {
+    columns
+        .iter()
+        .map(|(table, table_columns)| {
+            let types = table_columns
+                .iter()
+                .map(|(column, description)| (column.clone(), description.column_type()))
+                .collect();
+            (table.clone(), types)
+        })
+        .collect()
+}
 
 pub fn check_sqltype_values(sqltype_map: &SqlTypeHashMap) -> bool {
-    let compare_map: SqlTypeHashMap = HashMap::from([
+    let compare_map: HashMap<String, HashMap<String, String>> = HashMap::from([
         (
             "hpm_events".to_string(),
             HashMap::from([
@@ -42,9 +59,11 @@ pub fn check_sqltype_values(sqltype_map: &SqlTypeHashMap) -> bool {
 
     compare_map.iter().all(|(key, sub_map)| {
         sqltype_map.get(key).is_some_and(|existing_sub_map| {
-            sub_map
-                .iter()
-                .all(|(sub_key, sub_value)| existing_sub_map.get(sub_key) == Some(sub_value))
+            sub_map.iter().all(|(sub_key, sub_value)| {
+                existing_sub_map
+                    .get(sub_key)
+                    .is_some_and(|column| &column.column_type() == sub_value)
+            })
         })
     })
 }
@@ -64,12 +83,23 @@ pub fn check_sqltypes_file(sqltypes_file: String) -> bool {
     // Check YAML structure (basic check)
     let yaml: serde_yml::Value =
         serde_yml::from_str(&contents).expect("Created sqltype file is not valid YAML");
-    assert!(yaml.get("hpm_events").is_some());
-    assert!(yaml.get("mpi_details").is_some());
-    assert!(yaml.get("power_types").is_some());
+    assert_eq!(
+        yaml.get("version").and_then(|version| version.as_u64()),
+        Some(SQLTYPES_VERSION as u64)
+    );
+    let tables = yaml
+        .get("tables")
+        .expect("Created sqltype file has no tables");
+    assert!(tables.get("hpm_events").is_some());
+    assert!(tables.get("mpi_details").is_some());
+    assert!(tables.get("power_types").is_some());
 
     // Further checks can be added here to validate specific keys/values
-    let sqltypes_map: HashMap<String, HashMap<String, String>> =
-        serde_yml::from_str(&contents).expect("Failed to deserialize sqltype file into hashmap");
-    check_sqltype_values(&sqltypes_map)
+    let columns = parse_sqltypes(&contents).expect("Failed to parse sqltype file");
+    let runs = &columns["runs"];
+    assert_eq!(runs["rid"].key, "PRI");
+    assert_eq!(runs["rid"].extra, "auto_increment");
+    assert_eq!(runs["clid"].references.as_deref(), Some("clusters"));
+    assert!(runs["md5sum_exe"].nullable);
+    check_sqltype_values(&to_sqltype_hashmap(&columns))
 }
@@ -0,0 +1,90 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::sqltypes::{fingerprint_guard, schema_fingerprint, SqlColumn, SqlTypeHashMap};
+    use sha2::{Digest, Sha256};
+    use std::collections::HashMap;
+
+    fn sample_sqltypes() -> SqlTypeHashMap {
+        HashMap::from([
+            (
+                "runs".to_string(),
+                HashMap::from([
+                    ("rid".to_string(), SqlColumn::from_column_type("int(11)")),
+                    (
+                        "start_date".to_string(),
+                        SqlColumn::from_column_type("int(11)"),
+                    ),
+                ]),
+            ),
+            (
+                "mpi".to_string(),
+                HashMap::from([("calls".to_string(), SqlColumn::from_column_type("int(11)"))]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_schema_fingerprint() {
+        let sqltypes = sample_sqltypes();
+        // SHA-256 of "mpi.calls int(11)\nruns.rid int(11)\nruns.start_date int(11)"
+        assert_eq!(
+            schema_fingerprint(&sqltypes),
+            "a66897fb9a9eb0826461578bc74cf9e2222e2795f55eb84f5f443c55b0a6166a"
+        );
+
+        // Changing a single column type changes the fingerprint
+        let mut changed = sqltypes.clone();
+        changed.get_mut("mpi").unwrap().insert(
+            "calls".to_string(),
+            SqlColumn::from_column_type("bigint(20)"),
+        );
+        assert_ne!(schema_fingerprint(&changed), schema_fingerprint(&sqltypes));
+    }
+
+    #[test]
+    fn test_fingerprint_order() {
+        // "run-2.c" sorts before "run.c", but the table run comes before run-2
+        let sqltypes: SqlTypeHashMap = HashMap::from([
+            (
+                "run".to_string(),
+                HashMap::from([("c".to_string(), SqlColumn::from_column_type("int(11)"))]),
+            ),
+            (
+                "run-2".to_string(),
+                HashMap::from([("c".to_string(), SqlColumn::from_column_type("int(11)"))]),
+            ),
+        ]);
+        let expected: String = Sha256::digest(b"run.c int(11)\nrun-2.c int(11)")
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        assert_eq!(schema_fingerprint(&sqltypes), expected);
+    }
+
+    #[test]
+    fn test_fingerprint_guard() {
+        let sqltypes = sample_sqltypes();
+        let guard = fingerprint_guard(&sqltypes);
+        assert_eq!(guard.len(), 4);
+        assert_eq!(guard[1], "DELIMITER //");
+        assert!(guard[2].contains("TABLE_NAME IN ('mpi', 'runs')"));
+        assert!(guard[2].contains("IF(IS_NULLABLE = 'YES', ' null', '')"));
+        assert!(guard[2].contains(&format!("<=> '{}'", schema_fingerprint(&sqltypes))));
+        assert!(guard[2].ends_with("END IF //"));
+        assert_eq!(guard[3], "DELIMITER ;");
+    }
+}
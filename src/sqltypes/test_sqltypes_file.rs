@@ -0,0 +1,147 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::CliArgs;
+    use crate::sqltypes::{
+        parse_sqltypes, read_sqltypes_from_file, to_sqltype_hashmap, unfilled_columns, SqlColumn,
+        SqlTypesFile, SQLTYPES_VERSION,
+    };
+    use anyhow::Result;
+    use std::collections::BTreeMap;
+
+    const VERSIONED: &str = r#"---
+version: 2
+tables:
+  unfilled_test:
+    id:
+      type: int(11)
+      key: PRI
+      extra: auto_increment
+    clid:
+      type: int(11)
+      default: '1'
+      key: MUL
+      references: clusters
+    name:
+      type: varchar(32)
+    comment:
+      type: varchar(256)
+      nullable: true
+"#;
+
+    #[test]
+    fn test_parse_flat_sqltypes() -> Result<()> {
+        let columns =
+            parse_sqltypes("---\nruns:\n  rid: int(11)\n  perf_unit: varchar(32) null\n")?;
+        assert_eq!(
+            columns["runs"]["perf_unit"],
+            SqlColumn {
+                sql_type: "varchar(32)".to_string(),
+                nullable: true,
+                flat: true,
+                ..Default::default()
+            }
+        );
+        // Flat files do not record defaults, so no column is required
+        assert!(!columns["runs"]["rid"].is_required());
+        let sqltypes = to_sqltype_hashmap(&columns);
+        assert_eq!(sqltypes["runs"]["rid"].column_type(), "int(11)");
+        assert_eq!(
+            sqltypes["runs"]["perf_unit"].column_type(),
+            "varchar(32) null"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_versioned_sqltypes() -> Result<()> {
+        let columns = parse_sqltypes(VERSIONED)?;
+        let table = &columns["unfilled_test"];
+        assert_eq!(table["clid"].default.as_deref(), Some("1"));
+        assert_eq!(table["clid"].references.as_deref(), Some("clusters"));
+        assert!(!table["id"].is_required());
+        assert!(!table["clid"].is_required());
+        assert!(table["name"].is_required());
+        assert!(!table["comment"].is_required());
+        assert_eq!(
+            to_sqltype_hashmap(&columns)["unfilled_test"]["comment"].column_type(),
+            "varchar(256) null"
+        );
+
+        // Writing and reading again gives the same columns
+        let file = SqlTypesFile {
+            version: SQLTYPES_VERSION,
+            tables: columns.clone(),
+        };
+        assert_eq!(parse_sqltypes(&serde_yaml::to_string(&file)?)?, columns);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_sqltypes_version() {
+        let err = parse_sqltypes("version: 99\ntables: {}\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Sqltypes file version 99 is not supported, the latest known version is {}",
+                SQLTYPES_VERSION
+            )
+        );
+        assert!(parse_sqltypes("version: 2\ntables: {}\nother: 1\n").is_err());
+        assert_eq!(
+            parse_sqltypes("version: 2\ntables: {}\n").unwrap(),
+            BTreeMap::new()
+        );
+    }
+
+    #[test]
+    fn test_unfilled_columns() -> Result<()> {
+        let sqltypes = to_sqltype_hashmap(&parse_sqltypes(VERSIONED)?);
+        let keys = vec!["clid".to_string(), "comment".to_string()];
+        assert_eq!(
+            unfilled_columns("unfilled_test", &keys, &sqltypes),
+            vec!["name"]
+        );
+        let keys = vec!["name".to_string()];
+        assert!(unfilled_columns("unfilled_test", &keys, &sqltypes).is_empty());
+        assert!(unfilled_columns("unknown_table", &keys, &sqltypes).is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_embedded_sqltypes_fallback() -> Result<()> {
+        // A missing default sqltypes file falls back to the embedded one
+        let mut args = CliArgs {
+            sqltypes_file: "no_such_sqltypes.yml".to_string(),
+            ..Default::default()
+        };
+        args.sources
+            .insert("sqltypes_file".to_string(), "default".to_string());
+        let embedded = read_sqltypes_from_file(&args).await?;
+        let shipped = parse_sqltypes(&std::fs::read_to_string("sqltypes.yml")?)?;
+        assert_eq!(embedded, shipped);
+
+        // A sqltypes file given explicitly must exist
+        args.sources
+            .insert("sqltypes_file".to_string(), "command line".to_string());
+        let err = read_sqltypes_from_file(&args).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to read sqltypes file no_such_sqltypes.yml"
+        );
+        Ok(())
+    }
+}
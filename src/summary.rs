@@ -0,0 +1,122 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::archive::display_name;
+use crate::jobdata::{ImportOutcome, ImportStatus};
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+pub(crate) mod test_summary;
+
+/// Exit status of `schema diff` if there are breaking changes
+pub const EXIT_BREAKING_CHANGES: u8 = 1;
+/// Exit status for configuration errors, e.g. invalid paths or a missing sqltypes file.
+/// This is the same exit status clap uses for invalid command line arguments.
+pub const EXIT_CONFIG_ERROR: u8 = 2;
+/// Exit status if some, but not all LMX_summary files failed
+pub const EXIT_PARTIAL_FAILURE: u8 = 3;
+/// Exit status if all LMX_summary files failed
+pub const EXIT_TOTAL_FAILURE: u8 = 4;
+
+/// Counts of processed LMX_summary files together with the failures
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub found: usize,
+    pub imported: usize,
+    pub skipped: usize,
+    /// Pairs of file name and the root cause of its error
+    pub failures: Vec<(String, String)>,
+}
+
+impl ImportSummary {
+    /// Builds the summary from the results of all processed files.
+    ///
+    /// # Arguments
+    /// * `results` - Pairs of file name and result as returned by the worker pool
+    ///
+    /// # Returns
+    /// Returns the `ImportSummary` with one count per result
+    ///
+    pub fn from_results(results: &[(String, Result<ImportOutcome>)]) -> Self {
+        let mut summary = ImportSummary {
+            found: results.len(),
+            ..Default::default()
+        };
+        for (file_name, result) in results {
+            match result {
+                Ok(outcome) => match outcome.status {
+                    ImportStatus::Imported => summary.imported += 1,
+                    ImportStatus::Skipped => summary.skipped += 1,
+                },
+                Err(e) => summary
+                    .failures
+                    .push((display_name(file_name), e.root_cause().to_string())),
+            }
+        }
+        summary
+    }
+
+    /// Returns the number of failed files
+    pub fn failed(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Groups the failed files by the root cause of their errors.
+    ///
+    /// # Returns
+    /// Returns a map from the error message to the list of affected files
+    ///
+    pub fn failures_by_cause(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut grouped: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (file_name, cause) in &self.failures {
+            grouped
+                .entry(cause.as_str())
+                .or_default()
+                .push(file_name.as_str());
+        }
+        grouped
+    }
+
+    /// Returns the process exit status: 0 if no file failed,
+    /// `EXIT_TOTAL_FAILURE` if all files failed and
+    /// `EXIT_PARTIAL_FAILURE` otherwise.
+    pub fn exit_status(&self) -> u8 {
+        if self.failures.is_empty() {
+            0
+        } else if self.failed() == self.found {
+            EXIT_TOTAL_FAILURE
+        } else {
+            EXIT_PARTIAL_FAILURE
+        }
+    }
+
+    /// Prints the summary and the failed files grouped by cause to stdout.
+    pub fn print(&self) {
+        println!("Summary of LMX_summary files:");
+        println!("    found:    {}", self.found);
+        println!("    imported: {}", self.imported);
+        println!("    skipped:  {}", self.skipped);
+        println!("    failed:   {}", self.failed());
+        if !self.failures.is_empty() {
+            println!("Failed files grouped by cause:");
+            for (cause, files) in self.failures_by_cause() {
+                println!("    {} ({} files):", cause, files.len());
+                for file_name in files {
+                    println!("        {}", file_name);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::jobdata::{ImportOutcome, ImportStatus};
+    use crate::summary::{ImportSummary, EXIT_PARTIAL_FAILURE, EXIT_TOTAL_FAILURE};
+    use anyhow::{anyhow, Result};
+
+    fn outcome(status: ImportStatus) -> ImportOutcome {
+        ImportOutcome {
+            status,
+            ..ImportOutcome::skipped()
+        }
+    }
+
+    #[test]
+    fn test_all_files_imported() {
+        let results: Vec<(String, Result<ImportOutcome>)> = vec![
+            (
+                "a/LMX_summary.1.0.yml".to_string(),
+                Ok(outcome(ImportStatus::Imported)),
+            ),
+            (
+                "b/LMX_summary.2.0.yml".to_string(),
+                Ok(outcome(ImportStatus::Skipped)),
+            ),
+        ];
+        let summary = ImportSummary::from_results(&results);
+        assert_eq!(summary.found, 2);
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.failed(), 0);
+        assert_eq!(summary.exit_status(), 0);
+    }
+
+    #[test]
+    fn test_no_files_found() {
+        let summary = ImportSummary::from_results(&[]);
+        assert_eq!(summary.found, 0);
+        assert_eq!(summary.exit_status(), 0);
+    }
+
+    #[test]
+    fn test_partial_failure_grouped_by_cause() {
+        let results: Vec<(String, Result<ImportOutcome>)> = vec![
+            (
+                "a/LMX_summary.1.0.yml".to_string(),
+                Ok(outcome(ImportStatus::Imported)),
+            ),
+            (
+                "b/LMX_summary.2.0.yml".to_string(),
+                Err(anyhow!("Required project file 'project.yml' not found")),
+            ),
+            (
+                "c/LMX_summary.3.0.yml".to_string(),
+                Err(anyhow!("Missing 'base_data' in LMX summary")),
+            ),
+            (
+                "d/LMX_summary.4.0.yml".to_string(),
+                Err(anyhow!("Required project file 'project.yml' not found")
+                    .context("Processing d/LMX_summary.4.0.yml")),
+            ),
+        ];
+        let summary = ImportSummary::from_results(&results);
+        assert_eq!(summary.found, 4);
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.failed(), 3);
+        assert_eq!(summary.exit_status(), EXIT_PARTIAL_FAILURE);
+
+        // Errors with the same root cause end up in the same group
+        let grouped = summary.failures_by_cause();
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(
+            grouped["Required project file 'project.yml' not found"],
+            vec!["b/LMX_summary.2.0.yml", "d/LMX_summary.4.0.yml"]
+        );
+        assert_eq!(
+            grouped["Missing 'base_data' in LMX summary"],
+            vec!["c/LMX_summary.3.0.yml"]
+        );
+    }
+
+    #[test]
+    fn test_total_failure() {
+        let results: Vec<(String, Result<ImportOutcome>)> = vec![(
+            "a/LMX_summary.1.0.yml".to_string(),
+            Err(anyhow!("Cannot cast null value to string")),
+        )];
+        let summary = ImportSummary::from_results(&results);
+        assert_eq!(summary.exit_status(), EXIT_TOTAL_FAILURE);
+    }
+}
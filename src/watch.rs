@@ -0,0 +1,308 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cmdline::{CliArgs, WatchArgs};
+use crate::globbing::{find_lmx_summary_files, find_lmx_type_files};
+use crate::jobdata::RunStatus;
+use crate::sqltypes::SqlTypeHashMap;
+use crate::summary::ImportSummary;
+use crate::workers;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{MySql, Pool};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::signal::unix::{signal, SignalKind};
+
+#[cfg(test)]
+pub(crate) mod test_watch;
+
+/// Size and modification time of all files belonging to a run,
+/// i.e. the LMX_summary file together with its MPI and itimer profile files.
+/// A run is considered complete once its stamp no longer changes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunStamp {
+    pub files: usize,
+    pub bytes: u64,
+    /// Latest modification time in milliseconds since the epoch
+    pub modified: u128,
+}
+
+/// A run recorded in the state file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProcessedRun {
+    pub status: RunStatus,
+    pub stamp: RunStamp,
+}
+
+/// Persistent state of the watch mode: all runs processed so far,
+/// keyed by the name of their LMX_summary file
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct WatchState {
+    pub processed: BTreeMap<String, ProcessedRun>,
+}
+
+impl WatchState {
+    /// Reads the state file, or returns an empty state if it does not exist yet.
+    ///
+    /// # Errors
+    /// Returns an error if the state file exists but cannot be read or parsed
+    ///
+    pub fn load(state_file: &str) -> Result<Self> {
+        if !Path::new(state_file).exists() {
+            return Ok(WatchState::default());
+        }
+        let content = std::fs::read_to_string(state_file)?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid watch state file {}", state_file))
+    }
+
+    /// Writes the state file. The state is written to a temporary file first,
+    /// so that an interrupted write never leaves a truncated state file behind.
+    ///
+    /// # Errors
+    /// Returns an error if the state file cannot be written
+    ///
+    pub fn save(&self, state_file: &str) -> Result<()> {
+        let temp_file = format!("{}.tmp", state_file);
+        std::fs::write(&temp_file, serde_json::to_string_pretty(self)? + "\n")?;
+        std::fs::rename(&temp_file, state_file)?;
+        Ok(())
+    }
+
+    /// Checks whether a run was imported or skipped before. These runs are
+    /// never processed again, so their files need not be looked at.
+    pub fn is_finished(&self, file_name: &str) -> bool {
+        self.processed
+            .get(file_name)
+            .is_some_and(|run| run.status != RunStatus::Failed)
+    }
+
+    /// Checks whether a run needs to be processed. Imported and skipped runs are
+    /// never processed again, failed runs only if their files have changed since.
+    pub fn needs_processing(&self, file_name: &str, stamp: &RunStamp) -> bool {
+        match self.processed.get(file_name) {
+            None => true,
+            Some(run) => run.status == RunStatus::Failed && run.stamp != *stamp,
+        }
+    }
+}
+
+/// Determines the `RunStamp` of the run belonging to an LMX_summary file.
+///
+/// # Arguments
+/// * `file_name` - Path to the LMX_summary file
+///
+/// # Returns
+/// Returns the stamp over the LMX_summary file and all its profile files
+///
+/// # Errors
+/// Returns an error if the profile files cannot be found or their metadata cannot be read
+///
+pub fn run_stamp(file_name: &str) -> Result<RunStamp> {
+    let mut run_files = vec![file_name.to_string()];
+    run_files.extend(find_lmx_type_files(file_name, "MPI")?);
+    run_files.extend(find_lmx_type_files(file_name, "itimer")?);
+
+    let mut stamp = RunStamp {
+        files: run_files.len(),
+        bytes: 0,
+        modified: 0,
+    };
+    for run_file in &run_files {
+        let metadata = std::fs::metadata(run_file)?;
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        stamp.bytes += metadata.len();
+        stamp.modified = stamp.modified.max(modified);
+    }
+    Ok(stamp)
+}
+
+/// Watches directories for new runs and imports them once they are complete.
+pub struct Watcher {
+    watch_args: WatchArgs,
+    state: WatchState,
+    /// Runs waiting for their files to settle, with the stamp
+    /// and the time at which this stamp was first seen
+    pending: HashMap<String, (RunStamp, Instant)>,
+}
+
+impl Watcher {
+    /// Creates a watcher, restoring the runs processed before from the state file.
+    ///
+    /// # Errors
+    /// Returns an error if the state file cannot be read
+    ///
+    pub fn new(watch_args: &WatchArgs) -> Result<Self> {
+        Ok(Watcher {
+            watch_args: watch_args.clone(),
+            state: WatchState::load(&watch_args.state_file)?,
+            pending: HashMap::new(),
+        })
+    }
+
+    /// Checks whether the files of a run have settled, i.e. have not changed for
+    /// the settle time. This is either known from the modification times, or from
+    /// the stamp being unchanged over the settle time, in case clocks are skewed
+    /// between the compute nodes and the watching node.
+    fn is_settled(&mut self, file_name: &str, stamp: RunStamp) -> bool {
+        let settle_time = Duration::from_secs(self.watch_args.settle_time);
+        let modified = UNIX_EPOCH + Duration::from_millis(stamp.modified as u64);
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+        match self.pending.get(file_name) {
+            Some((pending_stamp, first_seen)) if *pending_stamp == stamp => {
+                age >= settle_time || first_seen.elapsed() >= settle_time
+            }
+            _ => {
+                self.pending
+                    .insert(file_name.to_string(), (stamp, Instant::now()));
+                age >= settle_time
+            }
+        }
+    }
+
+    /// Scans the watched directories once and imports all complete runs
+    /// not processed before. The state file is updated after the import.
+    ///
+    /// # Arguments
+    /// * `pool` - Optional MySQL connection pool
+    /// * `sqltypes` - Shared database schema mapping for type checks
+    /// * `args` - Shared command line arguments
+    ///
+    /// # Returns
+    /// Returns the number of runs processed in this scan
+    ///
+    /// # Errors
+    /// Returns an error if the directories cannot be scanned or the state file
+    /// cannot be written. Errors of single runs are recorded in the state file.
+    ///
+    pub async fn poll(
+        &mut self,
+        pool: &Option<Pool<MySql>>,
+        sqltypes: Arc<SqlTypeHashMap>,
+        args: Arc<CliArgs>,
+    ) -> Result<usize> {
+        // Archives are extracted until the files have been processed
+        let found = find_lmx_summary_files(&self.watch_args.directories, &args)?;
+        // Forget pending runs that vanished or are no longer found
+        let found_files: HashSet<&String> = found.files.iter().collect();
+        self.pending
+            .retain(|file_name, _| found_files.contains(file_name));
+        let mut ready = Vec::new();
+        let mut stamps = HashMap::new();
+        for file_name in found.files {
+            // Finished runs are skipped before their files are stat'ed
+            if self.state.is_finished(&file_name) {
+                continue;
+            }
+            // Files may vanish between the scan and reading their metadata
+            let stamp = match run_stamp(&file_name) {
+                Ok(stamp) => stamp,
+                Err(e) => {
+                    println!("Cannot determine state of {}: {}", file_name, e);
+                    continue;
+                }
+            };
+            if !self.state.needs_processing(&file_name, &stamp) {
+                continue;
+            }
+            if self.is_settled(&file_name, stamp) {
+                stamps.insert(file_name.clone(), stamp);
+                ready.push(file_name);
+            } else if args.verbose || args.dry_run {
+                println!("Waiting for run to finish: {}", file_name);
+            }
+        }
+        if ready.is_empty() {
+            return Ok(0);
+        }
+
+        let results = workers::process_lmx_files(ready, pool, sqltypes, Arc::clone(&args)).await?;
+        for (file_name, result) in &results {
+            self.pending.remove(file_name);
+            self.state.processed.insert(
+                file_name.clone(),
+                ProcessedRun {
+                    status: RunStatus::of(result),
+                    stamp: stamps[file_name],
+                },
+            );
+        }
+        // A dry run must not keep later runs from importing these runs
+        if !args.dry_run {
+            self.state.save(&self.watch_args.state_file)?;
+        }
+        ImportSummary::from_results(&results).print();
+        Ok(results.len())
+    }
+}
+
+/// Runs the watch mode until SIGTERM or SIGINT is received. A scan
+/// in progress is always completed, so that no import is interrupted.
+/// Errors of a single scan, e.g. a temporarily unavailable file system,
+/// are reported and the next scan is attempted after the interval.
+///
+/// # Arguments
+/// * `watch_args` - Options of the watch subcommand
+/// * `pool` - Optional MySQL connection pool
+/// * `sqltypes` - Shared database schema mapping for type checks
+/// * `args` - Shared command line arguments
+///
+/// # Errors
+/// Returns an error if a watched path is not a directory, the state file
+/// cannot be read, or signal handlers cannot be installed
+///
+pub async fn watch_directories(
+    watch_args: &WatchArgs,
+    pool: &Option<Pool<MySql>>,
+    sqltypes: Arc<SqlTypeHashMap>,
+    args: Arc<CliArgs>,
+) -> Result<()> {
+    for directory in &watch_args.directories {
+        if !Path::new(directory).is_dir() {
+            bail!("Watched path is not a directory: {}", directory);
+        }
+    }
+    // Install the handlers first, so that signals received during a scan are not lost
+    let mut sigterm = signal(SignalKind::terminate())?;
+    let mut sigint = signal(SignalKind::interrupt())?;
+    let mut watcher = Watcher::new(watch_args)?;
+    println!(
+        "Watching {:?} every {} s, press Ctrl-C or send SIGTERM to stop",
+        watch_args.directories, watch_args.interval
+    );
+    loop {
+        if let Err(e) = watcher
+            .poll(pool, Arc::clone(&sqltypes), Arc::clone(&args))
+            .await
+        {
+            eprintln!("Error: {:#}", e);
+        }
+        tokio::select! {
+            _ = sigterm.recv() => break,
+            _ = sigint.recv() => break,
+            _ = tokio::time::sleep(Duration::from_secs(watch_args.interval)) => {}
+        }
+    }
+    println!("Stopped watching, state saved in {}", watch_args.state_file);
+    Ok(())
+}
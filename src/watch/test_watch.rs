@@ -0,0 +1,163 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::{CliArgs, WatchArgs};
+    use crate::jobdata::RunStatus;
+    use crate::sqltypes::read_sqltypes;
+    use crate::watch::{run_stamp, ProcessedRun, RunStamp, WatchState, Watcher};
+    use anyhow::Result;
+    use fs_extra::dir::{copy, CopyOptions};
+    use std::path::Path;
+    use std::sync::Arc;
+
+    /// Copies the NAMD project file and the run `run_name` into `target_dir`
+    fn copy_run(run_name: &str, target_dir: &Path) -> Result<()> {
+        std::fs::copy(
+            "tests/data/NAMD/project.yml",
+            target_dir.join("project.yml"),
+        )?;
+        copy(
+            Path::new("tests/data/NAMD").join(run_name),
+            target_dir,
+            &CopyOptions::new(),
+        )?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_watch_imports_settled_runs_once() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let watch_dir = temp_dir.path().join("runs");
+        std::fs::create_dir(&watch_dir)?;
+        copy_run("run_0002", &watch_dir)?;
+        let sql_file = temp_dir.path().join("import.sql");
+        let args = Arc::new(CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            sql_file: sql_file.to_string_lossy().to_string(),
+            project_file: "project.yml".to_string(),
+            settings_file: "settings.yml".to_string(),
+            module_file: "modules.yml".to_string(),
+            ..Default::default()
+        });
+        let sqltypes = Arc::new(read_sqltypes(None, &args).await?);
+        let mut watch_args = WatchArgs {
+            interval: 1,
+            settle_time: 3600,
+            state_file: temp_dir
+                .path()
+                .join("state.json")
+                .to_string_lossy()
+                .to_string(),
+            directories: vec![watch_dir.to_string_lossy().to_string()],
+        };
+
+        // The freshly copied files have not settled yet
+        let mut watcher = Watcher::new(&watch_args)?;
+        let processed = watcher
+            .poll(&None, Arc::clone(&sqltypes), Arc::clone(&args))
+            .await?;
+        assert_eq!(processed, 0);
+        assert!(!sql_file.exists());
+        assert_eq!(watcher.pending.len(), 1);
+
+        watch_args.settle_time = 0;
+        let mut watcher = Watcher::new(&watch_args)?;
+        let processed = watcher
+            .poll(&None, Arc::clone(&sqltypes), Arc::clone(&args))
+            .await?;
+        assert_eq!(processed, 1);
+        let content = std::fs::read_to_string(&sql_file)?;
+        assert_eq!(content.matches("-- Queries for file").count(), 1);
+
+        // The state file records the run together with all of its profile files
+        let state = WatchState::load(&watch_args.state_file)?;
+        assert_eq!(state.processed.len(), 1);
+        let (file_name, run) = state.processed.iter().next().unwrap();
+        assert_eq!(run.status, RunStatus::Imported);
+        let content = std::fs::read_to_string(&watch_args.state_file)?;
+        assert!(content.contains("\"status\": \"imported\""), "{}", content);
+        assert_eq!(run.stamp, run_stamp(file_name)?);
+        assert!(run.stamp.files > 1);
+
+        // Neither the same watcher nor a restarted one processes the run again
+        let processed = watcher
+            .poll(&None, Arc::clone(&sqltypes), Arc::clone(&args))
+            .await?;
+        assert_eq!(processed, 0);
+        let mut watcher = Watcher::new(&watch_args)?;
+        let processed = watcher
+            .poll(&None, Arc::clone(&sqltypes), Arc::clone(&args))
+            .await?;
+        assert_eq!(processed, 0);
+
+        // A new run is picked up
+        copy_run("run_0001", &watch_dir)?;
+        let processed = watcher
+            .poll(&None, Arc::clone(&sqltypes), Arc::clone(&args))
+            .await?;
+        assert_eq!(processed, 1);
+        assert_eq!(WatchState::load(&watch_args.state_file)?.processed.len(), 2);
+
+        // A pending run that vanishes before it settled is forgotten
+        watch_args.settle_time = 3600;
+        let mut watcher = Watcher::new(&watch_args)?;
+        copy_run("run_0003", &watch_dir)?;
+        watcher
+            .poll(&None, Arc::clone(&sqltypes), Arc::clone(&args))
+            .await?;
+        assert_eq!(watcher.pending.len(), 1);
+        std::fs::remove_dir_all(watch_dir.join("run_0003"))?;
+        let processed = watcher.poll(&None, sqltypes, args).await?;
+        assert_eq!(processed, 0);
+        assert!(watcher.pending.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_runs_are_retried_after_change() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let state_file = temp_dir.path().join("state.json");
+        let state_file = state_file.to_str().unwrap();
+        let file_name = "tests/data/NAMD/run_0001/LMX_summary.225250.0.yml";
+        let stamp = run_stamp(file_name)?;
+
+        let mut state = WatchState::default();
+        assert!(state.needs_processing(file_name, &stamp));
+        state.processed.insert(
+            file_name.to_string(),
+            ProcessedRun {
+                status: RunStatus::Failed,
+                stamp,
+            },
+        );
+        state.save(state_file)?;
+
+        let mut state = WatchState::load(state_file)?;
+        assert!(!state.is_finished(file_name));
+        assert!(!state.needs_processing(file_name, &stamp));
+        let changed = RunStamp {
+            bytes: stamp.bytes + 1,
+            ..stamp
+        };
+        assert!(state.needs_processing(file_name, &changed));
+
+        // Imported runs are finished and never processed again
+        state.processed.get_mut(file_name).unwrap().status = RunStatus::Imported;
+        assert!(state.is_finished(file_name));
+        assert!(!state.needs_processing(file_name, &changed));
+        Ok(())
+    }
+}
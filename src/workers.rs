@@ -0,0 +1,126 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::archive::display_name;
+use crate::cmdline::CliArgs;
+use crate::jobdata::transaction::TransactionGroup;
+use crate::jobdata::{self, ImportOutcome, ImportStatus};
+use crate::sqltypes::SqlTypeHashMap;
+use anyhow::{anyhow, Result};
+use sqlx::{MySql, Pool};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+#[cfg(test)]
+pub(crate) mod test_workers;
+
+/// Processes a list of LMX_summary files with a bounded pool of concurrent workers.
+///
+/// At most `args.jobs` files are processed at the same time. Each file is handled by
+/// `jobdata::process_lmx_file`, which uses its own transaction on the shared
+/// connection pool or appends its own block to the SQL file. In the grouped
+/// transaction modes of `args.tx_mode`, the runs share the transactions of a
+/// `TransactionGroup` instead, and the last group is committed once all files are done.
+/// Errors of individual files are reported and do not stop the processing of the remaining files.
+/// If the transaction of a group is rolled back, all files of the group have failed.
+///
+/// # Arguments
+///
+/// * `list_of_files` - LMX_summary files to process
+/// * `pool` - Optional MySQL connection pool shared by all workers
+/// * `sqltypes` - Shared database schema mapping for type checks
+/// * `args` - Shared command line arguments
+///
+/// # Returns
+///
+/// Returns the result of each file, in the order of `list_of_files`,
+/// once all files have been processed.
+///
+/// # Errors
+///
+/// Returns an error if the worker pool itself fails.
+/// A panic in a worker is propagated to the caller.
+pub async fn process_lmx_files(
+    list_of_files: Vec<String>,
+    pool: &Option<Pool<MySql>>,
+    sqltypes: Arc<SqlTypeHashMap>,
+    args: Arc<CliArgs>,
+) -> Result<Vec<(String, Result<ImportOutcome>)>> {
+    // A value of 0 (e.g. from CliArgs::default()) means sequential processing
+    let semaphore = Arc::new(Semaphore::new(args.jobs.max(1)));
+    let mut workers = JoinSet::new();
+    let group = TransactionGroup::new(pool, &args).map(Arc::new);
+
+    for (index, file_name) in list_of_files.into_iter().enumerate() {
+        // Acquire the permit before spawning, so that no more than
+        // `jobs` tasks exist at any time.
+        let permit = semaphore.clone().acquire_owned().await?;
+        let pool = pool.clone();
+        let sqltypes = Arc::clone(&sqltypes);
+        let args = Arc::clone(&args);
+        let group = group.clone();
+        workers.spawn(async move {
+            // Files extracted from archives are shown with their archive member path
+            let display_name = display_name(&file_name);
+            println!("Processing file: {}", display_name);
+            let return_code =
+                jobdata::process_lmx_file(&file_name, &pool, group.as_deref(), &sqltypes, &args)
+                    .await;
+            match &return_code {
+                Ok(outcome) if outcome.status == ImportStatus::Imported => {}
+                Ok(_) => {
+                    println!(
+                        "Skipping {}: run already exists in the database",
+                        display_name
+                    )
+                }
+                Err(e) => println!("Ignoring {} because of error:\n     {}", display_name, e),
+            }
+            drop(permit);
+            (index, file_name, return_code)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = workers.join_next().await {
+        match joined {
+            Ok(result) => results.push(result),
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(e) => return Err(e.into()),
+        }
+    }
+    if let Some(group) = group {
+        // A failed commit is recorded for each run of the group
+        let _ = group.commit(&args).await;
+        // Runs of a group rolled back after they succeeded have failed after all
+        let failed = group.take_failed().await;
+        for (_, file_name, result) in results.iter_mut() {
+            if let (Ok(_), Some(error)) = (&result, failed.get(file_name)) {
+                println!(
+                    "Ignoring {} because of error:\n     {}",
+                    display_name(file_name),
+                    error
+                );
+                *result = Err(anyhow!(error.clone()));
+            }
+        }
+    }
+    // Restore the order of the input list
+    results.sort_by_key(|(index, _, _)| *index);
+    Ok(results
+        .into_iter()
+        .map(|(_, file_name, result)| (file_name, result))
+        .collect())
+}
@@ -0,0 +1,91 @@
+// Copyright 2026 lmx2db C. Pospiech
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests {
+    use crate::cmdline::CliArgs;
+    use crate::globbing::find_lmx_summary_files;
+    use crate::jobdata::ImportStatus;
+    use crate::sqltypes::read_sqltypes;
+    use crate::workers::process_lmx_files;
+    use anyhow::Result;
+    use std::sync::Arc;
+    use tempfile::NamedTempFile;
+
+    #[tokio::test]
+    async fn test_concurrent_runs_do_not_interleave() -> Result<()> {
+        // Keep temp_file in scope to prevent automatic deletion until test completes
+        let temp_file = NamedTempFile::new()?;
+        let args = CliArgs {
+            sqltypes_file: "sqltypes.yml".to_string(),
+            sql_file: temp_file.path().to_string_lossy().to_string(),
+            project_file: "project.yml".to_string(),
+            settings_file: "settings.yml".to_string(),
+            module_file: "modules.yml".to_string(),
+            jobs: 3,
+            ..Default::default()
+        };
+        let list_of_files =
+            find_lmx_summary_files(&vec!["tests/data/NAMD".to_string()], &args)?.files;
+        assert_eq!(list_of_files.len(), 3);
+        let sqltypes = read_sqltypes(None, &args).await?;
+
+        let results = process_lmx_files(
+            list_of_files.clone(),
+            &None,
+            Arc::new(sqltypes),
+            Arc::new(args),
+        )
+        .await?;
+
+        // Results are returned in the order of the input list
+        assert_eq!(results.len(), list_of_files.len());
+        for ((file_name, result), expected) in results.iter().zip(&list_of_files) {
+            assert_eq!(file_name, expected);
+            let outcome = result.as_ref().expect("Processing failed");
+            assert_eq!(outcome.status, ImportStatus::Imported);
+            assert_eq!(outcome.rid, None);
+            assert_eq!(outcome.row_counts["runs"], 1);
+        }
+
+        // Every run block must be framed by its own START TRANSACTION and COMMIT
+        let content = std::fs::read_to_string(temp_file.path())?;
+        let mut blocks = Vec::new();
+        let mut in_block = false;
+        for line in content.lines() {
+            match line {
+                "START TRANSACTION;" => {
+                    assert!(!in_block, "Nested transaction block found");
+                    in_block = true;
+                }
+                "COMMIT;" => {
+                    assert!(in_block, "COMMIT without START TRANSACTION");
+                    in_block = false;
+                }
+                _ if line.starts_with("-- Queries for file ") => {
+                    assert!(in_block, "File marker outside of transaction block");
+                    blocks.push(line.to_string());
+                }
+                _ => assert!(in_block, "Query outside of transaction block: {}", line),
+            }
+        }
+        assert!(!in_block, "Last transaction block is not committed");
+        assert_eq!(blocks.len(), list_of_files.len());
+        for file_name in &list_of_files {
+            let marker = format!("-- Queries for file {};", file_name);
+            assert!(blocks.contains(&marker), "Missing block for {}", file_name);
+        }
+        Ok(())
+    }
+}